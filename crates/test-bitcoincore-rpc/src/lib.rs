@@ -135,6 +135,7 @@ pub struct TransactionTemplate<'a> {
   pub op_return_index: Option<usize>,
   pub output_values: &'a [u64],
   pub outputs: usize,
+  pub recipient: Option<Address>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -181,6 +182,7 @@ impl<'a> Default for TransactionTemplate<'a> {
       op_return_index: None,
       output_values: &[],
       outputs: 1,
+      recipient: None,
     }
   }
 }
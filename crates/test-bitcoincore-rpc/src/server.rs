@@ -547,6 +547,17 @@ impl Api for Server {
     }
   }
 
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(
+      self
+        .state()
+        .mempool()
+        .iter()
+        .map(Transaction::txid)
+        .collect(),
+    )
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -594,7 +605,14 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match self.state().transactions.get(&txid) {
+      let state = self.state();
+
+      match state.transactions.get(&txid).or_else(|| {
+        state
+          .mempool()
+          .iter()
+          .find(|transaction| transaction.txid() == txid)
+      }) {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -849,15 +867,23 @@ impl Api for Server {
     .unwrap();
 
     for (i, txin) in psbt.unsigned_tx.input.iter().enumerate() {
-      psbt.inputs[i].witness_utxo = Some(
-        self
-          .state()
-          .transactions
-          .get(&txin.previous_output.txid)
-          .unwrap()
-          .output[txin.previous_output.vout as usize]
-          .clone(),
-      );
+      let state = self.state();
+
+      if let Some(transaction) = state
+        .transactions
+        .get(&txin.previous_output.txid)
+        .or_else(|| {
+          state
+            .mempool
+            .iter()
+            .find(|tx| tx.txid() == txin.previous_output.txid)
+        })
+      {
+        psbt.inputs[i].witness_utxo =
+          Some(transaction.output[txin.previous_output.vout as usize].clone());
+      }
+
+      assert!(psbt.inputs[i].witness_utxo.is_some());
     }
 
     if let Some(sign) = sign {
@@ -106,6 +106,7 @@ impl State {
     for tx in block.txdata.iter() {
       for input in tx.input.iter() {
         self.utxos.remove(&input.previous_output);
+        self.locked.remove(&input.previous_output);
       }
 
       for (vout, txout) in tx.output.iter().enumerate() {
@@ -173,7 +174,11 @@ impl State {
             .get(i)
             .cloned()
             .unwrap_or(value_per_output),
-          script_pubkey: script::Builder::new().into_script(),
+          script_pubkey: template
+            .recipient
+            .as_ref()
+            .map(|recipient| recipient.script_pubkey())
+            .unwrap_or_else(|| script::Builder::new().into_script()),
         })
         .collect(),
     };
@@ -1,16 +1,28 @@
 use {
   super::{
-    target_as_block_hash, BlockHash, Chain, Deserialize, Height, InscriptionId, OutPoint, Pile,
-    Rarity, SatPoint, Serialize, SpacedRune, TxMerkleNode, TxOut,
+    target_as_block_hash, BTreeMap, BlockHash, Chain, DateTime, Deserialize, Height, InscriptionId,
+    OutPoint, Pile, Rarity, RuneActivity, RuneId, SatPoint, Serialize, SpacedRune, TxMerkleNode,
+    TxOut, Utc,
   },
   serde_hex::{SerHex, Strict},
 };
 
 pub use crate::templates::{
-  BlocksHtml as Blocks, RuneHtml as Rune, RunesHtml as Runes, StatusHtml as Status,
-  TransactionHtml as Transaction,
+  BlocksHtml as Blocks, Concentration, InscriptionHistoryHtml as InscriptionHistory,
+  InscriptionTransfer, MempoolHtml as Mempool, PendingRuneTransfer, PendingTransaction,
+  PendingTransfer, RuneHolder, RuneHoldersHtml as RuneHolders, RuneHtml as Rune,
+  RunesHtml as Runes, StatusHtml as Status, TransactionHtml as Transaction,
 };
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressInfo {
+  pub outputs: Vec<OutPoint>,
+  pub inscriptions: Vec<InscriptionId>,
+  pub sat_balance: u64,
+  pub runes_balances: Vec<(SpacedRune, Pile)>,
+  pub sat_ranges: Option<Vec<(u64, u64)>>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Block {
   pub hash: BlockHash,
@@ -18,6 +30,7 @@ pub struct Block {
   pub best_height: u32,
   pub height: u32,
   pub inscriptions: Vec<InscriptionId>,
+  pub runes: Vec<(SpacedRune, RuneActivity)>,
 }
 
 impl Block {
@@ -26,6 +39,7 @@ impl Block {
     height: Height,
     best_height: Height,
     inscriptions: Vec<InscriptionId>,
+    runes: Vec<(SpacedRune, RuneActivity)>,
   ) -> Self {
     Self {
       hash: block.header.block_hash(),
@@ -33,6 +47,7 @@ impl Block {
       height: height.0,
       best_height: best_height.0,
       inscriptions,
+      runes,
     }
   }
 }
@@ -89,6 +104,7 @@ pub struct Inscription {
   pub next: Option<InscriptionId>,
   pub number: i32,
   pub parent: Option<InscriptionId>,
+  pub pending_transfer: Option<PendingTransfer>,
   pub previous: Option<InscriptionId>,
   pub rune: Option<SpacedRune>,
   pub sat: Option<ordinals::Sat>,
@@ -97,6 +113,16 @@ pub struct Inscription {
   pub value: Option<u64>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingInscription {
+  pub content_length: Option<usize>,
+  pub content_type: Option<String>,
+  pub delegate: Option<InscriptionId>,
+  pub id: InscriptionId,
+  pub metaprotocol: Option<String>,
+  pub parent: Option<InscriptionId>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct InscriptionRecursive {
   pub charms: Vec<String>,
@@ -161,6 +187,24 @@ impl Output {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneAvailability {
+  pub available: bool,
+  pub etched: Option<RuneId>,
+  pub reserved: bool,
+  pub rune: SpacedRune,
+  pub unlock_height: Option<u32>,
+  pub unlock_time: Option<DateTime<Utc>>,
+  pub unlocked: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneLedger {
+  pub activity: BTreeMap<u32, RuneActivity>,
+  pub id: RuneId,
+  pub rune: SpacedRune,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sat {
   pub number: u64,
@@ -2,15 +2,17 @@ use {
   self::{
     entry::{
       Entry, HeaderValue, InscriptionEntry, InscriptionEntryValue, InscriptionIdValue,
-      OutPointValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange, TxidValue,
+      OutPointValue, ReorgEntryValue, RuneActivityValue, RuneEntryValue, RuneIdValue,
+      SatPointValue, SatRange, TransferEntry, TxidValue,
     },
-    event::Event,
+    event::{Event, WebhookEvent},
     reorg::*,
     runes::{Rune, RuneId},
     updater::Updater,
   },
   super::*,
   crate::{
+    api,
     subcommand::{find::FindRangeOutput, server::query},
     templates::StatusHtml,
   },
@@ -25,7 +27,7 @@ use {
   redb::{
     Database, DatabaseError, MultimapTable, MultimapTableDefinition, MultimapTableHandle,
     ReadOnlyTable, ReadableMultimapTable, ReadableTable, RepairSession, StorageError, Table,
-    TableDefinition, TableHandle, TableStats, WriteTransaction,
+    TableDefinition, TableError, TableHandle, TableStats, WriteTransaction,
   },
   std::{
     collections::HashMap,
@@ -34,19 +36,27 @@ use {
   },
 };
 
-pub use {self::entry::RuneEntry, entry::MintEntry};
+pub use {
+  self::entry::{ReorgEntry, RuneActivity, RuneEntry},
+  entry::MintEntry,
+};
 
 pub(crate) mod entry;
 pub mod event;
 mod fetcher;
+mod migrations;
 mod reorg;
 mod rtx;
+mod snapshot;
 mod updater;
+mod verify;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 18;
+const SCHEMA_VERSION: u64 = 24;
+
+const RUNE_HOLDERS_CACHE_SIZE: usize = 16;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -61,8 +71,11 @@ macro_rules! define_multimap_table {
   };
 }
 
+define_multimap_table! { HEIGHT_TO_RUNE_ID, u32, RuneIdValue }
+define_multimap_table! { RUNE_ID_TO_OUTPOINT, RuneIdValue, &OutPointValue }
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], &OutPointValue }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
 define_table! { CONTENT_TYPE_TO_COUNT, Option<&[u8]>, u64 }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
@@ -72,16 +85,22 @@ define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_TXOUT, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
+define_table! { REORG_NUMBER_TO_REORG_ENTRY, u32, ReorgEntryValue }
+define_table! { RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY, (RuneIdValue, u32), RuneActivityValue }
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
 define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
+define_table! { SEQUENCE_NUMBER_AND_TRANSFER_NUMBER_TO_TRANSFER, (u32, u32), &[u8] }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
+define_table! { WEBHOOK_SEQUENCE_NUMBER_TO_EVENT, u64, &[u8] }
+define_table! { WEBHOOK_URL_TO_CURSOR, &str, u64 }
 define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
 
 #[derive(Copy, Clone)]
@@ -101,6 +120,9 @@ pub(crate) enum Statistic {
   IndexTransactions = 12,
   IndexSpentSats = 13,
   InitialSyncTime = 14,
+  IndexAddresses = 15,
+  WebhookEvents = 16,
+  IndexTransfers = 17,
 }
 
 impl Statistic {
@@ -126,6 +148,7 @@ pub(crate) struct Info {
   metadata_bytes: u64,
   outputs_traversed: u64,
   page_size: usize,
+  reorgs: Vec<ReorgEntry>,
   sat_ranges: u64,
   stored_bytes: u64,
   tables: BTreeMap<String, TableInfo>,
@@ -181,6 +204,16 @@ pub(crate) struct InscriptionInfo {
   pub(crate) charms: u16,
 }
 
+pub(crate) struct RuneHolders {
+  pub(crate) balances: Vec<(OutPoint, u128)>,
+  pub(crate) held: u128,
+}
+
+pub(crate) struct PendingRunes {
+  pub(crate) burned: BTreeMap<SpacedRune, Pile>,
+  pub(crate) outputs: Vec<BTreeMap<SpacedRune, Pile>>,
+}
+
 pub(crate) trait BitcoinCoreRpcResultExt<T> {
   fn into_option(self) -> Result<Option<T>>;
 }
@@ -213,12 +246,17 @@ pub struct Index {
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
   index_runes: bool,
   index_sats: bool,
   index_spent_sats: bool,
   index_transactions: bool,
+  index_transfers: bool,
+  index_webhook_events: bool,
   settings: Settings,
   path: PathBuf,
+  read_only: bool,
+  rune_holders: Mutex<HashMap<RuneId, (BlockHash, Arc<RuneHolders>)>>,
   started: DateTime<Utc>,
   unrecoverably_reorged: AtomicBool,
 }
@@ -231,6 +269,18 @@ impl Index {
   pub fn open_with_event_sender(
     settings: &Settings,
     event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  ) -> Result<Self> {
+    Index::open_with_options(settings, event_sender, false)
+  }
+
+  pub fn open_read_only(settings: &Settings) -> Result<Self> {
+    Index::open_with_options(settings, None, true)
+  }
+
+  fn open_with_options(
+    settings: &Settings,
+    event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+    read_only: bool,
   ) -> Result<Self> {
     let client = settings.bitcoin_rpc_client(None)?;
 
@@ -292,6 +342,11 @@ impl Index {
             .unwrap_or(0);
 
           match schema_version.cmp(&SCHEMA_VERSION) {
+            cmp::Ordering::Less if migrations::plan(schema_version).is_ok() =>
+              bail!(
+                "index at `{}` was built with an older version of ord and can be migrated in place, run `ord index migrate` to upgrade it: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
+                path.display()
+              ),
             cmp::Ordering::Less =>
               bail!(
                 "index at `{}` appears to have been built with an older, incompatible version of ord, consider deleting and rebuilding the index: index schema {schema_version}, ord schema {SCHEMA_VERSION}",
@@ -309,6 +364,18 @@ impl Index {
 
         database
       }
+      Err(DatabaseError::Storage(StorageError::Io(error)))
+        if read_only && error.kind() == io::ErrorKind::NotFound =>
+      {
+        bail!(
+          "no index at `{}`, read-only servers need an existing index, e.g. from `ord index restore`",
+          path.display()
+        )
+      }
+      Err(DatabaseError::DatabaseAlreadyOpen) if read_only => bail!(
+        "index at `{}` is already open in another process, read-only servers need their own copy of the index, e.g. from `ord index snapshot`",
+        path.display()
+      ),
       Err(DatabaseError::Storage(StorageError::Io(error)))
         if error.kind() == io::ErrorKind::NotFound =>
       {
@@ -320,8 +387,11 @@ impl Index {
 
         tx.set_durability(durability);
 
+        tx.open_multimap_table(HEIGHT_TO_RUNE_ID)?;
+        tx.open_multimap_table(RUNE_ID_TO_OUTPOINT)?;
         tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
         tx.open_table(CONTENT_TYPE_TO_COUNT)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
@@ -330,14 +400,20 @@ impl Index {
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_TXOUT)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
+        tx.open_table(REORG_NUMBER_TO_REORG_ENTRY)?;
+        tx.open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
+        tx.open_table(SEQUENCE_NUMBER_AND_TRANSFER_NUMBER_TO_TRANSFER)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
         tx.open_table(TRANSACTION_ID_TO_RUNE)?;
+        tx.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_EVENT)?;
+        tx.open_table(WEBHOOK_URL_TO_CURSOR)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
         {
@@ -348,6 +424,12 @@ impl Index {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexAddresses,
+            u64::from(settings.index_addresses()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexRunes,
@@ -372,6 +454,12 @@ impl Index {
             u64::from(settings.index_transactions()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexTransfers,
+            u64::from(settings.index_transfers()),
+          )?;
+
           Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
         }
 
@@ -382,18 +470,22 @@ impl Index {
       Err(error) => bail!("failed to open index: {error}"),
     };
 
+    let index_addresses;
     let index_runes;
     let index_sats;
     let index_spent_sats;
     let index_transactions;
+    let index_transfers;
 
     {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+      index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
       index_spent_sats = Self::is_statistic_set(&statistics, Statistic::IndexSpentSats)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+      index_transfers = Self::is_statistic_set(&statistics, Statistic::IndexTransfers)?;
     }
 
     let genesis_block_coinbase_transaction =
@@ -408,12 +500,17 @@ impl Index {
       first_inscription_height: settings.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
+      index_addresses,
       index_runes,
       index_sats,
       index_spent_sats,
       index_transactions,
+      index_transfers,
+      index_webhook_events: !settings.webhooks().is_empty(),
       settings: settings.clone(),
       path,
+      read_only,
+      rune_holders: Mutex::new(HashMap::new()),
       started: Utc::now(),
       unrecoverably_reorged: AtomicBool::new(false),
     })
@@ -435,10 +532,135 @@ impl Index {
     )
   }
 
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
   pub(crate) fn has_rune_index(&self) -> bool {
     self.index_runes
   }
 
+  pub(crate) fn has_transfer_index(&self) -> bool {
+    self.index_transfers
+  }
+
+  fn queue_webhook_events(
+    wtx: &WriteTransaction,
+    statistic_to_count: &mut Table<u64, u64>,
+    events: Vec<WebhookEvent>,
+  ) -> Result {
+    if events.is_empty() {
+      return Ok(());
+    }
+
+    let mut webhook_sequence_number_to_event = wtx.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_EVENT)?;
+
+    let mut next = statistic_to_count
+      .get(&Statistic::WebhookEvents.key())?
+      .map(|x| x.value())
+      .unwrap_or_default();
+
+    for event in events {
+      webhook_sequence_number_to_event.insert(&next, serde_json::to_vec(&event)?.as_slice())?;
+      next += 1;
+    }
+
+    statistic_to_count.insert(&Statistic::WebhookEvents.key(), &next)?;
+
+    Ok(())
+  }
+
+  pub(crate) fn webhook_events(
+    &self,
+    cursor: u64,
+    limit: usize,
+  ) -> Result<Vec<(u64, WebhookEvent)>> {
+    let rtx = self.database.begin_read()?;
+
+    let webhook_sequence_number_to_event = match rtx.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_EVENT) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let events = webhook_sequence_number_to_event
+      .range(cursor..)?
+      .take(limit)
+      .map(|result| {
+        let (sequence_number, event) = result?;
+        Ok((
+          sequence_number.value(),
+          serde_json::from_slice(event.value())?,
+        ))
+      })
+      .collect::<Result<Vec<(u64, WebhookEvent)>>>()?;
+
+    Ok(events)
+  }
+
+  pub(crate) fn webhook_cursor(&self, url: &str) -> Result<u64> {
+    let rtx = self.database.begin_read()?;
+
+    let webhook_url_to_cursor = match rtx.open_table(WEBHOOK_URL_TO_CURSOR) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(0),
+      Err(err) => return Err(err.into()),
+    };
+
+    let cursor = webhook_url_to_cursor
+      .get(url)?
+      .map(|cursor| cursor.value())
+      .unwrap_or_default();
+
+    Ok(cursor)
+  }
+
+  /// Also prunes events that every configured webhook has received.
+  pub(crate) fn set_webhook_cursor(&self, url: &str, cursor: u64) -> Result {
+    let wtx = self.begin_write()?;
+
+    {
+      let mut webhook_url_to_cursor = wtx.open_table(WEBHOOK_URL_TO_CURSOR)?;
+
+      webhook_url_to_cursor.insert(url, &cursor)?;
+
+      let mut delivered = u64::MAX;
+      for webhook in self.settings.webhooks() {
+        delivered = delivered.min(
+          webhook_url_to_cursor
+            .get(webhook.url.as_str())?
+            .map(|cursor| cursor.value())
+            .unwrap_or_default(),
+        );
+      }
+
+      wtx
+        .open_table(WEBHOOK_SEQUENCE_NUMBER_TO_EVENT)?
+        .drain(..delivered)?;
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn reorgs(&self) -> Result<Vec<ReorgEntry>> {
+    let rtx = self.database.begin_read()?;
+
+    let reorg_number_to_reorg_entry = match rtx.open_table(REORG_NUMBER_TO_REORG_ENTRY) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+      Err(err) => return Err(err.into()),
+    };
+
+    let reorgs = reorg_number_to_reorg_entry
+      .iter()?
+      .map(|result| result.map(|(_number, entry)| ReorgEntry::load(entry.value())))
+      .collect::<Result<Vec<ReorgEntry>, StorageError>>()?;
+
+    Ok(reorgs)
+  }
+
   pub(crate) fn has_sat_index(&self) -> bool {
     self.index_sats
   }
@@ -493,6 +715,16 @@ impl Index {
         self.settings.chain(),
         Height(next_height),
       ),
+      reorgs: self.reorgs()?,
+      replica_lag: if self.read_only {
+        self.client.get_block_count().ok().map(|height| {
+          u32::try_from(height + 1)
+            .unwrap_or(u32::MAX)
+            .saturating_sub(next_height)
+        })
+      } else {
+        None
+      },
       rune_index: statistic(Statistic::IndexRunes)? != 0,
       runes: statistic(Statistic::Runes)?,
       sat_index: statistic(Statistic::IndexSats)? != 0,
@@ -504,7 +736,7 @@ impl Index {
   }
 
   pub(crate) fn info(&self) -> Result<Info> {
-    let stats = self.database.begin_write()?.stats()?;
+    let stats = self.begin_write()?.stats()?;
 
     let rtx = self.database.begin_read()?;
 
@@ -566,6 +798,7 @@ impl Index {
         sat_ranges,
         outputs_traversed,
         page_size: stats.page_size(),
+        reorgs: self.reorgs()?,
         stored_bytes: stats.stored_bytes(),
         total_bytes,
         tables,
@@ -704,6 +937,7 @@ impl Index {
   }
 
   fn begin_write(&self) -> Result<WriteTransaction> {
+    ensure!(!self.read_only, "index is read-only");
     let mut tx = self.database.begin_write()?;
     tx.set_durability(self.durability);
     Ok(tx)
@@ -871,20 +1105,127 @@ impl Index {
     Ok(Some((RuneId::load(id), entry, parent)))
   }
 
-  pub(crate) fn runes(&self) -> Result<Vec<(RuneId, RuneEntry)>> {
-    let mut entries = Vec::new();
+  pub(crate) fn rune_availability(
+    &self,
+    runes: &[SpacedRune],
+  ) -> Result<Vec<api::RuneAvailability>> {
+    let rtx = self.database.begin_read()?;
 
-    for result in self
-      .database
-      .begin_read()?
-      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
-      .iter()?
-    {
-      let (id, entry) = result?;
-      entries.push((RuneId::load(id.value()), RuneEntry::load(entry.value())));
+    let rune_to_rune_id = rtx.open_table(RUNE_TO_RUNE_ID)?;
+    let height_to_block_header = rtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+
+    let tip = height_to_block_header
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, header)| (height.value(), Header::load(*header.value()).time));
+
+    let next = tip.map(|(height, _)| height + 1).unwrap_or_default();
+
+    let chain = self.settings.chain();
+
+    let mut availability = Vec::new();
+
+    for spaced_rune in runes {
+      let rune = spaced_rune.rune;
+
+      let etched = rune_to_rune_id
+        .get(rune.0)?
+        .map(|guard| RuneId::load(guard.value()));
+
+      let unlock_height = rune.unlock_height(chain);
+
+      let unlock_time = match (unlock_height, tip) {
+        (Some(height), _) if height.n() < next => height_to_block_header
+          .get(height.n())?
+          .map(|header| timestamp(Header::load(*header.value()).time)),
+        (Some(height), Some((tip_height, tip_time))) => Some(
+          timestamp(tip_time)
+            .checked_add_signed(
+              chrono::Duration::try_seconds(10 * 60 * i64::from(height.n() - tip_height))
+                .context("timestamp out of range")?,
+            )
+            .context("timestamp out of range")?,
+        ),
+        _ => None,
+      };
+
+      let unlocked = unlock_height.map_or(false, |height| height.n() <= next);
+
+      availability.push(api::RuneAvailability {
+        available: etched.is_none() && unlocked,
+        etched,
+        reserved: rune.is_reserved(),
+        rune: *spaced_rune,
+        unlock_height: unlock_height.map(|height| height.n()),
+        unlock_time,
+        unlocked,
+      });
     }
 
-    Ok(entries)
+    Ok(availability)
+  }
+
+  /// Sorting by number reads rune entries in ID order, only up to the end of the
+  /// page. Other sorts read every entry.
+  pub(crate) fn runes_matching(
+    &self,
+    query: &subcommand::runes::Runes,
+  ) -> Result<(Vec<(RuneId, RuneEntry)>, bool)> {
+    let rtx = self.database.begin_read()?;
+
+    let (height, timestamp) = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, header)| (height.value() + 1, Header::load(*header.value()).time))
+      .unwrap_or_default();
+
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let iter = rune_id_to_rune_entry.iter()?;
+
+    let iter: Box<dyn Iterator<Item = _>> = match query.order.unwrap_or_default() {
+      subcommand::runes::Order::Asc => Box::new(iter),
+      subcommand::runes::Order::Desc => Box::new(iter.rev()),
+    };
+
+    let mut matching = iter
+      .map(|result| {
+        result.map(|(id, entry)| (RuneId::load(id.value()), RuneEntry::load(entry.value())))
+      })
+      .filter(|result| {
+        result
+          .as_ref()
+          .map_or(true, |(_, entry)| query.matches(entry, height, timestamp))
+      });
+
+    if query.sort.unwrap_or_default() == subcommand::runes::Sort::Number {
+      let Some(page) = query.page else {
+        return Ok((matching.collect::<Result<_, _>>()?, false));
+      };
+
+      let start = usize::try_from(page)
+        .unwrap()
+        .saturating_mul(subcommand::runes::Runes::PAGE_SIZE);
+
+      let entries = matching
+        .by_ref()
+        .skip(start)
+        .take(subcommand::runes::Runes::PAGE_SIZE)
+        .collect::<Result<Vec<_>, _>>()?;
+
+      let more = matching.next().transpose()?.is_some();
+
+      return Ok((entries, more));
+    }
+
+    let mut entries = matching.collect::<Result<Vec<_>, _>>()?;
+
+    query.sort(&mut entries);
+
+    Ok(query.paginate(entries))
   }
 
   pub(crate) fn get_rune_balances_for_outpoint(
@@ -928,6 +1269,311 @@ impl Index {
     Ok(balances)
   }
 
+  /// Where the runes spent, claimed, or etched by `tx` would go if it were
+  /// mined in the next block.
+  pub(crate) fn get_pending_runes(
+    &self,
+    tx: &Transaction,
+    runestone: Option<&Runestone>,
+  ) -> Result<PendingRunes> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let tip = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, header)| (height.value(), Header::load(*header.value())));
+
+    let height = tip.map(|(height, _header)| height + 1).unwrap_or(0);
+
+    // the next block's timestamp isn't known yet, so use the chain tip's
+    let timestamp = tip.map(|(_height, header)| header.time).unwrap_or(0);
+
+    let mut runes = HashMap::<u128, (SpacedRune, Pile)>::new();
+    let mut unallocated = HashMap::<u128, u128>::new();
+
+    let mut load = |id: u128| -> Result<Option<RuneEntry>> {
+      let Ok(rune_id) = RuneId::try_from(id) else {
+        return Ok(None);
+      };
+
+      let Some(entry) = id_to_rune_entries
+        .get(rune_id.store())?
+        .map(|entry| RuneEntry::load(entry.value()))
+      else {
+        return Ok(None);
+      };
+
+      runes.insert(id, (entry.spaced_rune(), entry.pile(0)));
+
+      Ok(Some(entry))
+    };
+
+    for input in &tx.input {
+      let Some(balances) = outpoint_to_balances.get(&input.previous_output.store())? else {
+        continue;
+      };
+
+      let buffer = balances.value();
+      let mut i = 0;
+      while i < buffer.len() {
+        let (id, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+        let (amount, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+
+        load(id)?;
+
+        *unallocated.entry(id).or_default() += amount;
+      }
+    }
+
+    let mut etched = None;
+
+    if let Some(runestone) = runestone {
+      if let Some(id) = runestone.claim {
+        if let Some(entry) = load(id)?.filter(|entry| entry.mintable(height, timestamp)) {
+          *unallocated.entry(id).or_default() +=
+            entry.mint.unwrap().limit.unwrap_or(runes::MAX_LIMIT);
+        }
+      }
+
+      if let Some(etching) = runestone.etching {
+        let rune = match etching.rune {
+          Some(rune) => (rune >= Rune::minimum_at_height(self.settings.chain(), Height(height))
+            && !rune.is_reserved()
+            && rtx.open_table(RUNE_TO_RUNE_ID)?.get(rune.0)?.is_none())
+          .then_some(rune),
+          None => Some(Rune::reserved(
+            rtx
+              .open_table(STATISTIC_TO_COUNT)?
+              .get(&Statistic::ReservedRunes.key())?
+              .map(|count| count.value())
+              .unwrap_or_default()
+              .into(),
+          )),
+        };
+
+        if let Some(rune) = rune {
+          // the transaction's position in the block isn't known yet, but
+          // nothing has been etched at index 0 of the next block
+          let id = u128::from(height) << 16;
+
+          runes.insert(
+            id,
+            (
+              SpacedRune {
+                rune,
+                spacers: etching.spacers,
+              },
+              Pile {
+                amount: 0,
+                divisibility: etching.divisibility,
+                symbol: etching.symbol,
+              },
+            ),
+          );
+
+          etched = Some((id, etching.balance()));
+        }
+      }
+    }
+
+    let allocation = runes::Allocation::new(tx, runestone, unallocated, etched);
+
+    let piles = |balances: HashMap<u128, u128>| {
+      balances
+        .into_iter()
+        .map(|(id, amount)| {
+          let (spaced_rune, pile) = runes[&id];
+          (spaced_rune, Pile { amount, ..pile })
+        })
+        .collect::<BTreeMap<SpacedRune, Pile>>()
+    };
+
+    Ok(PendingRunes {
+      burned: piles(allocation.burned),
+      outputs: allocation.outputs.into_iter().map(piles).collect(),
+    })
+  }
+
+  pub(crate) fn get_rune_activity(
+    &self,
+    id: RuneId,
+    from: u32,
+    to: u32,
+  ) -> Result<BTreeMap<u32, RuneActivity>> {
+    let mut activity = BTreeMap::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?
+      .range((id.store(), from)..=(id.store(), to))?
+    {
+      let (key, value) = result?;
+      activity.insert(key.value().1, RuneActivity::load(value.value()));
+    }
+
+    Ok(activity)
+  }
+
+  pub(crate) fn get_rune_activity_in_block(
+    &self,
+    height: u32,
+  ) -> Result<Vec<(RuneEntry, RuneActivity)>> {
+    let rtx = self.database.begin_read()?;
+
+    let rune_id_and_height_to_rune_activity =
+      rtx.open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?;
+    let rune_id_to_rune_entry = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+
+    let mut runes = Vec::new();
+
+    for result in rtx.open_multimap_table(HEIGHT_TO_RUNE_ID)?.get(height)? {
+      let id = result?.value();
+
+      let entry = rune_id_to_rune_entry
+        .get(id)?
+        .ok_or_else(|| anyhow!("missing rune entry for rune {}", RuneId::load(id)))?;
+
+      let activity = rune_id_and_height_to_rune_activity
+        .get((id, height))?
+        .ok_or_else(|| {
+          anyhow!(
+            "missing activity for rune {} at height {height}",
+            RuneId::load(id)
+          )
+        })?;
+
+      runes.push((
+        RuneEntry::load(entry.value()),
+        RuneActivity::load(activity.value()),
+      ));
+    }
+
+    Ok(runes)
+  }
+
+  /// Holders only change when a block is indexed, so the sorted holders of
+  /// recently requested runes are kept until the tip changes.
+  pub(crate) fn get_rune_holders(&self, id: RuneId) -> Result<Arc<RuneHolders>> {
+    let rtx = self.database.begin_read()?;
+
+    let tip = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(_height, header)| Header::load(*header.value()).block_hash());
+
+    if let Some((hash, holders)) = self.rune_holders.lock().unwrap().get(&id) {
+      if Some(*hash) == tip {
+        return Ok(holders.clone());
+      }
+    }
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let mut balances = Vec::new();
+
+    for result in rtx
+      .open_multimap_table(RUNE_ID_TO_OUTPOINT)?
+      .get(id.store())?
+    {
+      let outpoint = OutPoint::load(*result?.value());
+
+      let buffer = outpoint_to_balances
+        .get(&outpoint.store())?
+        .ok_or_else(|| anyhow!("missing rune balances for outpoint {outpoint}"))?;
+
+      let buffer = buffer.value();
+
+      let mut i = 0;
+      while i < buffer.len() {
+        let (balance_id, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+        let (balance, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+
+        if RuneId::try_from(balance_id)? == id {
+          balances.push((outpoint, balance));
+        }
+      }
+    }
+
+    balances.sort_by(|(a_outpoint, a_balance), (b_outpoint, b_balance)| {
+      b_balance
+        .cmp(a_balance)
+        .then_with(|| a_outpoint.cmp(b_outpoint))
+    });
+
+    let holders = Arc::new(RuneHolders {
+      held: balances.iter().map(|(_outpoint, balance)| balance).sum(),
+      balances,
+    });
+
+    if let Some(tip) = tip {
+      let mut cache = self.rune_holders.lock().unwrap();
+
+      cache.retain(|_id, (hash, _holders)| *hash == tip);
+
+      if cache.len() >= RUNE_HOLDERS_CACHE_SIZE {
+        cache.clear();
+      }
+
+      cache.insert(id, (tip, holders.clone()));
+    }
+
+    Ok(holders)
+  }
+
+  pub(crate) fn get_output_address(&self, outpoint: OutPoint) -> Result<Option<Address>> {
+    if !self.index_addresses {
+      return Ok(None);
+    }
+
+    let Some(txout) = self
+      .database
+      .begin_read()?
+      .open_table(OUTPOINT_TO_TXOUT)?
+      .get(&outpoint.store())?
+      .map(|txout| consensus::encode::deserialize::<TxOut>(txout.value()))
+      .transpose()?
+    else {
+      return Ok(None);
+    };
+
+    Ok(
+      self
+        .settings
+        .chain()
+        .address_from_script(&txout.script_pubkey)
+        .ok(),
+    )
+  }
+
+  #[cfg(test)]
+  pub(crate) fn runes(&self) -> Result<Vec<(RuneId, RuneEntry)>> {
+    let mut entries = Vec::new();
+
+    for result in self
+      .database
+      .begin_read()?
+      .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+      .iter()?
+    {
+      let (id, entry) = result?;
+      entries.push((RuneId::load(id.value()), RuneEntry::load(entry.value())));
+    }
+
+    Ok(entries)
+  }
+
   pub(crate) fn get_rune_balance_map(&self) -> Result<BTreeMap<Rune, BTreeMap<OutPoint, u128>>> {
     let outpoint_balances = self.get_rune_balances()?;
 
@@ -987,6 +1633,29 @@ impl Index {
     Ok(result)
   }
 
+  pub(crate) fn get_address_info(&self, address: &Address) -> Result<Vec<(OutPoint, TxOut)>> {
+    let rtx = self.database.begin_read()?;
+
+    let script_pubkey_to_outpoint = rtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+    let outpoint_to_txout = rtx.open_table(OUTPOINT_TO_TXOUT)?;
+
+    let mut outputs = Vec::new();
+
+    for result in script_pubkey_to_outpoint.get(address.script_pubkey().as_bytes())? {
+      let outpoint = OutPoint::load(*result?.value());
+
+      let txout = outpoint_to_txout
+        .get(&outpoint.store())?
+        .map(|txout| consensus::encode::deserialize(txout.value()))
+        .transpose()?
+        .ok_or_else(|| anyhow!("missing txout for outpoint {outpoint}"))?;
+
+      outputs.push((outpoint, txout));
+    }
+
+    Ok(outputs)
+  }
+
   pub(crate) fn block_header(&self, hash: BlockHash) -> Result<Option<Header>> {
     self.client.get_block_header(&hash).into_option()
   }
@@ -1256,7 +1925,6 @@ impl Index {
     .transpose()
   }
 
-  #[cfg(test)]
   pub(crate) fn get_inscription_id_by_inscription_number(
     &self,
     inscription_number: i32,
@@ -1396,6 +2064,10 @@ impl Index {
     self.client.get_raw_transaction(&txid, None).into_option()
   }
 
+  pub(crate) fn get_raw_mempool(&self) -> Result<Vec<Txid>> {
+    Ok(self.client.get_raw_mempool()?)
+  }
+
   pub(crate) fn find(&self, sat: Sat) -> Result<Option<SatPoint>> {
     let sat = sat.0;
     let rtx = self.begin_read()?;
@@ -1877,6 +2549,33 @@ impl Index {
     Ok(entry)
   }
 
+  pub(crate) fn get_inscription_transfers(
+    &self,
+    inscription_id: InscriptionId,
+  ) -> Result<Option<Vec<TransferEntry>>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?
+      .get(&inscription_id.store())?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let mut transfers = Vec::new();
+
+    for result in rtx
+      .open_table(SEQUENCE_NUMBER_AND_TRANSFER_NUMBER_TO_TRANSFER)?
+      .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+    {
+      let (_key, value) = result?;
+      transfers.push(TransferEntry::load(value.value().to_vec()));
+    }
+
+    Ok(Some(transfers))
+  }
+
   #[cfg(test)]
   fn assert_inscription_location(
     &self,
@@ -2006,7 +2705,13 @@ impl Index {
 
 #[cfg(test)]
 mod tests {
-  use {super::*, crate::index::testing::Context};
+  use {
+    super::*,
+    crate::{
+      index::testing::Context,
+      runes::{Edict, Etching, Mint},
+    },
+  };
 
   #[test]
   fn height_limit() {
@@ -2797,6 +3502,136 @@ mod tests {
     }
   }
 
+  #[test]
+  fn inscription_transfers_are_recorded_with_index_transfers() {
+    let context = Context::builder().arg("--index-transfers").build();
+
+    context.mine_blocks(1);
+
+    let inscribe = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId {
+      txid: inscribe,
+      index: 0,
+    };
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_transfers(inscription_id)
+        .unwrap(),
+      Some(Vec::new()),
+    );
+
+    let transfer = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    let transfer_output = context.mine_blocks(1)[0].txdata[1].output[0].clone();
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..Default::default()
+    });
+
+    let coinbase = context.mine_blocks(1)[0].txdata[0].clone();
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_transfers(inscription_id)
+        .unwrap(),
+      Some(vec![
+        TransferEntry {
+          height: 3,
+          new_satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: transfer,
+              vout: 0,
+            },
+            offset: 0,
+          },
+          old_satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: inscribe,
+              vout: 0,
+            },
+            offset: 0,
+          },
+          output: Some(transfer_output),
+          txid: transfer,
+        },
+        TransferEntry {
+          height: 4,
+          new_satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: coinbase.txid(),
+              vout: 0,
+            },
+            offset: 50 * COIN_VALUE,
+          },
+          old_satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: transfer,
+              vout: 0,
+            },
+            offset: 0,
+          },
+          output: Some(coinbase.output[0].clone()),
+          txid: coinbase.txid(),
+        },
+      ]),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_transfers(InscriptionId {
+          txid: inscribe,
+          index: 1,
+        })
+        .unwrap(),
+      None,
+    );
+  }
+
+  #[test]
+  fn inscription_transfers_are_not_recorded_without_index_transfers() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(!context.index.has_transfer_index());
+
+    assert_eq!(
+      context
+        .index
+        .get_inscription_transfers(InscriptionId { txid, index: 0 })
+        .unwrap(),
+      Some(Vec::new()),
+    );
+  }
+
   #[test]
   fn lost_inscriptions() {
     for context in Context::configurations() {
@@ -3117,6 +3952,117 @@ mod tests {
       format!("index at `{}{delimiter}regtest{delimiter}index.redb` appears to have been built with a newer, incompatible version of ord, consider updating ord: index schema {}, ord schema {SCHEMA_VERSION}", path.display(), u64::MAX));
   }
 
+  #[test]
+  fn migratable_schema_is_migrated_in_place() {
+    let Context {
+      index,
+      rpc_server,
+      tempdir,
+    } = Context::builder().build();
+
+    {
+      let wtx = index.database.begin_write().unwrap();
+
+      wtx.delete_multimap_table(HEIGHT_TO_RUNE_ID).unwrap();
+      wtx.delete_multimap_table(RUNE_ID_TO_OUTPOINT).unwrap();
+      wtx
+        .delete_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)
+        .unwrap();
+      wtx
+        .delete_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)
+        .unwrap();
+      wtx.delete_table(OUTPOINT_TO_TXOUT).unwrap();
+      wtx.delete_table(REORG_NUMBER_TO_REORG_ENTRY).unwrap();
+      wtx
+        .delete_table(SEQUENCE_NUMBER_AND_TRANSFER_NUMBER_TO_TRANSFER)
+        .unwrap();
+      wtx.delete_table(WEBHOOK_SEQUENCE_NUMBER_TO_EVENT).unwrap();
+      wtx.delete_table(WEBHOOK_URL_TO_CURSOR).unwrap();
+
+      let mut statistics = wtx.open_table(STATISTIC_TO_COUNT).unwrap();
+
+      statistics.insert(&Statistic::Schema.key(), &18).unwrap();
+      statistics.remove(&Statistic::IndexAddresses.key()).unwrap();
+
+      drop(statistics);
+
+      wtx.commit().unwrap();
+    }
+
+    drop(index);
+
+    let settings = Settings::from_options(
+      Options::try_parse_from([
+        "ord".into(),
+        "--regtest".into(),
+        "--bitcoin-rpc-url".into(),
+        rpc_server.url().into(),
+        "--cookie-file".into(),
+        tempdir.path().join("cookie").into_os_string(),
+        "--data-dir".into(),
+        tempdir.path().as_os_str().to_owned(),
+      ])
+      .unwrap(),
+    )
+    .or_defaults()
+    .unwrap();
+
+    assert_eq!(
+      Index::open(&settings).err().unwrap().to_string(),
+      format!(
+        "index at `{}` was built with an older version of ord and can be migrated in place, run `ord index migrate` to upgrade it: index schema 18, ord schema {SCHEMA_VERSION}",
+        settings.index().display(),
+      ),
+    );
+
+    let plan = Index::migrate(&settings, true).unwrap();
+
+    assert!(plan.dry_run);
+    assert_eq!(plan.from, 18);
+    assert_eq!(plan.to, SCHEMA_VERSION);
+    assert_eq!(
+      plan
+        .steps
+        .iter()
+        .map(|step| step.from)
+        .collect::<Vec<u64>>(),
+      (18..SCHEMA_VERSION).collect::<Vec<u64>>()
+    );
+
+    assert_eq!(Index::migrate(&settings, true).unwrap(), plan);
+
+    let output = Index::migrate(&settings, false).unwrap();
+
+    assert!(!output.dry_run);
+    assert_eq!(output.steps, plan.steps);
+
+    assert!(Index::migrate(&settings, false).unwrap().steps.is_empty());
+
+    let index = Index::open(&settings).unwrap();
+
+    assert!(!index.has_address_index());
+
+    {
+      let rtx = index.database.begin_read().unwrap();
+      rtx.open_table(OUTPOINT_TO_TXOUT).unwrap();
+      rtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT).unwrap();
+      assert_eq!(
+        rtx
+          .open_table(STATISTIC_TO_COUNT)
+          .unwrap()
+          .get(&Statistic::IndexAddresses.key())
+          .unwrap()
+          .unwrap()
+          .value(),
+        0,
+      );
+    }
+
+    index.update().unwrap();
+
+    assert!(index.reorgs().unwrap().is_empty());
+  }
+
   #[test]
   fn inscriptions_on_output() {
     for context in Context::configurations() {
@@ -5703,6 +6649,16 @@ mod tests {
 
     context.mine_blocks(1);
 
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted { block_height: 0 }
+    );
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted { block_height: 1 }
+    );
+
     let inscription = Inscription::default();
     let create_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(1, 0, 0, inscription.to_witness())],
@@ -5737,6 +6693,11 @@ mod tests {
       }
     );
 
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted { block_height: 2 }
+    );
+
     // Transfer inscription
     let transfer_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(2, 1, 0, Default::default())],
@@ -5770,5 +6731,394 @@ mod tests {
         sequence_number: 0,
       }
     );
+
+    assert_eq!(
+      event_receiver.blocking_recv().unwrap(),
+      Event::BlockCommitted { block_height: 3 }
+    );
+  }
+
+  #[test]
+  fn rune_holders_are_cached_until_the_tip_changes() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    context.mine_blocks(1);
+
+    let etch = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 1000,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(99246114928149462)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    let holders = context.index.get_rune_holders(id).unwrap();
+
+    assert_eq!(
+      holders.balances,
+      [(
+        OutPoint {
+          txid: etch,
+          vout: 0
+        },
+        1000
+      )]
+    );
+    assert_eq!(holders.held, 1000);
+
+    assert!(Arc::ptr_eq(
+      &holders,
+      &context.index.get_rune_holders(id).unwrap()
+    ));
+
+    let transfer = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.get_rune_holders(id).unwrap().balances,
+      [(
+        OutPoint {
+          txid: transfer,
+          vout: 0
+        },
+        1000
+      )]
+    );
+  }
+
+  #[test]
+  fn event_sender_channel_rune_events() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let context = Context::builder()
+      .arg("--index-runes")
+      .event_sender(event_sender)
+      .build();
+
+    context.mine_blocks(1);
+
+    let etch_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 0,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(99246114928149462)),
+            mint: Some(Mint {
+              limit: Some(1000),
+              ..Default::default()
+            }),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let rune_id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    let mint_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          claim: Some(u128::from(rune_id)),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let burn_txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          burn: true,
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_receiver.try_recv() {
+      events.push(event);
+    }
+
+    assert_eq!(
+      events,
+      [
+        Event::BlockCommitted { block_height: 0 },
+        Event::BlockCommitted { block_height: 1 },
+        Event::RuneEtched {
+          block_height: 2,
+          rune_id,
+          txid: etch_txid,
+        },
+        Event::RuneTransferred {
+          amount: 1000,
+          block_height: 2,
+          outpoint: OutPoint {
+            txid: etch_txid,
+            vout: 0,
+          },
+          rune_id,
+          txid: etch_txid,
+        },
+        Event::BlockCommitted { block_height: 2 },
+        Event::RuneMinted {
+          amount: 1000,
+          block_height: 3,
+          rune_id,
+          txid: mint_txid,
+        },
+        Event::RuneTransferred {
+          amount: 1000,
+          block_height: 3,
+          outpoint: OutPoint {
+            txid: mint_txid,
+            vout: 0,
+          },
+          rune_id,
+          txid: mint_txid,
+        },
+        Event::BlockCommitted { block_height: 3 },
+        Event::RuneBurned {
+          amount: 1000,
+          block_height: 4,
+          rune_id,
+          txid: burn_txid,
+        },
+        Event::BlockCommitted { block_height: 4 },
+      ]
+    );
+  }
+
+  #[test]
+  fn event_sender_channel_reorg_rolled_back() {
+    let (event_sender, mut event_receiver) = tokio::sync::mpsc::channel(1024);
+    let mut context = Context::builder().event_sender(event_sender).build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+    context.mine_blocks(6);
+    context.mine_blocks(1);
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_receiver.try_recv() {
+      events.push(event);
+    }
+
+    assert_eq!(
+      events,
+      [
+        Event::BlockCommitted { block_height: 0 },
+        Event::BlockCommitted { block_height: 1 },
+        Event::BlockCommitted { block_height: 2 },
+        Event::BlockCommitted { block_height: 3 },
+        Event::BlockCommitted { block_height: 4 },
+        Event::BlockCommitted { block_height: 5 },
+        Event::BlockCommitted { block_height: 6 },
+        Event::BlockCommitted { block_height: 7 },
+        Event::BlockCommitted { block_height: 8 },
+        Event::ReorgRolledBack {
+          block_count: 8,
+          depth: 2,
+          height: 9,
+        },
+        Event::BlockCommitted { block_height: 8 },
+        Event::BlockCommitted { block_height: 9 },
+      ]
+    );
+  }
+
+  #[test]
+  fn webhook_cursor_survives_reorg() {
+    let mut context = Context::builder()
+      .config("webhooks:\n- url: http://127.0.0.1:1\n")
+      .build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+    context.mine_blocks(6);
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.webhook_events(0, 100).unwrap().len(), 9);
+
+    context
+      .index
+      .set_webhook_cursor("http://127.0.0.1:1", 9)
+      .unwrap();
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context.index.webhook_cursor("http://127.0.0.1:1").unwrap(),
+      9
+    );
+
+    assert_eq!(
+      context
+        .index
+        .webhook_events(9, 100)
+        .unwrap()
+        .into_iter()
+        .map(|(sequence_number, event)| (sequence_number, event.event))
+        .collect::<Vec<(u64, Event)>>(),
+      [
+        (
+          9,
+          Event::ReorgRolledBack {
+            block_count: 8,
+            depth: 2,
+            height: 9,
+          }
+        ),
+        (10, Event::BlockCommitted { block_height: 8 }),
+        (11, Event::BlockCommitted { block_height: 9 }),
+      ]
+    );
+  }
+
+  #[test]
+  fn reorgs_are_recorded() {
+    let mut context = Context::builder().build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+    context.mine_blocks(6);
+    context.mine_blocks(1);
+
+    assert!(context.index.reorgs().unwrap().is_empty());
+
+    let old_tip = context.index.block_hash(Some(8)).unwrap().unwrap();
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let new_tip = context.index.block_hash(Some(8)).unwrap().unwrap();
+
+    assert_ne!(old_tip, new_tip);
+
+    let reorgs = context.index.reorgs().unwrap();
+
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(reorgs[0].depth, 2);
+    assert_eq!(reorgs[0].height, 9);
+    assert_eq!(reorgs[0].old_tip, old_tip);
+    assert_eq!(reorgs[0].new_tip, new_tip);
+
+    let old_tip = context.index.block_hash(Some(9)).unwrap().unwrap();
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let reorgs = context.index.reorgs().unwrap();
+
+    assert_eq!(reorgs.len(), 2);
+    assert_eq!(reorgs[0].height, 9);
+    assert_eq!(reorgs[1].height, 10);
+    assert_eq!(reorgs[1].old_tip, old_tip);
+    assert_eq!(
+      reorgs[1].new_tip,
+      context.index.block_hash(Some(9)).unwrap().unwrap()
+    );
+
+    assert_eq!(context.index.status().unwrap().reorgs, reorgs);
+  }
+
+  #[test]
+  fn max_savepoints_and_savepoint_interval_configure_recoverable_reorg_depth() {
+    let mut context = Context::builder().build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    for _ in 0..20 {
+      context.mine_blocks(1);
+    }
+
+    for _ in 0..12 {
+      context.rpc_server.invalidate_tip();
+    }
+
+    context.mine_blocks_with_update(14, false);
+
+    assert_eq!(
+      context
+        .index
+        .update()
+        .unwrap_err()
+        .downcast_ref::<ReorgError>(),
+      Some(&ReorgError::Unrecoverable),
+    );
+
+    let mut context = Context::builder()
+      .args(["--max-savepoints", "4", "--savepoint-interval", "5"])
+      .build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    for _ in 0..20 {
+      context.mine_blocks(1);
+    }
+
+    for _ in 0..12 {
+      context.rpc_server.invalidate_tip();
+    }
+
+    context.mine_blocks(14);
+
+    assert_eq!(context.index.block_count().unwrap(), 23);
+
+    let reorgs = context.index.reorgs().unwrap();
+
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(reorgs[0].depth, 13);
+    assert_eq!(reorgs[0].height, 21);
   }
 }
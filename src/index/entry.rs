@@ -77,6 +77,24 @@ impl RuneEntry {
       spacers: self.spacers,
     }
   }
+
+  pub(crate) fn pile(&self, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: self.divisibility,
+      symbol: self.symbol,
+    }
+  }
+
+  pub(crate) fn mintable(&self, height: u32, timestamp: u32) -> bool {
+    let Some(mint) = self.mint else {
+      return false;
+    };
+
+    mint.end.map_or(true, |end| height < end)
+      && mint.deadline.map_or(true, |deadline| timestamp < deadline)
+      && mint.limit != Some(0)
+  }
 }
 
 impl Default for RuneEntry {
@@ -178,6 +196,162 @@ impl Entry for RuneEntry {
   }
 }
 
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize)]
+pub struct ReorgEntry {
+  pub depth: u32,
+  pub height: u32,
+  pub new_tip: BlockHash,
+  pub old_tip: BlockHash,
+  pub timestamp: u32,
+}
+
+type BlockHashValue = (u128, u128);
+
+pub(super) type ReorgEntryValue = (
+  u32,            // depth
+  u32,            // height
+  BlockHashValue, // new tip
+  BlockHashValue, // old tip
+  u32,            // timestamp
+);
+
+fn load_block_hash((low, high): BlockHashValue) -> BlockHash {
+  let mut bytes = [0; 32];
+  bytes[..16].copy_from_slice(&low.to_le_bytes());
+  bytes[16..].copy_from_slice(&high.to_le_bytes());
+  BlockHash::from_byte_array(bytes)
+}
+
+fn store_block_hash(block_hash: BlockHash) -> BlockHashValue {
+  let bytes = block_hash.to_byte_array();
+  (
+    u128::from_le_bytes(bytes[..16].try_into().unwrap()),
+    u128::from_le_bytes(bytes[16..].try_into().unwrap()),
+  )
+}
+
+impl Entry for ReorgEntry {
+  type Value = ReorgEntryValue;
+
+  fn load((depth, height, new_tip, old_tip, timestamp): ReorgEntryValue) -> Self {
+    Self {
+      depth,
+      height,
+      new_tip: load_block_hash(new_tip),
+      old_tip: load_block_hash(old_tip),
+      timestamp,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.depth,
+      self.height,
+      store_block_hash(self.new_tip),
+      store_block_hash(self.old_tip),
+      self.timestamp,
+    )
+  }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone, Serialize, Deserialize, Default)]
+pub struct RuneActivity {
+  pub burned: u128,
+  pub etched: bool,
+  pub minted: u128,
+  pub mints: u64,
+  pub transferred: u128,
+  pub transfers: u64,
+}
+
+pub(super) type RuneActivityValue = (
+  u128, // burned
+  bool, // etched
+  u128, // minted
+  u64,  // mints
+  u128, // transferred
+  u64,  // transfers
+);
+
+impl Entry for RuneActivity {
+  type Value = RuneActivityValue;
+
+  fn load((burned, etched, minted, mints, transferred, transfers): RuneActivityValue) -> Self {
+    Self {
+      burned,
+      etched,
+      minted,
+      mints,
+      transferred,
+      transfers,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.burned,
+      self.etched,
+      self.minted,
+      self.mints,
+      self.transferred,
+      self.transfers,
+    )
+  }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct TransferEntry {
+  pub height: u32,
+  pub new_satpoint: SatPoint,
+  pub old_satpoint: SatPoint,
+  pub output: Option<TxOut>,
+  pub txid: Txid,
+}
+
+// consensus encoded height, txid, old satpoint, and new satpoint, followed by
+// the consensus encoded output, if any
+pub(super) type TransferEntryValue = Vec<u8>;
+
+impl Entry for TransferEntry {
+  type Value = TransferEntryValue;
+
+  fn load(value: TransferEntryValue) -> Self {
+    let length = value.len();
+    let mut cursor = Cursor::new(value);
+
+    let height = Decodable::consensus_decode(&mut cursor).unwrap();
+    let txid = Decodable::consensus_decode(&mut cursor).unwrap();
+    let old_satpoint = Decodable::consensus_decode(&mut cursor).unwrap();
+    let new_satpoint = Decodable::consensus_decode(&mut cursor).unwrap();
+
+    let output = (usize::try_from(cursor.position()).unwrap() < length)
+      .then(|| Decodable::consensus_decode(&mut cursor).unwrap());
+
+    Self {
+      height,
+      new_satpoint,
+      old_satpoint,
+      output,
+      txid,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    let mut value = Vec::new();
+
+    self.height.consensus_encode(&mut value).unwrap();
+    self.txid.consensus_encode(&mut value).unwrap();
+    self.old_satpoint.consensus_encode(&mut value).unwrap();
+    self.new_satpoint.consensus_encode(&mut value).unwrap();
+
+    if let Some(output) = self.output {
+      output.consensus_encode(&mut value).unwrap();
+    }
+
+    value
+  }
+}
+
 pub(super) type RuneIdValue = (u32, u16);
 
 impl Entry for RuneId {
@@ -486,6 +660,85 @@ mod tests {
     assert_eq!(RuneEntry::load(value), entry);
   }
 
+  #[test]
+  fn reorg_entry() {
+    let entry = ReorgEntry {
+      depth: 1,
+      height: 2,
+      new_tip: BlockHash::from_byte_array([
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E,
+        0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19, 0x1A, 0x1B, 0x1C, 0x1D,
+        0x1E, 0x1F,
+      ]),
+      old_tip: BlockHash::from_byte_array([0xFF; 32]),
+      timestamp: 3,
+    };
+
+    let value = (
+      1,
+      2,
+      (
+        0x0F0E0D0C0B0A09080706050403020100,
+        0x1F1E1D1C1B1A19181716151413121110,
+      ),
+      (u128::MAX, u128::MAX),
+      3,
+    );
+
+    assert_eq!(entry.store(), value);
+    assert_eq!(ReorgEntry::load(value), entry);
+  }
+
+  #[test]
+  fn rune_activity() {
+    let activity = RuneActivity {
+      burned: 1,
+      etched: true,
+      minted: 2,
+      mints: 3,
+      transferred: 4,
+      transfers: 5,
+    };
+
+    let value = (1, true, 2, 3, 4, 5);
+
+    assert_eq!(activity.store(), value);
+    assert_eq!(RuneActivity::load(value), activity);
+  }
+
+  #[test]
+  fn transfer_entry() {
+    let mut entry = TransferEntry {
+      height: 1,
+      new_satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: txid(2),
+          vout: 3,
+        },
+        offset: 4,
+      },
+      old_satpoint: SatPoint {
+        outpoint: OutPoint {
+          txid: txid(5),
+          vout: 6,
+        },
+        offset: 7,
+      },
+      output: Some(TxOut {
+        value: 8,
+        script_pubkey: ScriptBuf::from_bytes(vec![9, 10]),
+      }),
+      txid: txid(2),
+    };
+
+    assert_eq!(TransferEntry::load(entry.clone().store()), entry);
+
+    entry.output = None;
+
+    assert_eq!(entry.clone().store().len(), 4 + 32 + 44 + 44);
+    assert_eq!(TransferEntry::load(entry.clone().store()), entry);
+  }
+
   #[test]
   fn rune_id_entry() {
     assert_eq!(
@@ -520,4 +773,44 @@ mod tests {
 
     assert_eq!(actual, expected);
   }
+
+  #[test]
+  fn rune_entry_mintable() {
+    assert!(!RuneEntry::default().mintable(0, 0));
+
+    let entry = RuneEntry {
+      mint: Some(MintEntry {
+        deadline: Some(10),
+        end: Some(20),
+        limit: Some(1),
+      }),
+      ..Default::default()
+    };
+
+    assert!(entry.mintable(19, 9));
+    assert!(!entry.mintable(20, 9));
+    assert!(!entry.mintable(19, 10));
+
+    let entry = RuneEntry {
+      mint: Some(MintEntry {
+        deadline: None,
+        end: None,
+        limit: None,
+      }),
+      ..Default::default()
+    };
+
+    assert!(entry.mintable(u32::MAX, u32::MAX));
+
+    let entry = RuneEntry {
+      mint: Some(MintEntry {
+        deadline: None,
+        end: None,
+        limit: Some(0),
+      }),
+      ..Default::default()
+    };
+
+    assert!(!entry.mintable(0, 0));
+  }
 }
@@ -1,7 +1,13 @@
-use crate::{InscriptionId, SatPoint};
+use crate::{InscriptionId, RuneId, SatPoint};
+use bitcoin::{OutPoint, ScriptBuf, Txid};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Event {
+  BlockCommitted {
+    block_height: u32,
+  },
   InscriptionCreated {
     block_height: u32,
     charms: u16,
@@ -17,4 +23,100 @@ pub enum Event {
     old_location: SatPoint,
     sequence_number: u32,
   },
+  ReorgRolledBack {
+    block_count: u32,
+    depth: u32,
+    height: u32,
+  },
+  RuneBurned {
+    amount: u128,
+    block_height: u32,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+  RuneEtched {
+    block_height: u32,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+  RuneMinted {
+    amount: u128,
+    block_height: u32,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+  RuneTransferred {
+    amount: u128,
+    block_height: u32,
+    outpoint: OutPoint,
+    rune_id: RuneId,
+    txid: Txid,
+  },
+}
+
+impl Event {
+  pub(crate) const KINDS: &'static [&'static str] = &[
+    "block_committed",
+    "inscription_created",
+    "inscription_transferred",
+    "reorg_rolled_back",
+    "rune_burned",
+    "rune_etched",
+    "rune_minted",
+    "rune_transferred",
+  ];
+
+  pub(crate) fn kind(&self) -> &'static str {
+    match self {
+      Self::BlockCommitted { .. } => "block_committed",
+      Self::InscriptionCreated { .. } => "inscription_created",
+      Self::InscriptionTransferred { .. } => "inscription_transferred",
+      Self::ReorgRolledBack { .. } => "reorg_rolled_back",
+      Self::RuneBurned { .. } => "rune_burned",
+      Self::RuneEtched { .. } => "rune_etched",
+      Self::RuneMinted { .. } => "rune_minted",
+      Self::RuneTransferred { .. } => "rune_transferred",
+    }
+  }
+
+  pub(crate) fn outpoints(&self) -> Vec<OutPoint> {
+    match self {
+      Self::InscriptionCreated { location, .. } => {
+        location.iter().map(|location| location.outpoint).collect()
+      }
+      Self::InscriptionTransferred {
+        new_location,
+        old_location,
+        ..
+      } => vec![new_location.outpoint, old_location.outpoint],
+      Self::RuneTransferred { outpoint, .. } => vec![*outpoint],
+      Self::BlockCommitted { .. }
+      | Self::ReorgRolledBack { .. }
+      | Self::RuneBurned { .. }
+      | Self::RuneEtched { .. }
+      | Self::RuneMinted { .. } => Vec::new(),
+    }
+  }
+
+  /// Rollbacks report the block count they rolled back to.
+  pub(crate) fn block_height(&self) -> u32 {
+    match self {
+      Self::BlockCommitted { block_height }
+      | Self::InscriptionCreated { block_height, .. }
+      | Self::InscriptionTransferred { block_height, .. }
+      | Self::RuneBurned { block_height, .. }
+      | Self::RuneEtched { block_height, .. }
+      | Self::RuneMinted { block_height, .. }
+      | Self::RuneTransferred { block_height, .. } => *block_height,
+      Self::ReorgRolledBack { block_count, .. } => *block_count,
+    }
+  }
+}
+
+/// Webhook events carry the script pubkeys of the outputs they refer to, since
+/// those outputs may be spent by the time the event is delivered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct WebhookEvent {
+  pub(crate) event: Event,
+  pub(crate) script_pubkeys: Vec<ScriptBuf>,
 }
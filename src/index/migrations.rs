@@ -0,0 +1,248 @@
+use {
+  super::*,
+  crate::subcommand::index::migrate::{Output, Step},
+};
+
+#[derive(Debug)]
+pub(super) struct Migration {
+  description: &'static str,
+  from: u64,
+  run: fn(&WriteTransaction) -> Result,
+}
+
+/// Each migration upgrades an index from schema `from` to `from + 1`.
+const MIGRATIONS: &[Migration] = &[
+  Migration {
+    description: "create empty, disabled address index",
+    from: 18,
+    run: |wtx| {
+      // the address index can only be built from genesis, so record it as
+      // disabled
+      wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+      wtx.open_table(OUTPOINT_TO_TXOUT)?;
+      Index::set_statistic(
+        &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+        Statistic::IndexAddresses,
+        0,
+      )?;
+      Ok(())
+    },
+  },
+  Migration {
+    description: "create webhook tables",
+    from: 19,
+    run: |wtx| {
+      wtx.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_EVENT)?;
+      wtx.open_table(WEBHOOK_URL_TO_CURSOR)?;
+      Ok(())
+    },
+  },
+  Migration {
+    description: "create reorg history table",
+    from: 20,
+    run: |wtx| {
+      wtx.open_table(REORG_NUMBER_TO_REORG_ENTRY)?;
+      Ok(())
+    },
+  },
+  Migration {
+    description: "create and populate rune holder index",
+    from: 21,
+    run: |wtx| {
+      let outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut rune_id_to_outpoint = wtx.open_multimap_table(RUNE_ID_TO_OUTPOINT)?;
+
+      for result in outpoint_to_rune_balances.iter()? {
+        let (outpoint, buffer) = result?;
+        let buffer = buffer.value();
+
+        let mut i = 0;
+        while i < buffer.len() {
+          let (id, length) = runes::varint::decode(&buffer[i..]);
+          i += length;
+          let (_amount, length) = runes::varint::decode(&buffer[i..]);
+          i += length;
+
+          rune_id_to_outpoint.insert(RuneId::try_from(id)?.store(), outpoint.value())?;
+        }
+      }
+
+      Ok(())
+    },
+  },
+  Migration {
+    description: "create rune activity tables, recording activity from the next block onward",
+    from: 22,
+    run: |wtx| {
+      wtx.open_multimap_table(HEIGHT_TO_RUNE_ID)?;
+      wtx.open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?;
+      Ok(())
+    },
+  },
+  Migration {
+    description: "create inscription transfer history table",
+    from: 23,
+    run: |wtx| {
+      wtx.open_table(SEQUENCE_NUMBER_AND_TRANSFER_NUMBER_TO_TRANSFER)?;
+      Ok(())
+    },
+  },
+];
+
+pub(super) fn plan(schema_version: u64) -> Result<&'static [Migration]> {
+  if schema_version > SCHEMA_VERSION {
+    bail!(
+      "index schema {schema_version} is newer than ord schema {SCHEMA_VERSION}, consider updating ord"
+    );
+  }
+
+  let Some(start) = MIGRATIONS
+    .iter()
+    .position(|migration| migration.from == schema_version)
+  else {
+    if schema_version == SCHEMA_VERSION {
+      return Ok(&[]);
+    }
+
+    bail!(
+      "index schema {schema_version} cannot be migrated in place to ord schema {SCHEMA_VERSION}, since migrations are only available from schema {}, consider deleting and rebuilding the index",
+      MIGRATIONS[0].from,
+    );
+  };
+
+  Ok(&MIGRATIONS[start..])
+}
+
+impl Index {
+  pub(crate) fn migrate(settings: &Settings, dry_run: bool) -> Result<Output> {
+    let path = settings.index();
+
+    if !path.exists() {
+      bail!("no index at `{}`", path.display());
+    }
+
+    let database = Database::builder()
+      .set_cache_size(settings.index_cache_size())
+      .open(path)?;
+
+    let from = database
+      .begin_read()?
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    let migrations = plan(from)?;
+
+    let steps = migrations
+      .iter()
+      .map(|migration| Step {
+        description: migration.description.into(),
+        from: migration.from,
+        to: migration.from + 1,
+      })
+      .collect();
+
+    if !dry_run {
+      let progress_bar = if cfg!(test)
+        || log_enabled!(log::Level::Info)
+        || settings.integration_test()
+        || migrations.is_empty()
+      {
+        None
+      } else {
+        let progress_bar = ProgressBar::new(migrations.len().try_into()?);
+        progress_bar.set_style(
+          ProgressStyle::with_template("[migrating index] {wide_bar} {pos}/{len} {msg}").unwrap(),
+        );
+        Some(progress_bar)
+      };
+
+      // each step commits separately, so an interrupted migration resumes from
+      // the last completed step
+      for migration in migrations {
+        log::info!(
+          "Migrating index from schema {} to {}: {}",
+          migration.from,
+          migration.from + 1,
+          migration.description,
+        );
+
+        if let Some(progress_bar) = &progress_bar {
+          progress_bar.set_message(migration.description);
+        }
+
+        let wtx = database.begin_write()?;
+
+        (migration.run)(&wtx)?;
+
+        Index::set_statistic(
+          &mut wtx.open_table(STATISTIC_TO_COUNT)?,
+          Statistic::Schema,
+          migration.from + 1,
+        )?;
+
+        wtx.commit()?;
+
+        if let Some(progress_bar) = &progress_bar {
+          progress_bar.inc(1);
+        }
+      }
+
+      if let Some(progress_bar) = &progress_bar {
+        progress_bar.finish_and_clear();
+      }
+    }
+
+    Ok(Output {
+      dry_run,
+      from,
+      steps,
+      to: SCHEMA_VERSION,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn migrations_are_contiguous_and_end_at_current_schema() {
+    for (i, migration) in MIGRATIONS.iter().enumerate() {
+      assert_eq!(
+        migration.from,
+        MIGRATIONS[0].from + u64::try_from(i).unwrap()
+      );
+    }
+
+    assert_eq!(MIGRATIONS.last().unwrap().from + 1, SCHEMA_VERSION);
+  }
+
+  #[test]
+  fn plan_covers_every_step() {
+    assert_eq!(plan(SCHEMA_VERSION).unwrap().len(), 0);
+    assert_eq!(plan(SCHEMA_VERSION - 1).unwrap().len(), 1);
+    assert_eq!(plan(SCHEMA_VERSION - 2).unwrap().len(), 2);
+    assert_eq!(plan(SCHEMA_VERSION - 3).unwrap().len(), 3);
+    assert_eq!(plan(MIGRATIONS[0].from).unwrap().len(), MIGRATIONS.len());
+  }
+
+  #[test]
+  fn plan_explains_unsupported_schemas() {
+    assert_eq!(
+      plan(0).unwrap_err().to_string(),
+      format!(
+        "index schema 0 cannot be migrated in place to ord schema {SCHEMA_VERSION}, since migrations are only available from schema 18, consider deleting and rebuilding the index"
+      ),
+    );
+
+    assert_eq!(
+      plan(u64::MAX).unwrap_err().to_string(),
+      format!(
+        "index schema {} is newer than ord schema {SCHEMA_VERSION}, consider updating ord",
+        u64::MAX
+      ),
+    );
+  }
+}
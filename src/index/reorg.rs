@@ -19,10 +19,6 @@ impl Display for ReorgError {
 
 impl std::error::Error for ReorgError {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
-
 pub(crate) struct Reorg {}
 
 impl Reorg {
@@ -32,8 +28,11 @@ impl Reorg {
     match index.block_hash(height.checked_sub(1))? {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
+        let max_savepoints = index.settings.max_savepoints();
+        let savepoint_interval = index.settings.savepoint_interval();
+
         let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
+          (max_savepoints - 1) * savepoint_interval + height % savepoint_interval;
 
         for depth in 1..max_recoverable_reorg_depth {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
@@ -60,20 +59,118 @@ impl Reorg {
       panic!("set index durability to `Durability::Immediate` to test reorg handling");
     }
 
+    let tip = height.saturating_sub(1);
+
+    let old_tip = index
+      .block_hash(Some(tip))?
+      .with_context(|| format!("index has no block at height {tip}"))?;
+
+    let new_tip = index.client.get_block_hash(tip.into())?;
+
     let mut wtx = index.begin_write()?;
 
     let oldest_savepoint =
       wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
 
+    // webhook deliveries happened outside the rolled back blocks, so cursors
+    // and event sequence numbers must survive the restore
+    let webhook_state = if index.index_webhook_events {
+      let cursors = wtx
+        .open_table(WEBHOOK_URL_TO_CURSOR)?
+        .iter()?
+        .map(|result| result.map(|(url, cursor)| (url.value().to_string(), cursor.value())))
+        .collect::<Result<Vec<(String, u64)>, StorageError>>()?;
+
+      let next = wtx
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&Statistic::WebhookEvents.key())?
+        .map(|x| x.value())
+        .unwrap_or_default();
+
+      Some((cursors, next))
+    } else {
+      None
+    };
+
+    // reorg history must also survive the restore, since it may predate the
+    // oldest savepoint
+    let reorgs = wtx
+      .open_table(REORG_NUMBER_TO_REORG_ENTRY)?
+      .iter()?
+      .map(|result| result.map(|(number, entry)| (number.value(), entry.value())))
+      .collect::<Result<Vec<(u32, ReorgEntryValue)>, StorageError>>()?;
+
     wtx.restore_savepoint(&oldest_savepoint)?;
 
+    {
+      let mut reorg_number_to_reorg_entry = wtx.open_table(REORG_NUMBER_TO_REORG_ENTRY)?;
+
+      for (number, entry) in &reorgs {
+        reorg_number_to_reorg_entry.insert(number, entry)?;
+      }
+
+      reorg_number_to_reorg_entry.insert(
+        u32::try_from(reorgs.len()).unwrap(),
+        ReorgEntry {
+          depth,
+          height,
+          new_tip,
+          old_tip,
+          timestamp: u32::try_from(
+            SystemTime::now()
+              .duration_since(SystemTime::UNIX_EPOCH)?
+              .as_secs(),
+          )?,
+        }
+        .store(),
+      )?;
+    }
+
+    if let Some((cursors, next)) = webhook_state {
+      let mut webhook_url_to_cursor = wtx.open_table(WEBHOOK_URL_TO_CURSOR)?;
+      for (url, cursor) in cursors {
+        webhook_url_to_cursor.insert(url.as_str(), &cursor)?;
+      }
+
+      let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
+      Index::set_statistic(&mut statistic_to_count, Statistic::WebhookEvents, next)?;
+
+      let block_count = wtx
+        .open_table(HEIGHT_TO_BLOCK_HEADER)?
+        .range(0..)?
+        .next_back()
+        .transpose()?
+        .map(|(height, _header)| height.value() + 1)
+        .unwrap_or(0);
+
+      Index::queue_webhook_events(
+        &wtx,
+        &mut statistic_to_count,
+        vec![WebhookEvent {
+          event: Event::ReorgRolledBack {
+            block_count,
+            depth,
+            height,
+          },
+          script_pubkeys: Vec::new(),
+        }],
+      )?;
+    }
+
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
-    log::info!(
-      "successfully rolled back database to height {}",
-      index.begin_read()?.block_count()?
-    );
+    let block_count = index.begin_read()?.block_count()?;
+
+    log::info!("successfully rolled back database to height {block_count}");
+
+    if let Some(sender) = index.event_sender.as_ref() {
+      sender.blocking_send(Event::ReorgRolledBack {
+        block_count,
+        depth,
+        height,
+      })?;
+    }
 
     Ok(())
   }
@@ -83,7 +180,9 @@ impl Reorg {
       return Ok(());
     }
 
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
+    let savepoint_interval = index.settings.savepoint_interval();
+
+    if (height < savepoint_interval || height % savepoint_interval == 0)
       && u32::try_from(
         index
           .settings
@@ -93,13 +192,13 @@ impl Reorg {
       )
       .unwrap()
       .saturating_sub(height)
-        <= CHAIN_TIP_DISTANCE
+        <= index.settings.savepoint_chain_tip_distance()
     {
       let wtx = index.begin_write()?;
 
       let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
 
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
+      if savepoints.len() >= usize::try_from(index.settings.max_savepoints()).unwrap() {
         wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
       }
 
@@ -0,0 +1,453 @@
+use {
+  super::*,
+  crate::subcommand::index::snapshot::Manifest,
+  redb::{RedbKey, RedbValue},
+};
+
+const DATABASE: &str = "index.redb";
+const MANIFEST: &str = "manifest.json";
+
+fn copy_table<K: RedbKey + 'static, V: RedbValue + 'static>(
+  rtx: &redb::ReadTransaction,
+  wtx: &WriteTransaction,
+  definition: TableDefinition<K, V>,
+) -> Result {
+  let source = match rtx.open_table(definition) {
+    Ok(table) => table,
+    Err(TableError::TableDoesNotExist(_)) => return Ok(()),
+    Err(err) => return Err(err.into()),
+  };
+
+  let mut destination = wtx.open_table(definition)?;
+
+  for result in source.iter()? {
+    let (key, value) = result?;
+    destination.insert(key.value(), value.value())?;
+  }
+
+  Ok(())
+}
+
+fn copy_multimap_table<K: RedbKey + 'static, V: RedbKey + 'static>(
+  rtx: &redb::ReadTransaction,
+  wtx: &WriteTransaction,
+  definition: MultimapTableDefinition<K, V>,
+) -> Result {
+  let source = match rtx.open_multimap_table(definition) {
+    Ok(table) => table,
+    Err(TableError::TableDoesNotExist(_)) => return Ok(()),
+    Err(err) => return Err(err.into()),
+  };
+
+  let mut destination = wtx.open_multimap_table(definition)?;
+
+  for result in source.iter()? {
+    let (key, values) = result?;
+    for value in values {
+      destination.insert(key.value(), value?.value())?;
+    }
+  }
+
+  Ok(())
+}
+
+impl Index {
+  /// Copies the index from a single read transaction, so the copy is consistent.
+  pub(crate) fn snapshot(&self, path: &Path) -> Result<Manifest> {
+    if path.exists() {
+      bail!("snapshot destination `{}` already exists", path.display());
+    }
+
+    fs::create_dir_all(path)?;
+
+    let rtx = self.database.begin_read()?;
+
+    let (height, block_hash) = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, header)| (height.value(), Header::load(*header.value()).block_hash()))
+      .unzip();
+
+    let schema_version = rtx
+      .open_table(STATISTIC_TO_COUNT)?
+      .get(&Statistic::Schema.key())?
+      .map(|x| x.value())
+      .unwrap_or(0);
+
+    let database = Database::builder().create(path.join(DATABASE))?;
+
+    let wtx = database.begin_write()?;
+
+    copy_multimap_table(&rtx, &wtx, HEIGHT_TO_RUNE_ID)?;
+    copy_multimap_table(&rtx, &wtx, RUNE_ID_TO_OUTPOINT)?;
+    copy_multimap_table(&rtx, &wtx, SATPOINT_TO_SEQUENCE_NUMBER)?;
+    copy_multimap_table(&rtx, &wtx, SAT_TO_SEQUENCE_NUMBER)?;
+    copy_multimap_table(&rtx, &wtx, SCRIPT_PUBKEY_TO_OUTPOINT)?;
+    copy_multimap_table(&rtx, &wtx, SEQUENCE_NUMBER_TO_CHILDREN)?;
+    copy_table(&rtx, &wtx, CONTENT_TYPE_TO_COUNT)?;
+    copy_table(&rtx, &wtx, HEIGHT_TO_BLOCK_HEADER)?;
+    copy_table(&rtx, &wtx, HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
+    copy_table(&rtx, &wtx, HOME_INSCRIPTIONS)?;
+    copy_table(&rtx, &wtx, INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+    copy_table(&rtx, &wtx, INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    copy_table(&rtx, &wtx, OUTPOINT_TO_RUNE_BALANCES)?;
+    copy_table(&rtx, &wtx, OUTPOINT_TO_SAT_RANGES)?;
+    copy_table(&rtx, &wtx, OUTPOINT_TO_TXOUT)?;
+    copy_table(&rtx, &wtx, OUTPOINT_TO_VALUE)?;
+    copy_table(&rtx, &wtx, REORG_NUMBER_TO_REORG_ENTRY)?;
+    copy_table(&rtx, &wtx, RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?;
+    copy_table(&rtx, &wtx, RUNE_ID_TO_RUNE_ENTRY)?;
+    copy_table(&rtx, &wtx, RUNE_TO_RUNE_ID)?;
+    copy_table(&rtx, &wtx, SAT_TO_SATPOINT)?;
+    copy_table(&rtx, &wtx, SEQUENCE_NUMBER_AND_TRANSFER_NUMBER_TO_TRANSFER)?;
+    copy_table(&rtx, &wtx, SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+    copy_table(&rtx, &wtx, SEQUENCE_NUMBER_TO_RUNE_ID)?;
+    copy_table(&rtx, &wtx, SEQUENCE_NUMBER_TO_SATPOINT)?;
+    copy_table(&rtx, &wtx, STATISTIC_TO_COUNT)?;
+    copy_table(&rtx, &wtx, TRANSACTION_ID_TO_RUNE)?;
+    copy_table(&rtx, &wtx, TRANSACTION_ID_TO_TRANSACTION)?;
+    copy_table(
+      &rtx,
+      &wtx,
+      WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP,
+    )?;
+
+    // webhook deliveries are specific to this node, so the tables are left
+    // empty
+    wtx.open_table(WEBHOOK_SEQUENCE_NUMBER_TO_EVENT)?;
+    wtx.open_table(WEBHOOK_URL_TO_CURSOR)?;
+
+    wtx.commit()?;
+
+    let manifest = Manifest {
+      block_hash,
+      chain: self.settings.chain(),
+      height,
+      index_addresses: self.index_addresses,
+      index_inscriptions: self.settings.index_inscriptions(),
+      index_runes: self.index_runes,
+      index_sats: self.index_sats,
+      index_spent_sats: self.index_spent_sats,
+      index_transactions: self.index_transactions,
+      index_transfers: self.index_transfers,
+      schema_version,
+    };
+
+    // the manifest is written last, so incomplete snapshots can't be restored
+    fs::write(
+      path.join(MANIFEST),
+      serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    Ok(manifest)
+  }
+
+  pub(crate) fn restore(settings: &Settings, path: &Path, replace: bool) -> Result<Manifest> {
+    let manifest_path = path.join(MANIFEST);
+
+    let manifest: Manifest = serde_json::from_str(
+      &fs::read_to_string(&manifest_path)
+        .with_context(|| format!("failed to read `{}`", manifest_path.display()))?,
+    )
+    .with_context(|| format!("failed to parse `{}`", manifest_path.display()))?;
+
+    ensure!(
+      manifest.chain == settings.chain(),
+      "snapshot is for {} but ord is on {}",
+      manifest.chain,
+      settings.chain(),
+    );
+
+    ensure!(
+      manifest.schema_version == SCHEMA_VERSION,
+      "snapshot has index schema {} but ord has schema {SCHEMA_VERSION}",
+      manifest.schema_version,
+    );
+
+    for (name, snapshot, setting) in [
+      (
+        "--index-addresses",
+        manifest.index_addresses,
+        settings.index_addresses(),
+      ),
+      (
+        "--no-index-inscriptions",
+        !manifest.index_inscriptions,
+        !settings.index_inscriptions(),
+      ),
+      (
+        "--index-runes",
+        manifest.index_runes,
+        settings.index_runes(),
+      ),
+      ("--index-sats", manifest.index_sats, settings.index_sats()),
+      (
+        "--index-spent-sats",
+        manifest.index_spent_sats,
+        settings.index_spent_sats(),
+      ),
+      (
+        "--index-transactions",
+        manifest.index_transactions,
+        settings.index_transactions(),
+      ),
+      (
+        "--index-transfers",
+        manifest.index_transfers,
+        settings.index_transfers(),
+      ),
+    ] {
+      ensure!(
+        snapshot == setting,
+        "snapshot was built {} `{name}` but ord is running {}",
+        if snapshot { "with" } else { "without" },
+        if setting { "with it" } else { "without it" },
+      );
+    }
+
+    let index = settings.index();
+
+    if !replace && index.exists() {
+      bail!(
+        "index already exists at `{}`, delete it before restoring a snapshot",
+        index.display()
+      );
+    }
+
+    if let Some(parent) = index.parent() {
+      fs::create_dir_all(parent)?;
+    }
+
+    let partial = index.with_extension("partial");
+
+    fs::copy(path.join(DATABASE), &partial)?;
+    fs::rename(&partial, index)?;
+
+    Ok(manifest)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  fn settings(context: &Context, args: &[&str], index: &Path) -> Settings {
+    Settings::from_options(
+      Options::try_parse_from(
+        [
+          "ord".to_string(),
+          "--regtest".to_string(),
+          format!("--bitcoin-rpc-url={}", context.rpc_server.url()),
+          format!(
+            "--cookie-file={}",
+            context.tempdir.path().join("cookie").display()
+          ),
+          format!("--index={}", index.display()),
+        ]
+        .into_iter()
+        .chain(args.iter().map(|arg| arg.to_string())),
+      )
+      .unwrap(),
+    )
+    .or_defaults()
+    .unwrap()
+  }
+
+  #[test]
+  fn snapshot_contains_every_table() {
+    let context = Context::builder()
+      .chain(Chain::Regtest)
+      .args(["--index-sats", "--index-runes"])
+      .build();
+
+    context.mine_blocks(2);
+
+    {
+      let wtx = context.index.database.begin_write().unwrap();
+      wtx
+        .open_table(WEBHOOK_URL_TO_CURSOR)
+        .unwrap()
+        .insert("http://localhost", 1)
+        .unwrap();
+      wtx.commit().unwrap();
+    }
+
+    let path = context.tempdir.path().join("snapshot");
+
+    let manifest = context.index.snapshot(&path).unwrap();
+
+    assert_eq!(
+      manifest,
+      Manifest {
+        block_hash: context.index.block_hash(None).unwrap(),
+        chain: Chain::Regtest,
+        height: Some(2),
+        index_addresses: false,
+        index_inscriptions: true,
+        index_runes: true,
+        index_sats: true,
+        index_spent_sats: false,
+        index_transactions: false,
+        index_transfers: false,
+        schema_version: SCHEMA_VERSION,
+      }
+    );
+
+    let snapshot = Database::open(path.join(DATABASE)).unwrap();
+
+    let tables = |database: &Database| {
+      let rtx = database.begin_read().unwrap();
+      let mut tables = rtx
+        .list_tables()
+        .unwrap()
+        .map(|table| table.name().to_string())
+        .chain(
+          rtx
+            .list_multimap_tables()
+            .unwrap()
+            .map(|table| table.name().to_string()),
+        )
+        .collect::<Vec<String>>();
+      tables.sort();
+      tables
+    };
+
+    assert_eq!(tables(&snapshot), tables(&context.index.database));
+
+    assert_eq!(
+      snapshot
+        .begin_read()
+        .unwrap()
+        .open_table(OUTPOINT_TO_SAT_RANGES)
+        .unwrap()
+        .len()
+        .unwrap(),
+      context
+        .index
+        .database
+        .begin_read()
+        .unwrap()
+        .open_table(OUTPOINT_TO_SAT_RANGES)
+        .unwrap()
+        .len()
+        .unwrap(),
+    );
+
+    assert!(snapshot
+      .begin_read()
+      .unwrap()
+      .open_table(WEBHOOK_URL_TO_CURSOR)
+      .unwrap()
+      .is_empty()
+      .unwrap());
+
+    assert_eq!(
+      context.index.snapshot(&path).unwrap_err().to_string(),
+      format!("snapshot destination `{}` already exists", path.display()),
+    );
+  }
+
+  #[test]
+  fn restore_validates_manifest() {
+    let context = Context::builder()
+      .chain(Chain::Regtest)
+      .arg("--index-sats")
+      .build();
+
+    context.mine_blocks(1);
+
+    let path = context.tempdir.path().join("snapshot");
+
+    context.index.snapshot(&path).unwrap();
+
+    let index = context.tempdir.path().join("restored.redb");
+
+    assert_eq!(
+      Index::restore(&settings(&context, &[], &index), &path, false)
+        .unwrap_err()
+        .to_string(),
+      "snapshot was built with `--index-sats` but ord is running without it",
+    );
+
+    assert_eq!(
+      Index::restore(
+        &settings(&context, &["--index-sats", "--index-runes"], &index),
+        &path,
+        false,
+      )
+      .unwrap_err()
+      .to_string(),
+      "snapshot was built without `--index-runes` but ord is running with it",
+    );
+
+    let mut settings = settings(&context, &["--index-sats"], &index);
+
+    assert!(!index.exists());
+
+    Index::restore(&settings, &path, false).unwrap();
+
+    assert!(index.exists());
+
+    assert_eq!(
+      Index::restore(&settings, &path, false)
+        .unwrap_err()
+        .to_string(),
+      format!(
+        "index already exists at `{}`, delete it before restoring a snapshot",
+        index.display()
+      ),
+    );
+
+    settings =
+      Settings::from_options(Options::try_parse_from(["ord", "--signet", "--index-sats"]).unwrap())
+        .or_defaults()
+        .unwrap();
+
+    assert_eq!(
+      Index::restore(&settings, &path, false)
+        .unwrap_err()
+        .to_string(),
+      "snapshot is for regtest but ord is on signet",
+    );
+
+    fs::remove_file(path.join(MANIFEST)).unwrap();
+
+    assert_eq!(
+      Index::restore(&settings, &path, false)
+        .unwrap_err()
+        .to_string(),
+      format!("failed to read `{}`", path.join(MANIFEST).display()),
+    );
+  }
+
+  #[test]
+  fn restored_snapshot_can_be_opened() {
+    let context = Context::builder().chain(Chain::Regtest).build();
+
+    context.mine_blocks(3);
+
+    let path = context.tempdir.path().join("snapshot");
+
+    context.index.snapshot(&path).unwrap();
+
+    let settings = settings(&context, &[], &context.tempdir.path().join("restored.redb"));
+
+    Index::restore(&settings, &path, false).unwrap();
+
+    let index = Index::open(&settings).unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 4);
+    assert_eq!(
+      index.block_hash(None).unwrap(),
+      context.index.block_hash(None).unwrap()
+    );
+
+    context.mine_blocks(1);
+
+    index.update().unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 5);
+  }
+}
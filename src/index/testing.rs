@@ -3,6 +3,7 @@ use {super::*, std::ffi::OsString, tempfile::TempDir};
 pub(crate) struct ContextBuilder {
   args: Vec<OsString>,
   chain: Chain,
+  config: String,
   event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
   tempdir: Option<TempDir>,
 }
@@ -34,7 +35,10 @@ impl ContextBuilder {
 
     let options = Options::try_parse_from(command.into_iter().chain(self.args)).unwrap();
     let index = Index::open_with_event_sender(
-      &Settings::from_options(options).or_defaults().unwrap(),
+      &Settings::from_options(options)
+        .or(serde_yaml::from_str::<Settings>(&self.config).unwrap())
+        .or_defaults()
+        .unwrap(),
       self.event_sender,
     )?;
     index.update().unwrap();
@@ -61,6 +65,11 @@ impl ContextBuilder {
     self
   }
 
+  pub(crate) fn config(mut self, config: &str) -> Self {
+    self.config = config.into();
+    self
+  }
+
   pub(crate) fn tempdir(mut self, tempdir: TempDir) -> Self {
     self.tempdir = Some(tempdir);
     self
@@ -84,6 +93,7 @@ impl Context {
     ContextBuilder {
       args: Vec::new(),
       chain: Chain::Regtest,
+      config: String::new(),
       event_sender: None,
       tempdir: None,
     }
@@ -136,7 +146,7 @@ impl Context {
 
     let mut outstanding: HashMap<RuneId, u128> = HashMap::new();
 
-    for (_, balances) in balances {
+    for (_, balances) in balances.iter() {
       for (id, balance) in balances {
         *outstanding.entry(*id).or_default() += *balance;
       }
@@ -147,6 +157,24 @@ impl Context {
         outstanding.get(id).copied().unwrap_or_default(),
         entry.supply - entry.burned
       );
+
+      let mut holders = balances
+        .iter()
+        .filter_map(|(outpoint, balances)| {
+          balances
+            .iter()
+            .find(|(balance_id, _)| balance_id == id)
+            .map(|(_, balance)| (*outpoint, *balance))
+        })
+        .collect::<Vec<(OutPoint, u128)>>();
+
+      holders.sort_by(|(a_outpoint, a_balance), (b_outpoint, b_balance)| {
+        b_balance
+          .cmp(a_balance)
+          .then_with(|| a_outpoint.cmp(b_outpoint))
+      });
+
+      pretty_assert_eq!(holders, self.index.get_rune_holders(*id).unwrap().balances);
     }
   }
 }
@@ -2,13 +2,33 @@ use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
   super::{fetcher::Fetcher, *},
   futures::future::try_join_all,
-  std::sync::mpsc,
+  std::{cell::RefCell, sync::mpsc},
   tokio::sync::mpsc::{error::TryRecvError, Receiver, Sender},
 };
 
+mod block_source;
 mod inscription_updater;
 mod rune_updater;
 
+pub(super) struct EventSink<'a> {
+  outbox: Option<RefCell<Vec<Event>>>,
+  sender: Option<&'a Sender<Event>>,
+}
+
+impl EventSink<'_> {
+  pub(super) fn send(&self, event: Event) -> Result {
+    if let Some(outbox) = &self.outbox {
+      outbox.borrow_mut().push(event.clone());
+    }
+
+    if let Some(sender) = self.sender {
+      sender.blocking_send(event)?;
+    }
+
+    Ok(())
+  }
+}
+
 pub(crate) struct BlockData {
   pub(crate) header: Header,
   pub(crate) txdata: Vec<(Transaction, Txid)>,
@@ -71,7 +91,11 @@ impl<'index> Updater<'index> {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(self.index, self.height, self.index.index_sats)?;
+    let rx = Self::fetch_blocks_from(
+      self.index,
+      self.height,
+      self.index.index_sats || self.index.index_addresses,
+    )?;
 
     let (mut outpoint_sender, mut value_receiver) = Self::spawn_fetcher(&self.index.settings)?;
 
@@ -153,13 +177,13 @@ impl<'index> Updater<'index> {
   fn fetch_blocks_from(
     index: &Index,
     mut height: u32,
-    index_sats: bool,
+    full_blocks: bool,
   ) -> Result<mpsc::Receiver<BlockData>> {
     let (tx, rx) = mpsc::sync_channel(32);
 
     let height_limit = index.height_limit;
 
-    let client = index.settings.bitcoin_rpc_client(None)?;
+    let mut block_source = block_source::open(index, height)?;
 
     let first_inscription_height = index.first_inscription_height;
 
@@ -170,7 +194,7 @@ impl<'index> Updater<'index> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      match block_source.get_block(height, full_blocks || height >= first_inscription_height) {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -189,52 +213,6 @@ impl<'index> Updater<'index> {
     Ok(rx)
   }
 
-  fn get_block_with_retries(
-    client: &Client,
-    height: u32,
-    index_sats: bool,
-    first_inscription_height: u32,
-  ) -> Result<Option<Block>> {
-    let mut errors = 0;
-    loop {
-      match client
-        .get_block_hash(height.into())
-        .into_option()
-        .and_then(|option| {
-          option
-            .map(|hash| {
-              if index_sats || height >= first_inscription_height {
-                Ok(client.get_block(&hash)?)
-              } else {
-                Ok(Block {
-                  header: client.get_block_header(&hash)?,
-                  txdata: Vec::new(),
-                })
-              }
-            })
-            .transpose()
-        }) {
-        Err(err) => {
-          if cfg!(test) {
-            return Err(err);
-          }
-
-          errors += 1;
-          let seconds = 1 << errors;
-          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
-
-          if seconds > 120 {
-            log::error!("would sleep for more than 120s, giving up");
-            return Err(err);
-          }
-
-          thread::sleep(Duration::from_secs(seconds));
-        }
-        Ok(result) => return Ok(result),
-      }
-    }
-  }
-
   fn spawn_fetcher(settings: &Settings) -> Result<(Sender<OutPoint>, Receiver<u64>)> {
     let fetcher = Fetcher::new(settings)?;
 
@@ -384,6 +362,8 @@ impl<'index> Updater<'index> {
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
     let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let mut sequence_number_to_transfers =
+      wtx.open_table(SEQUENCE_NUMBER_AND_TRANSFER_NUMBER_TO_TRANSFER)?;
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
@@ -416,18 +396,24 @@ impl<'index> Updater<'index> {
 
     let home_inscription_count = home_inscriptions.len()?;
 
+    let event_sink = EventSink {
+      outbox: self.index.index_webhook_events.then(RefCell::default),
+      sender: self.index.event_sender.as_ref(),
+    };
+
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
       chain: self.index.settings.chain(),
       content_type_to_count: &mut content_type_to_count,
       cursed_inscription_count,
-      event_sender: self.index.event_sender.as_ref(),
+      event_sink: &event_sink,
       flotsam: Vec::new(),
       height: self.height,
       home_inscription_count,
       home_inscriptions: &mut home_inscriptions,
       id_to_sequence_number: &mut inscription_id_to_sequence_number,
       index_transactions: self.index.index_transactions,
+      index_transfers: self.index.index_transfers,
       inscription_number_to_sequence_number: &mut inscription_number_to_sequence_number,
       lost_sats,
       next_sequence_number,
@@ -438,6 +424,7 @@ impl<'index> Updater<'index> {
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
       sequence_number_to_satpoint: &mut sequence_number_to_satpoint,
+      sequence_number_to_transfers: &mut sequence_number_to_transfers,
       timestamp: block.header.time,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
@@ -578,8 +565,41 @@ impl<'index> Updater<'index> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
+    let mut spent_script_pubkeys = HashMap::new();
+
+    if self.index.index_addresses {
+      let mut outpoint_to_txout = wtx.open_table(OUTPOINT_TO_TXOUT)?;
+      let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+      // outputs spent by this block are about to be removed, so look up the
+      // ones webhook events refer to first
+      if let Some(outbox) = &event_sink.outbox {
+        for outpoint in outbox.borrow().iter().flat_map(Event::outpoints) {
+          if let Some(txout) = outpoint_to_txout.get(&outpoint.store())? {
+            spent_script_pubkeys.insert(
+              outpoint,
+              consensus::encode::deserialize::<TxOut>(txout.value())?.script_pubkey,
+            );
+          }
+        }
+      }
+
+      for (tx, txid) in &block.txdata {
+        Self::index_transaction_addresses(
+          tx,
+          *txid,
+          &mut outpoint_to_txout,
+          &mut script_pubkey_to_outpoint,
+        )?;
+      }
+    }
+
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
+      let mut height_to_rune_id = wtx.open_multimap_table(HEIGHT_TO_RUNE_ID)?;
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut rune_id_and_height_to_rune_activity =
+        wtx.open_table(RUNE_ID_AND_HEIGHT_TO_RUNE_ACTIVITY)?;
+      let mut rune_id_to_outpoint = wtx.open_multimap_table(RUNE_ID_TO_OUTPOINT)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
@@ -591,11 +611,13 @@ impl<'index> Updater<'index> {
         .unwrap_or(0);
 
       let mut rune_updater = RuneUpdater {
+        event_sink: &event_sink,
         height: self.height,
         id_to_entry: &mut rune_id_to_rune_entry,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
         minimum: Rune::minimum_at_height(self.index.settings.chain(), Height(self.height)),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        rune_id_to_outpoint: &mut rune_id_to_outpoint,
         rune_to_id: &mut rune_to_rune_id,
         runes,
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
@@ -609,7 +631,7 @@ impl<'index> Updater<'index> {
         rune_updater.index_runes(i, tx, *txid)?;
       }
 
-      for (rune_id, update) in rune_updater.updates {
+      for (rune_id, activity) in rune_updater.updates {
         let mut entry = RuneEntry::load(
           rune_id_to_rune_entry
             .get(&rune_id.store())?
@@ -617,14 +639,53 @@ impl<'index> Updater<'index> {
             .value(),
         );
 
-        entry.burned += update.burned;
-        entry.mints += update.mints;
-        entry.supply += update.supply;
+        entry.burned += activity.burned;
+        entry.mints += activity.mints;
+        entry.supply += activity.minted;
 
         rune_id_to_rune_entry.insert(&rune_id.store(), entry.store())?;
+
+        height_to_rune_id.insert(&self.height, &rune_id.store())?;
+        rune_id_and_height_to_rune_activity
+          .insert(&(rune_id.store(), self.height), activity.store())?;
       }
     }
 
+    event_sink.send(Event::BlockCommitted {
+      block_height: self.height,
+    })?;
+
+    if let Some(outbox) = event_sink.outbox {
+      let transactions = block
+        .txdata
+        .iter()
+        .map(|(tx, txid)| (*txid, tx))
+        .collect::<HashMap<Txid, &Transaction>>();
+
+      let events = outbox
+        .into_inner()
+        .into_iter()
+        .map(|event| WebhookEvent {
+          script_pubkeys: event
+            .outpoints()
+            .into_iter()
+            .filter_map(|outpoint| {
+              spent_script_pubkeys.get(&outpoint).cloned().or_else(|| {
+                transactions
+                  .get(&outpoint.txid)?
+                  .output
+                  .get(usize::try_from(outpoint.vout).unwrap())
+                  .map(|output| output.script_pubkey.clone())
+              })
+            })
+            .collect(),
+          event,
+        })
+        .collect();
+
+      Index::queue_webhook_events(wtx, &mut statistic_to_count, events)?;
+    }
+
     height_to_block_header.insert(&self.height, &block.header.store())?;
 
     self.height += 1;
@@ -638,6 +699,42 @@ impl<'index> Updater<'index> {
     Ok(())
   }
 
+  fn index_transaction_addresses(
+    tx: &Transaction,
+    txid: Txid,
+    outpoint_to_txout: &mut Table<&OutPointValue, &[u8]>,
+    script_pubkey_to_outpoint: &mut MultimapTable<&[u8], &OutPointValue>,
+  ) -> Result {
+    for input in &tx.input {
+      let outpoint = input.previous_output.store();
+
+      let Some(txout) = outpoint_to_txout.remove(&outpoint)? else {
+        continue;
+      };
+
+      let txout: TxOut = consensus::encode::deserialize(txout.value())?;
+
+      script_pubkey_to_outpoint.remove(txout.script_pubkey.as_bytes(), &outpoint)?;
+    }
+
+    for (vout, txout) in tx.output.iter().enumerate() {
+      if txout.script_pubkey.is_op_return() {
+        continue;
+      }
+
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      }
+      .store();
+
+      outpoint_to_txout.insert(&outpoint, consensus::encode::serialize(txout).as_slice())?;
+      script_pubkey_to_outpoint.insert(txout.script_pubkey.as_bytes(), &outpoint)?;
+    }
+
+    Ok(())
+  }
+
   fn index_transaction_sats(
     &mut self,
     tx: &Transaction,
@@ -0,0 +1,520 @@
+use {
+  super::*,
+  std::io::{Seek, SeekFrom},
+};
+
+/// Blocks this close to the tip may not be flushed to disk yet.
+const RPC_HANDOFF_DEPTH: u32 = 6;
+
+pub(super) trait BlockSource: Send {
+  /// Transactions may be omitted unless `full` is set.
+  fn get_block(&mut self, height: u32, full: bool) -> Result<Option<Block>>;
+}
+
+pub(super) fn open(index: &Index, height: u32) -> Result<Box<dyn BlockSource>> {
+  let rpc = RpcBlockSource {
+    client: index.settings.bitcoin_rpc_client(None)?,
+  };
+
+  if !index.settings.index_from_blk_files() {
+    return Ok(Box::new(rpc));
+  }
+
+  let tip = u32::try_from(rpc.client.get_block_count()?).unwrap();
+
+  if tip.saturating_sub(height) <= RPC_HANDOFF_DEPTH {
+    return Ok(Box::new(rpc));
+  }
+
+  Ok(Box::new(BlkFileBlockSource::open(
+    &index.settings.bitcoin_blocks_dir(),
+    index.settings.chain(),
+    rpc,
+  )?))
+}
+
+pub(super) struct RpcBlockSource {
+  client: Client,
+}
+
+impl BlockSource for RpcBlockSource {
+  fn get_block(&mut self, height: u32, full: bool) -> Result<Option<Block>> {
+    let mut errors = 0;
+    loop {
+      match self
+        .client
+        .get_block_hash(height.into())
+        .into_option()
+        .and_then(|option| {
+          option
+            .map(|hash| {
+              if full {
+                Ok(self.client.get_block(&hash)?)
+              } else {
+                Ok(Block {
+                  header: self.client.get_block_header(&hash)?,
+                  txdata: Vec::new(),
+                })
+              }
+            })
+            .transpose()
+        }) {
+        Err(err) => {
+          if cfg!(test) {
+            return Err(err);
+          }
+
+          errors += 1;
+          let seconds = 1 << errors;
+          log::warn!("failed to fetch block {height}, retrying in {seconds}s: {err}");
+
+          if seconds > 120 {
+            log::error!("would sleep for more than 120s, giving up");
+            return Err(err);
+          }
+
+          thread::sleep(Duration::from_secs(seconds));
+        }
+        Ok(result) => return Ok(result),
+      }
+    }
+  }
+}
+
+#[derive(Clone, Copy)]
+struct Location {
+  file: usize,
+  offset: u64,
+  size: u32,
+}
+
+/// Reads blocks from `blk*.dat` files, falling back to RPC near the tip.
+pub(super) struct BlkFileBlockSource {
+  files: Vec<PathBuf>,
+  locations: Vec<Location>,
+  rpc: RpcBlockSource,
+  xor: [u8; 8],
+}
+
+impl BlkFileBlockSource {
+  fn open(blocks_dir: &Path, chain: Chain, rpc: RpcBlockSource) -> Result<Self> {
+    let xor = match fs::read(blocks_dir.join("xor.dat")) {
+      Ok(key) => key
+        .try_into()
+        .map_err(|_| anyhow!("`{}` is not 8 bytes", blocks_dir.join("xor.dat").display()))?,
+      Err(err) if err.kind() == io::ErrorKind::NotFound => [0; 8],
+      Err(err) => return Err(err.into()),
+    };
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(blocks_dir)
+      .with_context(|| format!("failed to read blocks dir `{}`", blocks_dir.display()))?
+    {
+      let path = entry?.path();
+
+      let Some(number) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("blk"))
+        .and_then(|name| name.strip_suffix(".dat"))
+        .and_then(|number| number.parse::<u32>().ok())
+      else {
+        continue;
+      };
+
+      files.push((number, path));
+    }
+
+    files.sort();
+
+    let files = files
+      .into_iter()
+      .map(|(_number, path)| path)
+      .collect::<Vec<PathBuf>>();
+
+    log::info!(
+      "Scanning {} blk files in `{}` for block headers",
+      files.len(),
+      blocks_dir.display()
+    );
+
+    let magic = chain.network().magic().to_bytes();
+
+    let mut headers = HashMap::new();
+    for (i, path) in files.iter().enumerate() {
+      Self::scan(i, path, magic, xor, &mut headers)?;
+    }
+
+    let mut chain_hashes = Self::best_chain(chain.genesis_block().block_hash(), &headers);
+
+    let tip = u32::try_from(rpc.client.get_block_count()?).unwrap();
+
+    chain_hashes.truncate(
+      usize::try_from(tip.saturating_sub(RPC_HANDOFF_DEPTH))
+        .unwrap()
+        .saturating_add(1),
+    );
+
+    // stop where the files diverge from bitcoind's active chain
+    while let Some(hash) = chain_hashes.last() {
+      let height = u64::try_from(chain_hashes.len() - 1).unwrap();
+      if rpc.client.get_block_hash(height).into_option()? == Some(*hash) {
+        break;
+      }
+      chain_hashes.pop();
+    }
+
+    log::info!(
+      "Reading {} blocks from blk files before switching to RPC",
+      chain_hashes.len()
+    );
+
+    Ok(Self {
+      files,
+      locations: chain_hashes.iter().map(|hash| headers[hash].1).collect(),
+      rpc,
+      xor,
+    })
+  }
+
+  fn read_at(file: &mut File, offset: u64, buffer: &mut [u8], xor: [u8; 8]) -> io::Result<()> {
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buffer)?;
+
+    if xor != [0; 8] {
+      for (i, byte) in buffer.iter_mut().enumerate() {
+        *byte ^= xor[usize::try_from((offset + u64::try_from(i).unwrap()) % 8).unwrap()];
+      }
+    }
+
+    Ok(())
+  }
+
+  fn scan(
+    i: usize,
+    path: &Path,
+    magic: [u8; 4],
+    xor: [u8; 8],
+    headers: &mut HashMap<BlockHash, (BlockHash, Location)>,
+  ) -> Result {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    let mut offset = 0;
+    let mut prefix = [0; 8];
+    let mut header = [0; 80];
+
+    // files are preallocated and zero filled, and the last record may be
+    // incomplete if bitcoind is still writing it
+    while offset + 88 <= len {
+      Self::read_at(&mut file, offset, &mut prefix, xor)?;
+
+      if prefix[..4] != magic {
+        break;
+      }
+
+      let size = u32::from_le_bytes(prefix[4..].try_into().unwrap());
+
+      if offset + 8 + u64::from(size) > len {
+        break;
+      }
+
+      Self::read_at(&mut file, offset + 8, &mut header, xor)?;
+
+      let header = Header::consensus_decode(&mut header.as_slice())?;
+
+      headers.insert(
+        header.block_hash(),
+        (
+          header.prev_blockhash,
+          Location {
+            file: i,
+            offset: offset + 8,
+            size,
+          },
+        ),
+      );
+
+      offset += 8 + u64::from(size);
+    }
+
+    Ok(())
+  }
+
+  fn best_chain(
+    genesis: BlockHash,
+    headers: &HashMap<BlockHash, (BlockHash, Location)>,
+  ) -> Vec<BlockHash> {
+    if !headers.contains_key(&genesis) {
+      return Vec::new();
+    }
+
+    let mut children = HashMap::<BlockHash, Vec<BlockHash>>::new();
+    for (hash, (prev_blockhash, _location)) in headers {
+      children.entry(*prev_blockhash).or_default().push(*hash);
+    }
+
+    let mut tip = (0, genesis);
+    let mut stack = vec![(0, genesis)];
+
+    while let Some((height, hash)) = stack.pop() {
+      if height > tip.0 {
+        tip = (height, hash);
+      }
+
+      for child in children.get(&hash).into_iter().flatten() {
+        stack.push((height + 1, *child));
+      }
+    }
+
+    let mut chain = vec![tip.1];
+    while chain.len() <= tip.0 {
+      chain.push(headers[chain.last().unwrap()].0);
+    }
+
+    chain.reverse();
+
+    chain
+  }
+}
+
+impl BlockSource for BlkFileBlockSource {
+  fn get_block(&mut self, height: u32, full: bool) -> Result<Option<Block>> {
+    let Some(location) = self
+      .locations
+      .get(usize::try_from(height).unwrap())
+      .copied()
+    else {
+      return self.rpc.get_block(height, full);
+    };
+
+    let mut file = File::open(&self.files[location.file])?;
+
+    let mut buffer = vec![
+      0;
+      if full {
+        usize::try_from(location.size).unwrap()
+      } else {
+        80
+      }
+    ];
+
+    Self::read_at(&mut file, location.offset, &mut buffer, self.xor)?;
+
+    if full {
+      Ok(Some(consensus::encode::deserialize(&buffer)?))
+    } else {
+      Ok(Some(Block {
+        header: consensus::encode::deserialize(&buffer)?,
+        txdata: Vec::new(),
+      }))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context, tempfile::TempDir};
+
+  const XOR: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+  fn write_blk_file(path: &Path, blocks: &[&Block], xor: [u8; 8]) {
+    let mut buffer = Vec::new();
+
+    for block in blocks {
+      let block = consensus::encode::serialize(*block);
+      buffer.extend_from_slice(&Network::Regtest.magic().to_bytes());
+      buffer.extend_from_slice(&u32::try_from(block.len()).unwrap().to_le_bytes());
+      buffer.extend_from_slice(&block);
+    }
+
+    // bitcoind preallocates files
+    buffer.extend_from_slice(&[0; 64]);
+
+    for (i, byte) in buffer.iter_mut().enumerate() {
+      *byte ^= xor[i % 8];
+    }
+
+    fs::write(path, buffer).unwrap();
+  }
+
+  fn blocks_dir(context: &Context) -> PathBuf {
+    let blocks_dir = context.tempdir.path().join("bitcoin/regtest/blocks");
+    fs::create_dir_all(&blocks_dir).unwrap();
+    blocks_dir
+  }
+
+  fn rpc(context: &Context) -> RpcBlockSource {
+    RpcBlockSource {
+      client: context.index.settings.bitcoin_rpc_client(None).unwrap(),
+    }
+  }
+
+  #[test]
+  fn blocks_are_read_in_chain_order() {
+    let context = Context::builder().build();
+
+    let mut blocks = vec![Chain::Regtest.genesis_block()];
+    blocks.extend(context.mine_blocks(10));
+
+    let mut stale = blocks[5].clone();
+    stale.header.time += 1;
+
+    let blocks_dir = blocks_dir(&context);
+    fs::write(blocks_dir.join("xor.dat"), XOR).unwrap();
+
+    write_blk_file(
+      &blocks_dir.join("blk00000.dat"),
+      &[&blocks[0], &blocks[3], &blocks[1], &blocks[2], &stale],
+      XOR,
+    );
+
+    write_blk_file(
+      &blocks_dir.join("blk00001.dat"),
+      &[&blocks[5], &blocks[4], &blocks[7], &blocks[6]],
+      XOR,
+    );
+
+    write_blk_file(
+      &blocks_dir.join("blk00002.dat"),
+      &[&blocks[9], &blocks[8], &blocks[10]],
+      XOR,
+    );
+
+    let mut source = BlkFileBlockSource::open(&blocks_dir, Chain::Regtest, rpc(&context)).unwrap();
+
+    assert_eq!(source.locations.len(), 5);
+
+    for (height, block) in blocks.iter().enumerate() {
+      let height = u32::try_from(height).unwrap();
+
+      assert_eq!(
+        source.get_block(height, true).unwrap().as_ref(),
+        Some(block)
+      );
+
+      assert_eq!(
+        source.get_block(height, false).unwrap().unwrap().header,
+        block.header
+      );
+    }
+
+    assert_eq!(source.get_block(11, true).unwrap(), None);
+  }
+
+  #[test]
+  fn blocks_past_the_end_of_the_files_are_fetched_over_rpc() {
+    let context = Context::builder().build();
+
+    let mut blocks = vec![Chain::Regtest.genesis_block()];
+    blocks.extend(context.mine_blocks(20));
+
+    let blocks_dir = blocks_dir(&context);
+
+    write_blk_file(
+      &blocks_dir.join("blk00000.dat"),
+      &blocks[..8].iter().collect::<Vec<&Block>>(),
+      [0; 8],
+    );
+
+    let mut source = BlkFileBlockSource::open(&blocks_dir, Chain::Regtest, rpc(&context)).unwrap();
+
+    assert_eq!(source.locations.len(), 8);
+
+    for (height, block) in blocks.iter().enumerate() {
+      assert_eq!(
+        source
+          .get_block(u32::try_from(height).unwrap(), true)
+          .unwrap()
+          .as_ref(),
+        Some(block)
+      );
+    }
+  }
+
+  #[test]
+  fn truncated_records_are_ignored() {
+    let context = Context::builder().build();
+
+    let mut blocks = vec![Chain::Regtest.genesis_block()];
+    blocks.extend(context.mine_blocks(20));
+
+    let blocks_dir = blocks_dir(&context);
+    let path = blocks_dir.join("blk00000.dat");
+
+    write_blk_file(&path, &blocks[..5].iter().collect::<Vec<&Block>>(), [0; 8]);
+
+    let mut contents = fs::read(&path).unwrap();
+    contents.truncate(contents.len() - 70);
+    fs::write(&path, contents).unwrap();
+
+    let source = BlkFileBlockSource::open(&blocks_dir, Chain::Regtest, rpc(&context)).unwrap();
+
+    assert_eq!(source.locations.len(), 4);
+  }
+
+  #[test]
+  fn blocks_not_in_the_active_chain_are_fetched_over_rpc() {
+    let context = Context::builder().build();
+
+    let mut blocks = vec![Chain::Regtest.genesis_block()];
+    blocks.extend(context.mine_blocks(20));
+
+    let mut stale = blocks[4].clone();
+    stale.header.time += 1;
+
+    let blocks_dir = blocks_dir(&context);
+
+    write_blk_file(
+      &blocks_dir.join("blk00000.dat"),
+      &[&blocks[0], &blocks[1], &blocks[2], &blocks[3], &stale],
+      [0; 8],
+    );
+
+    let source = BlkFileBlockSource::open(&blocks_dir, Chain::Regtest, rpc(&context)).unwrap();
+
+    assert_eq!(source.locations.len(), 4);
+  }
+
+  #[test]
+  fn index_can_be_built_from_blk_files() {
+    let tempdir = TempDir::new().unwrap();
+    let bitcoin_data_dir = tempdir.path().join("bitcoin");
+
+    let context = Context::builder()
+      .arg("--index-from-blk-files")
+      .arg("--bitcoin-data-dir")
+      .arg(&bitcoin_data_dir)
+      .tempdir(tempdir)
+      .build();
+
+    let mut blocks = vec![Chain::Regtest.genesis_block()];
+    blocks.extend(context.rpc_server.mine_blocks(1));
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    blocks.extend(context.rpc_server.mine_blocks(10));
+
+    let blocks_dir = bitcoin_data_dir.join("regtest/blocks");
+    fs::create_dir_all(&blocks_dir).unwrap();
+
+    write_blk_file(
+      &blocks_dir.join("blk00000.dat"),
+      &blocks.iter().collect::<Vec<&Block>>(),
+      [0; 8],
+    );
+
+    context.index.update().unwrap();
+
+    assert_eq!(context.index.block_count().unwrap(), 12);
+
+    assert!(context
+      .index
+      .get_inscription_entry(InscriptionId { txid, index: 0 })
+      .unwrap()
+      .is_some());
+  }
+}
@@ -42,13 +42,14 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) chain: Chain,
   pub(super) content_type_to_count: &'a mut Table<'db, 'tx, Option<&'static [u8]>, u64>,
   pub(super) cursed_inscription_count: u64,
-  pub(super) event_sender: Option<&'a Sender<Event>>,
+  pub(super) event_sink: &'a EventSink<'a>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
   pub(super) home_inscriptions: &'a mut Table<'db, 'tx, u32, InscriptionIdValue>,
   pub(super) id_to_sequence_number: &'a mut Table<'db, 'tx, InscriptionIdValue, u32>,
   pub(super) index_transactions: bool,
+  pub(super) index_transfers: bool,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'db, 'tx, i32, u32>,
   pub(super) lost_sats: u64,
   pub(super) next_sequence_number: u32,
@@ -63,6 +64,7 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'db, 'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'db, 'tx, u32, InscriptionEntryValue>,
   pub(super) sequence_number_to_satpoint: &'a mut Table<'db, 'tx, u32, &'static SatPointValue>,
+  pub(super) sequence_number_to_transfers: &'a mut Table<'db, 'tx, (u32, u32), &'static [u8]>,
   pub(super) timestamp: u32,
   pub(super) unbound_inscriptions: u64,
   pub(super) value_cache: &'a mut HashMap<OutPoint, u64>,
@@ -346,7 +348,15 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         _ => new_satpoint,
       };
 
-      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+      self.update_inscription_location(
+        input_sat_ranges,
+        flotsam,
+        new_satpoint,
+        txid,
+        tx.output
+          .get(usize::try_from(new_satpoint.outpoint.vout).unwrap())
+          .cloned(),
+      )?;
     }
 
     if is_coinbase {
@@ -355,7 +365,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
-        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint, txid, None)?;
       }
       self.lost_sats += self.reward - output_value;
       Ok(())
@@ -393,6 +403,8 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
+    txid: Txid,
+    output: Option<TxOut>,
   ) -> Result {
     let inscription_id = flotsam.inscription_id;
     let (unbound, sequence_number) = match flotsam.origin {
@@ -407,14 +419,35 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           .unwrap()
           .value();
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
-            block_height: self.height,
-            inscription_id,
-            new_location: new_satpoint,
-            old_location: old_satpoint,
-            sequence_number,
-          })?;
+        self.event_sink.send(Event::InscriptionTransferred {
+          block_height: self.height,
+          inscription_id,
+          new_location: new_satpoint,
+          old_location: old_satpoint,
+          sequence_number,
+        })?;
+
+        if self.index_transfers {
+          let transfer_number = self
+            .sequence_number_to_transfers
+            .range((sequence_number, 0)..=(sequence_number, u32::MAX))?
+            .next_back()
+            .transpose()?
+            .map(|(key, _transfer)| key.value().1 + 1)
+            .unwrap_or(0);
+
+          self.sequence_number_to_transfers.insert(
+            (sequence_number, transfer_number),
+            TransferEntry {
+              height: self.height,
+              new_satpoint,
+              old_satpoint,
+              output,
+              txid,
+            }
+            .store()
+            .as_slice(),
+          )?;
         }
 
         (false, sequence_number)
@@ -512,16 +545,14 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           None => None,
         };
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::InscriptionCreated {
-            block_height: self.height,
-            charms,
-            inscription_id,
-            location: (!unbound).then_some(new_satpoint),
-            parent_inscription_id: parent,
-            sequence_number,
-          })?;
-        }
+        self.event_sink.send(Event::InscriptionCreated {
+          block_height: self.height,
+          charms,
+          inscription_id,
+          location: (!unbound).then_some(new_satpoint),
+          parent_inscription_id: parent,
+          sequence_number,
+        })?;
 
         self.sequence_number_to_entry.insert(
           sequence_number,
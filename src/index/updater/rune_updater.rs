@@ -1,6 +1,6 @@
 use {
   super::*,
-  crate::runes::{varint, Edict, Runestone},
+  crate::runes::{varint, Allocation, Runestone},
 };
 
 struct Claim {
@@ -18,26 +18,22 @@ struct Etched {
   symbol: Option<char>,
 }
 
-#[derive(Default)]
-pub(crate) struct RuneUpdate {
-  pub(crate) burned: u128,
-  pub(crate) mints: u64,
-  pub(crate) supply: u128,
-}
-
 pub(super) struct RuneUpdater<'a, 'db, 'tx> {
+  pub(super) event_sink: &'a EventSink<'a>,
   pub(super) height: u32,
   pub(super) id_to_entry: &'a mut Table<'db, 'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'db, 'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) rune_id_to_outpoint:
+    &'a mut MultimapTable<'db, 'tx, RuneIdValue, &'static OutPointValue>,
   pub(super) rune_to_id: &'a mut Table<'db, 'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'db, 'tx, u32, RuneIdValue>,
   pub(super) statistic_to_count: &'a mut Table<'db, 'tx, u64, u64>,
   pub(super) timestamp: u32,
   pub(super) transaction_id_to_rune: &'a mut Table<'db, 'tx, &'static TxidValue, u128>,
-  pub(super) updates: HashMap<RuneId, RuneUpdate>,
+  pub(super) updates: HashMap<RuneId, RuneActivity>,
 }
 
 impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
@@ -51,15 +47,9 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       .map(|runestone| runestone.burn)
       .unwrap_or_default();
 
-    let default_output = runestone.as_ref().and_then(|runestone| {
-      runestone
-        .default_output
-        .and_then(|default| usize::try_from(default).ok())
-    });
+    let mut etched = None;
 
-    let mut allocated: Vec<HashMap<u128, u128>> = vec![HashMap::new(); tx.output.len()];
-
-    if let Some(runestone) = runestone {
+    if let Some(runestone) = &runestone {
       if let Some(claim) = runestone
         .claim
         .and_then(|id| self.claim(id).transpose())
@@ -73,125 +63,39 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
           .or_default();
 
         update.mints += 1;
-        update.supply += claim.limit;
-      }
+        update.minted += claim.limit;
 
-      let mut etched = self.etched(index, &runestone)?;
-
-      if !burn {
-        for Edict { id, amount, output } in runestone.edicts {
-          let Ok(output) = usize::try_from(output) else {
-            continue;
-          };
-
-          // Skip edicts not referring to valid outputs
-          if output > tx.output.len() {
-            continue;
-          }
-
-          let (balance, id) = if id == 0 {
-            // If this edict allocates new issuance runes, skip it
-            // if no issuance was present, or if the issuance was invalid.
-            // Additionally, replace ID 0 with the newly assigned ID, and
-            // get the unallocated balance of the issuance.
-            match etched.as_mut() {
-              Some(Etched { balance, id, .. }) => (balance, *id),
-              None => continue,
-            }
-          } else {
-            // Get the unallocated balance of the given ID
-            match unallocated.get_mut(&id) {
-              Some(balance) => (balance, id),
-              None => continue,
-            }
-          };
-
-          let mut allocate = |balance: &mut u128, amount: u128, output: usize| {
-            if amount > 0 {
-              *balance -= amount;
-              *allocated[output].entry(id).or_default() += amount;
-            }
-          };
-
-          if output == tx.output.len() {
-            // find non-OP_RETURN outputs
-            let destinations = tx
-              .output
-              .iter()
-              .enumerate()
-              .filter_map(|(output, tx_out)| {
-                (!tx_out.script_pubkey.is_op_return()).then_some(output)
-              })
-              .collect::<Vec<usize>>();
-
-            if amount == 0 {
-              // if amount is zero, divide balance between eligible outputs
-              let amount = *balance / destinations.len() as u128;
-              let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
-
-              for (i, output) in destinations.iter().enumerate() {
-                allocate(
-                  balance,
-                  if i < remainder { amount + 1 } else { amount },
-                  *output,
-                );
-              }
-            } else {
-              // if amount is non-zero, distribute amount to eligible outputs
-              for output in destinations {
-                allocate(balance, amount.min(*balance), output);
-              }
-            }
-          } else {
-            // Get the allocatable amount
-            let amount = if amount == 0 {
-              *balance
-            } else {
-              amount.min(*balance)
-            };
-
-            allocate(balance, amount, output);
-          }
-        }
+        self.event_sink.send(Event::RuneMinted {
+          amount: claim.limit,
+          block_height: self.height,
+          rune_id: RuneId::try_from(claim.id).unwrap(),
+          txid,
+        })?;
       }
 
-      if let Some(etched) = etched {
-        self.create_rune_entry(txid, burn, etched)?;
-      }
+      etched = self.etched(index, runestone)?;
     }
 
-    let mut burned: HashMap<u128, u128> = HashMap::new();
-
-    if burn {
-      for (id, balance) in unallocated {
-        *burned.entry(id).or_default() += balance;
-      }
-    } else {
-      // assign all un-allocated runes to the default output, or the first non
-      // OP_RETURN output if there is no default, or if the default output is
-      // too large
-      if let Some(vout) = default_output
-        .filter(|vout| *vout < allocated.len())
-        .or_else(|| {
-          tx.output
-            .iter()
-            .enumerate()
-            .find(|(_vout, tx_out)| !tx_out.script_pubkey.is_op_return())
-            .map(|(vout, _tx_out)| vout)
-        })
-      {
-        for (id, balance) in unallocated {
-          if balance > 0 {
-            *allocated[vout].entry(id).or_default() += balance;
-          }
-        }
-      } else {
-        for (id, balance) in unallocated {
-          if balance > 0 {
-            *burned.entry(id).or_default() += balance;
-          }
-        }
-      }
+    let Allocation {
+      burned,
+      etched: etched_balance,
+      outputs: allocated,
+    } = Allocation::new(
+      tx,
+      runestone.as_ref(),
+      unallocated,
+      etched.as_ref().map(|etched| (etched.id, etched.balance)),
+    );
+
+    if let Some(etched) = etched {
+      self.create_rune_entry(
+        txid,
+        burn,
+        Etched {
+          balance: etched_balance,
+          ..etched
+        },
+      )?;
     }
 
     // update outpoint balances
@@ -201,14 +105,6 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         continue;
       }
 
-      // increment burned balances
-      if tx.output[vout].script_pubkey.is_op_return() {
-        for (id, balance) in &balances {
-          *burned.entry(*id).or_default() += balance;
-        }
-        continue;
-      }
-
       buffer.clear();
 
       let mut balances = balances.into_iter().collect::<Vec<(u128, u128)>>();
@@ -216,28 +112,53 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       // Sort balances by id so tests can assert balances in a fixed order
       balances.sort();
 
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      };
+
       for (id, balance) in balances {
         varint::encode_to_vec(id, &mut buffer);
         varint::encode_to_vec(balance, &mut buffer);
-      }
 
-      self.outpoint_to_balances.insert(
-        &OutPoint {
+        self
+          .rune_id_to_outpoint
+          .insert(RuneId::try_from(id).unwrap().store(), &outpoint.store())?;
+
+        let update = self
+          .updates
+          .entry(RuneId::try_from(id).unwrap())
+          .or_default();
+
+        update.transferred += balance;
+        update.transfers += 1;
+
+        self.event_sink.send(Event::RuneTransferred {
+          amount: balance,
+          block_height: self.height,
+          outpoint,
+          rune_id: RuneId::try_from(id).unwrap(),
           txid,
-          vout: vout.try_into().unwrap(),
-        }
-        .store(),
-        buffer.as_slice(),
-      )?;
+        })?;
+      }
+
+      self
+        .outpoint_to_balances
+        .insert(&outpoint.store(), buffer.as_slice())?;
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
-      self
-        .updates
-        .entry(RuneId::try_from(id).unwrap())
-        .or_default()
-        .burned += amount;
+      let rune_id = RuneId::try_from(id).unwrap();
+
+      self.updates.entry(rune_id).or_default().burned += amount;
+
+      self.event_sink.send(Event::RuneBurned {
+        amount,
+        block_height: self.height,
+        rune_id,
+        txid,
+      })?;
     }
 
     Ok(())
@@ -299,6 +220,14 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         .insert(sequence_number.value(), id.store())?;
     }
 
+    self.updates.entry(id).or_default().etched = true;
+
+    self.event_sink.send(Event::RuneEtched {
+      block_height: self.height,
+      rune_id: id,
+      txid,
+    })?;
+
     Ok(())
   }
 
@@ -345,15 +274,7 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
     };
 
     Ok(Some(Etched {
-      balance: if let Some(mint) = etching.mint {
-        if mint.term == Some(0) {
-          0
-        } else {
-          mint.limit.unwrap_or(runes::MAX_LIMIT)
-        }
-      } else {
-        u128::MAX
-      },
+      balance: etching.balance(),
       divisibility: etching.divisibility,
       id: u128::from(self.height) << 16 | u128::from(index),
       rune,
@@ -418,6 +339,11 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
           let (balance, len) = varint::decode(&buffer[i..]);
           i += len;
           *unallocated.entry(id).or_default() += balance;
+
+          self.rune_id_to_outpoint.remove(
+            RuneId::try_from(id).unwrap().store(),
+            &input.previous_output.store(),
+          )?;
         }
       }
     }
@@ -0,0 +1,651 @@
+use {
+  super::*,
+  crate::subcommand::index::verify::Check,
+  bitcoin::secp256k1::rand::{self, seq::IteratorRandom},
+};
+
+impl Index {
+  pub(crate) fn verify(&self, sample: Option<usize>) -> Result<BTreeMap<String, Check>> {
+    let rtx = self.database.begin_read()?;
+
+    let mut checks = BTreeMap::new();
+
+    if self.index_sats {
+      checks.insert("sat_ranges".into(), Self::verify_sat_ranges(&rtx)?);
+    }
+
+    checks.insert("satpoints".into(), Self::verify_satpoints(&rtx)?);
+    checks.insert(
+      "inscription_ids".into(),
+      Self::verify_inscription_ids(&rtx)?,
+    );
+    checks.insert(
+      "inscription_numbers".into(),
+      Self::verify_inscription_numbers(&rtx)?,
+    );
+
+    if self.index_runes {
+      checks.insert("rune_holders".into(), Self::verify_rune_holders(&rtx)?);
+      checks.insert("rune_supply".into(), Self::verify_rune_supply(&rtx)?);
+    }
+
+    if let Some(sample) = sample {
+      checks.insert(
+        "bitcoind".into(),
+        self.verify_against_bitcoind(&rtx, sample)?,
+      );
+    }
+
+    Ok(checks)
+  }
+
+  fn verify_sat_ranges(rtx: &redb::ReadTransaction) -> Result<Check> {
+    let mut check = Check::default();
+
+    let outpoint_to_sat_ranges = rtx.open_table(OUTPOINT_TO_SAT_RANGES)?;
+
+    for result in rtx.open_table(OUTPOINT_TO_VALUE)?.iter()? {
+      let (outpoint, value) = result?;
+      let value = value.value();
+
+      check.checked += 1;
+
+      let Some(sat_ranges) = outpoint_to_sat_ranges.get(outpoint.value())? else {
+        if value > 0 {
+          check.fail(format!(
+            "output {} has value {value} but no sat ranges",
+            OutPoint::load(*outpoint.value()),
+          ));
+        }
+        continue;
+      };
+
+      let total = sat_ranges
+        .value()
+        .chunks_exact(11)
+        .map(|chunk| {
+          let (start, end) = SatRange::load(chunk.try_into().unwrap());
+          end - start
+        })
+        .sum::<u64>();
+
+      if total != value {
+        check.fail(format!(
+          "sat ranges of output {} contain {total} sats but its value is {value}",
+          OutPoint::load(*outpoint.value()),
+        ));
+      }
+    }
+
+    Ok(check)
+  }
+
+  fn verify_satpoints(rtx: &redb::ReadTransaction) -> Result<Check> {
+    let mut check = Check::default();
+
+    let satpoint_to_sequence_number = rtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_satpoint = rtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+
+    for result in sequence_number_to_satpoint.iter()? {
+      let (sequence_number, satpoint) = result?;
+      let sequence_number = sequence_number.value();
+
+      check.checked += 1;
+
+      let mut found = false;
+      for result in satpoint_to_sequence_number.get(satpoint.value())? {
+        if result?.value() == sequence_number {
+          found = true;
+          break;
+        }
+      }
+
+      if !found {
+        check.fail(format!(
+          "satpoint {} of sequence number {sequence_number} does not map back to it",
+          SatPoint::load(*satpoint.value()),
+        ));
+      }
+    }
+
+    for result in satpoint_to_sequence_number.iter()? {
+      let (satpoint, sequence_numbers) = result?;
+      let satpoint = SatPoint::load(*satpoint.value());
+
+      for result in sequence_numbers {
+        let sequence_number = result?.value();
+
+        check.checked += 1;
+
+        let actual = sequence_number_to_satpoint
+          .get(sequence_number)?
+          .map(|satpoint| SatPoint::load(*satpoint.value()));
+
+        if actual != Some(satpoint) {
+          check.fail(format!(
+            "sequence number {sequence_number} at satpoint {satpoint} does not map back to it",
+          ));
+        }
+      }
+    }
+
+    Ok(check)
+  }
+
+  fn verify_inscription_ids(rtx: &redb::ReadTransaction) -> Result<Check> {
+    let mut check = Check::default();
+
+    let inscription_id_to_sequence_number = rtx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    for result in sequence_number_to_inscription_entry.iter()? {
+      let (sequence_number, entry) = result?;
+      let sequence_number = sequence_number.value();
+      let entry = InscriptionEntry::load(entry.value());
+
+      check.checked += 1;
+
+      let actual = inscription_id_to_sequence_number
+        .get(entry.id.store())?
+        .map(|sequence_number| sequence_number.value());
+
+      if actual != Some(sequence_number) {
+        check.fail(format!(
+          "inscription {} with sequence number {sequence_number} does not map back to it",
+          entry.id,
+        ));
+      }
+    }
+
+    let ids = inscription_id_to_sequence_number.len()?;
+    let entries = sequence_number_to_inscription_entry.len()?;
+
+    if ids != entries {
+      check.fail(format!(
+        "{ids} inscription ids map to sequence numbers but there are {entries} inscription entries",
+      ));
+    }
+
+    Ok(check)
+  }
+
+  fn verify_inscription_numbers(rtx: &redb::ReadTransaction) -> Result<Check> {
+    let mut check = Check::default();
+
+    let inscription_number_to_sequence_number =
+      rtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    for result in inscription_number_to_sequence_number.iter()? {
+      let (inscription_number, sequence_number) = result?;
+      let inscription_number = inscription_number.value();
+      let sequence_number = sequence_number.value();
+
+      check.checked += 1;
+
+      let actual = sequence_number_to_inscription_entry
+        .get(sequence_number)?
+        .map(|entry| InscriptionEntry::load(entry.value()).inscription_number);
+
+      if actual != Some(inscription_number) {
+        check.fail(format!(
+          "inscription number {inscription_number} maps to sequence number {sequence_number} which does not map back to it",
+        ));
+      }
+    }
+
+    for result in sequence_number_to_inscription_entry.iter()? {
+      let (sequence_number, entry) = result?;
+      let sequence_number = sequence_number.value();
+      let inscription_number = InscriptionEntry::load(entry.value()).inscription_number;
+
+      check.checked += 1;
+
+      let actual = inscription_number_to_sequence_number
+        .get(inscription_number)?
+        .map(|sequence_number| sequence_number.value());
+
+      if actual != Some(sequence_number) {
+        check.fail(format!(
+          "sequence number {sequence_number} has inscription number {inscription_number} which does not map back to it",
+        ));
+      }
+    }
+
+    Ok(check)
+  }
+
+  fn verify_rune_holders(rtx: &redb::ReadTransaction) -> Result<Check> {
+    let mut check = Check::default();
+
+    let mut holders = HashSet::<(RuneId, OutPoint)>::new();
+
+    for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+      let (outpoint, buffer) = result?;
+      let outpoint = OutPoint::load(*outpoint.value());
+      let buffer = buffer.value();
+
+      let mut i = 0;
+      while i < buffer.len() {
+        let (id, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+        let (_amount, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+
+        holders.insert((RuneId::try_from(id)?, outpoint));
+      }
+    }
+
+    for result in rtx.open_multimap_table(RUNE_ID_TO_OUTPOINT)?.iter()? {
+      let (id, outpoints) = result?;
+      let id = RuneId::load(id.value());
+
+      for result in outpoints {
+        let outpoint = OutPoint::load(*result?.value());
+
+        check.checked += 1;
+
+        if !holders.remove(&(id, outpoint)) {
+          check.fail(format!(
+            "rune {id} is indexed as held by output {outpoint} which does not hold it"
+          ));
+        }
+      }
+    }
+
+    for (id, outpoint) in holders {
+      check.checked += 1;
+      check.fail(format!(
+        "output {outpoint} holds rune {id} but is missing from the holder index"
+      ));
+    }
+
+    Ok(check)
+  }
+
+  fn verify_rune_supply(rtx: &redb::ReadTransaction) -> Result<Check> {
+    let mut check = Check::default();
+
+    let mut balances = HashMap::<RuneId, u128>::new();
+
+    for result in rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?.iter()? {
+      let (_outpoint, buffer) = result?;
+      let buffer = buffer.value();
+
+      let mut i = 0;
+      while i < buffer.len() {
+        let (id, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+        let (amount, length) = runes::varint::decode(&buffer[i..]);
+        i += length;
+
+        let balance = balances.entry(RuneId::try_from(id)?).or_default();
+        *balance = balance.saturating_add(amount);
+      }
+    }
+
+    for result in rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?.iter()? {
+      let (id, entry) = result?;
+      let id = RuneId::load(id.value());
+      let entry = RuneEntry::load(entry.value());
+
+      check.checked += 1;
+
+      let held = balances.remove(&id).unwrap_or_default();
+
+      if held.saturating_add(entry.burned) != entry.supply {
+        check.fail(format!(
+          "rune {} has supply {} but {held} are held and {} were burned",
+          entry.spaced_rune(),
+          entry.supply,
+          entry.burned,
+        ));
+      }
+    }
+
+    for (id, amount) in balances {
+      check.fail(format!("outputs hold {amount} of unknown rune {id}"));
+    }
+
+    Ok(check)
+  }
+
+  fn verify_against_bitcoind(&self, rtx: &redb::ReadTransaction, sample: usize) -> Result<Check> {
+    let mut check = Check::default();
+
+    let outpoint_to_value = rtx.open_table(OUTPOINT_TO_VALUE)?;
+
+    // reservoir sampling avoids loading every output into memory
+    let outputs = outpoint_to_value
+      .iter()?
+      .map(|result| {
+        result.map(|(outpoint, value)| (OutPoint::load(*outpoint.value()), value.value()))
+      })
+      .choose_multiple(
+        &mut rand::thread_rng(),
+        sample.min(usize::try_from(outpoint_to_value.len()?)?),
+      )
+      .into_iter()
+      .collect::<Result<Vec<(OutPoint, u64)>, StorageError>>()?;
+
+    for (outpoint, value) in outputs {
+      match self
+        .client
+        .get_tx_out(&outpoint.txid, outpoint.vout, Some(false))?
+      {
+        Some(txout) => {
+          check.checked += 1;
+
+          if txout.value.to_sat() != value {
+            check.fail(format!(
+              "output {outpoint} has value {value} but Bitcoin Core reports {}",
+              txout.value.to_sat(),
+            ));
+          }
+        }
+        None => {
+          // the genesis coinbase and unspendable outputs are never part of
+          // Bitcoin Core's UTXO set
+          let unspendable = outpoint.txid == self.genesis_block_coinbase_txid
+            || self
+              .get_transaction(outpoint.txid)?
+              .and_then(|tx| {
+                tx.output
+                  .get(usize::try_from(outpoint.vout).unwrap())
+                  .map(|output| output.script_pubkey.is_provably_unspendable())
+              })
+              .unwrap_or_default();
+
+          if !unspendable {
+            check.checked += 1;
+            check.fail(format!("output {outpoint} is not unspent in Bitcoin Core"));
+          }
+        }
+      }
+    }
+
+    Ok(check)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::{
+      index::testing::Context,
+      runes::{Edict, Etching, Runestone},
+    },
+  };
+
+  fn context() -> (Context, InscriptionId, RuneId) {
+    let context = Context::builder()
+      .args(["--index-sats", "--index-runes"])
+      .build();
+
+    context.mine_blocks(2);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 1000,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(99246114928149462)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    (
+      context,
+      InscriptionId { txid, index: 0 },
+      RuneId {
+        height: 3,
+        index: 2,
+      },
+    )
+  }
+
+  #[track_caller]
+  fn assert_failures(checks: &BTreeMap<String, Check>, expected: &[(&str, &str)]) {
+    let failures = checks
+      .iter()
+      .flat_map(|(name, check)| {
+        assert_eq!(check.failed, u64::try_from(check.errors.len()).unwrap());
+        check
+          .errors
+          .iter()
+          .map(move |error| (name.as_str(), error.as_str()))
+      })
+      .collect::<Vec<(&str, &str)>>();
+
+    assert_eq!(failures, expected);
+  }
+
+  #[test]
+  fn consistent_index_passes_all_checks() {
+    let (context, _, _) = context();
+
+    let checks = context.index.verify(Some(100)).unwrap();
+
+    assert_eq!(
+      checks.keys().map(String::as_str).collect::<Vec<&str>>(),
+      [
+        "bitcoind",
+        "inscription_ids",
+        "inscription_numbers",
+        "rune_holders",
+        "rune_supply",
+        "sat_ranges",
+        "satpoints",
+      ]
+    );
+
+    assert!(checks.values().all(|check| check.checked > 0));
+
+    assert_failures(&checks, &[]);
+  }
+
+  #[test]
+  fn checks_depend_on_indexed_data() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .verify(None)
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<&str>>(),
+      ["inscription_ids", "inscription_numbers", "satpoints"]
+    );
+  }
+
+  #[test]
+  fn mismatched_output_value_is_reported() {
+    let (context, inscription_id, _) = context();
+
+    let outpoint = OutPoint {
+      txid: inscription_id.txid,
+      vout: 0,
+    };
+
+    let wtx = context.index.begin_write().unwrap();
+    wtx
+      .open_table(OUTPOINT_TO_VALUE)
+      .unwrap()
+      .insert(&outpoint.store(), 1)
+      .unwrap();
+    wtx.commit().unwrap();
+
+    let checks = context.index.verify(Some(1000)).unwrap();
+
+    assert_failures(
+      &checks,
+      &[
+        (
+          "bitcoind",
+          &format!(
+            "output {outpoint} has value 1 but Bitcoin Core reports {}",
+            50 * COIN_VALUE
+          ),
+        ),
+        (
+          "sat_ranges",
+          &format!(
+            "sat ranges of output {outpoint} contain {} sats but its value is 1",
+            50 * COIN_VALUE
+          ),
+        ),
+      ],
+    );
+  }
+
+  #[test]
+  fn missing_satpoint_is_reported() {
+    let (context, inscription_id, _) = context();
+
+    let satpoint = SatPoint {
+      outpoint: OutPoint {
+        txid: inscription_id.txid,
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    let wtx = context.index.begin_write().unwrap();
+    wtx
+      .open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .remove_all(&satpoint.store())
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert_failures(
+      &context.index.verify(None).unwrap(),
+      &[(
+        "satpoints",
+        &format!("satpoint {satpoint} of sequence number 0 does not map back to it"),
+      )],
+    );
+  }
+
+  #[test]
+  fn inscription_tables_must_be_bijective() {
+    let (context, inscription_id, _) = context();
+
+    let wtx = context.index.begin_write().unwrap();
+    wtx
+      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .insert(1, 0)
+      .unwrap();
+    wtx
+      .open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)
+      .unwrap()
+      .insert(&inscription_id.store(), 1)
+      .unwrap();
+    wtx.commit().unwrap();
+
+    assert_failures(
+      &context.index.verify(None).unwrap(),
+      &[
+        (
+          "inscription_ids",
+          &format!("inscription {inscription_id} with sequence number 0 does not map back to it"),
+        ),
+        (
+          "inscription_numbers",
+          "inscription number 1 maps to sequence number 0 which does not map back to it",
+        ),
+      ],
+    );
+  }
+
+  #[test]
+  fn rune_supply_must_match_balances_and_burns() {
+    let (context, _, id) = context();
+
+    let wtx = context.index.begin_write().unwrap();
+    {
+      let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY).unwrap();
+      let mut entry = RuneEntry::load(
+        rune_id_to_rune_entry
+          .get(id.store())
+          .unwrap()
+          .unwrap()
+          .value(),
+      );
+      entry.burned = 1;
+      rune_id_to_rune_entry
+        .insert(id.store(), entry.store())
+        .unwrap();
+    }
+    wtx.commit().unwrap();
+
+    assert_failures(
+      &context.index.verify(None).unwrap(),
+      &[(
+        "rune_supply",
+        "rune AAAAAAAAAAAAA has supply 1000 but 1000 are held and 1 were burned",
+      )],
+    );
+  }
+
+  #[test]
+  fn rune_holder_index_must_match_balances() {
+    let (context, _, id) = context();
+
+    let outpoint = context.index.get_rune_holders(id).unwrap().balances[0].0;
+
+    let wtx = context.index.begin_write().unwrap();
+    {
+      let mut rune_id_to_outpoint = wtx.open_multimap_table(RUNE_ID_TO_OUTPOINT).unwrap();
+      rune_id_to_outpoint
+        .remove(id.store(), &outpoint.store())
+        .unwrap();
+      rune_id_to_outpoint
+        .insert(id.store(), &OutPoint::null().store())
+        .unwrap();
+    }
+    wtx.commit().unwrap();
+
+    assert_failures(
+      &context.index.verify(None).unwrap(),
+      &[
+        (
+          "rune_holders",
+          &format!(
+            "rune {id} is indexed as held by output {} which does not hold it",
+            OutPoint::null()
+          ),
+        ),
+        (
+          "rune_holders",
+          &format!("output {outpoint} holds rune {id} but is missing from the holder index"),
+        ),
+      ],
+    );
+  }
+}
@@ -22,10 +22,11 @@ use {
       teleburn, Charm, ParsedEnvelope,
     },
     representation::Representation,
-    runes::{Etching, Pile, SpacedRune},
+    runes::{Pile, SpacedRune, MAX_RUNESTONE_SIZE},
     settings::Settings,
     subcommand::{Subcommand, SubcommandResult},
     tally::Tally,
+    webhook::Webhook,
   },
   anyhow::{anyhow, bail, ensure, Context, Error},
   bip39::Mnemonic,
@@ -79,11 +80,11 @@ use {
 pub use self::{
   chain::Chain,
   fee_rate::FeeRate,
-  index::{Index, MintEntry, RuneEntry},
+  index::{Index, MintEntry, ReorgEntry, RuneActivity, RuneEntry},
   inscriptions::{Envelope, Inscription, InscriptionId},
   object::Object,
   options::Options,
-  runes::{Edict, Rune, RuneId, Runestone},
+  runes::{Edict, Etching, Flaw, Mint, Rune, RuneId, Runestone},
   wallet::transaction_builder::{Target, TransactionBuilder},
 };
 
@@ -111,6 +112,7 @@ mod decimal;
 mod fee_rate;
 pub mod index;
 mod inscriptions;
+mod mempool;
 mod object;
 pub mod options;
 pub mod outgoing;
@@ -122,6 +124,7 @@ pub mod subcommand;
 mod tally;
 pub mod templates;
 pub mod wallet;
+mod webhook;
 
 type Result<T = (), E = Error> = std::result::Result<T, E>;
 
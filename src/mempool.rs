@@ -0,0 +1,298 @@
+use {super::*, index::PendingRunes};
+
+/// What unconfirmed transactions would inscribe, transfer, etch and move.
+#[derive(Default)]
+pub(crate) struct Mempool {
+  state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+  height: Option<u32>,
+  transactions: BTreeMap<Txid, Pending>,
+}
+
+struct Pending {
+  parents: BTreeSet<Txid>,
+  transaction: api::PendingTransaction,
+}
+
+impl Mempool {
+  pub(crate) fn update(&self, index: &Index) -> Result {
+    let height = index.block_count()?;
+
+    let txids = index
+      .get_raw_mempool()?
+      .into_iter()
+      .collect::<BTreeSet<Txid>>();
+
+    let last = self.state.lock().unwrap().height;
+
+    // pending transactions are derived from the index, so those spending
+    // outputs of newly indexed blocks must be derived again
+    let confirmed = match last {
+      Some(last) if last <= height => {
+        let mut confirmed = BTreeSet::new();
+
+        for height in last..height {
+          if let Some(block) = index.get_block_by_height(height)? {
+            confirmed.extend(block.txdata.iter().map(Transaction::txid));
+          }
+        }
+
+        Some(confirmed)
+      }
+      _ => None,
+    };
+
+    let known = {
+      let state = self.state.lock().unwrap();
+
+      match &confirmed {
+        Some(confirmed) => state
+          .transactions
+          .iter()
+          .filter(|(_, pending)| pending.parents.is_disjoint(confirmed))
+          .map(|(txid, _)| *txid)
+          .collect(),
+        None => BTreeSet::new(),
+      }
+    };
+
+    let mut pending = Vec::new();
+
+    for txid in txids.difference(&known) {
+      // the transaction may have been confirmed or evicted since the mempool
+      // was listed
+      if let Some(transaction) = index.get_transaction(*txid)? {
+        pending.push(Pending {
+          parents: transaction
+            .input
+            .iter()
+            .map(|input| input.previous_output.txid)
+            .collect(),
+          transaction: Self::pending(index, *txid, &transaction)?,
+        });
+      }
+    }
+
+    let mut state = self.state.lock().unwrap();
+
+    if confirmed.is_none() {
+      state.transactions.clear();
+    }
+
+    state.height = Some(height);
+
+    state.transactions.retain(|txid, _| txids.contains(txid));
+
+    for pending in pending {
+      state.transactions.insert(pending.transaction.txid, pending);
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn get(&self, txid: Txid) -> Option<api::PendingTransaction> {
+    self
+      .state
+      .lock()
+      .unwrap()
+      .transactions
+      .get(&txid)
+      .map(|pending| pending.transaction.clone())
+  }
+
+  pub(crate) fn count(&self) -> usize {
+    self.state.lock().unwrap().transactions.len()
+  }
+
+  pub(crate) fn active(&self) -> Vec<api::PendingTransaction> {
+    self
+      .state
+      .lock()
+      .unwrap()
+      .transactions
+      .values()
+      .map(|pending| &pending.transaction)
+      .filter(|transaction| transaction.is_active())
+      .cloned()
+      .collect()
+  }
+
+  pub(crate) fn transfer(&self, inscription_id: InscriptionId) -> Option<api::PendingTransfer> {
+    self
+      .state
+      .lock()
+      .unwrap()
+      .transactions
+      .values()
+      .flat_map(|pending| &pending.transaction.transfers)
+      .find(|transfer| transfer.id == inscription_id)
+      .cloned()
+  }
+
+  fn pending(
+    index: &Index,
+    txid: Txid,
+    transaction: &Transaction,
+  ) -> Result<api::PendingTransaction> {
+    let inscriptions = (0..ParsedEnvelope::from_transaction(transaction).len())
+      .map(|i| InscriptionId {
+        txid,
+        index: u32::try_from(i).unwrap(),
+      })
+      .collect();
+
+    let runestone = Runestone::from_transaction(transaction);
+
+    let etching = runestone
+      .as_ref()
+      .and_then(|runestone| runestone.etching)
+      .and_then(|etching| {
+        etching.rune.map(|rune| SpacedRune {
+          rune,
+          spacers: etching.spacers,
+        })
+      });
+
+    let mut runes = Vec::new();
+
+    if index.has_rune_index() {
+      let PendingRunes { burned, outputs } =
+        index.get_pending_runes(transaction, runestone.as_ref())?;
+
+      for (vout, balances) in outputs.into_iter().enumerate() {
+        for (rune, pile) in balances {
+          runes.push(api::PendingRuneTransfer {
+            location: Some(OutPoint {
+              txid,
+              vout: u32::try_from(vout).unwrap(),
+            }),
+            pile,
+            rune,
+          });
+        }
+      }
+
+      for (rune, pile) in burned {
+        runes.push(api::PendingRuneTransfer {
+          location: None,
+          pile,
+          rune,
+        });
+      }
+    }
+
+    let inscribed_inputs = transaction
+      .input
+      .iter()
+      .map(|input| index.get_inscriptions_on_output_with_satpoints(input.previous_output))
+      .collect::<Result<Vec<_>>>()?;
+
+    let mut transfers = Vec::new();
+
+    if inscribed_inputs
+      .iter()
+      .any(|inscriptions| !inscriptions.is_empty())
+    {
+      // offset of the first sat of the current input in the transaction's
+      // input sats, unknown once an input's value can't be looked up
+      let mut offset = Some(0);
+
+      for (input, inscriptions) in transaction.input.iter().zip(inscribed_inputs) {
+        for (satpoint, id) in inscriptions {
+          transfers.push(api::PendingTransfer {
+            id,
+            location: offset
+              .and_then(|offset| Self::locate(txid, transaction, offset + satpoint.offset)),
+            txid,
+          });
+        }
+
+        let value = index
+          .get_transaction(input.previous_output.txid)?
+          .and_then(|previous| {
+            previous
+              .output
+              .get(usize::try_from(input.previous_output.vout).unwrap())
+              .map(|output| output.value)
+          });
+
+        offset = offset.zip(value).map(|(offset, value)| offset + value);
+      }
+    }
+
+    Ok(api::PendingTransaction {
+      etching,
+      inscriptions,
+      runes,
+      transfers,
+      txid,
+    })
+  }
+
+  fn locate(txid: Txid, transaction: &Transaction, offset: u64) -> Option<SatPoint> {
+    let mut start = 0;
+
+    for (vout, output) in transaction.output.iter().enumerate() {
+      let end = start + output.value;
+
+      if offset < end {
+        return Some(SatPoint {
+          outpoint: OutPoint {
+            txid,
+            vout: u32::try_from(vout).unwrap(),
+          },
+          offset: offset - start,
+        });
+      }
+
+      start = end;
+    }
+
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn locate() {
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![
+        TxOut {
+          value: 100,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 50,
+          script_pubkey: ScriptBuf::new(),
+        },
+      ],
+    };
+
+    #[track_caller]
+    fn case(transaction: &Transaction, offset: u64, expected: Option<(u32, u64)>) {
+      let txid = transaction.txid();
+      assert_eq!(
+        Mempool::locate(txid, transaction, offset),
+        expected.map(|(vout, offset)| SatPoint {
+          outpoint: OutPoint { txid, vout },
+          offset,
+        }),
+      );
+    }
+
+    case(&transaction, 0, Some((0, 0)));
+    case(&transaction, 99, Some((0, 99)));
+    case(&transaction, 100, Some((1, 0)));
+    case(&transaction, 149, Some((1, 49)));
+    case(&transaction, 150, None);
+  }
+}
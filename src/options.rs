@@ -21,6 +21,11 @@ pub struct Options {
     help = "Authenticate to Bitcoin Core RPC as <BITCOIN_RPC_USERNAME>."
   )]
   pub(crate) bitcoin_rpc_username: Option<String>,
+  #[arg(
+    long,
+    help = "Update index on block notifications from Bitcoin Core ZMQ publisher at <BITCOIN_ZMQ_URL>, e.g. `tcp://127.0.0.1:28332`. Polling continues as a fallback."
+  )]
+  pub(crate) bitcoin_zmq_url: Option<String>,
   #[arg(long = "chain", value_enum, help = "Use <CHAIN>. [default: mainnet]")]
   pub(crate) chain_argument: Option<Chain>,
   #[arg(
@@ -45,11 +50,18 @@ pub struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(long, help = "Track unspent output addresses.")]
+  pub(crate) index_addresses: bool,
   #[arg(
     long,
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(
+    long,
+    help = "Read blocks from Bitcoin Core's `blk*.dat` files when far behind the chain tip."
+  )]
+  pub(crate) index_from_blk_files: bool,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -61,8 +73,15 @@ pub struct Options {
   pub(crate) index_spent_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(long, help = "Record the history of inscription transfers.")]
+  pub(crate) index_transfers: bool,
   #[arg(long, help = "Run in integration test mode.")]
   pub(crate) integration_test: bool,
+  #[arg(
+    long,
+    help = "Keep <MAX_SAVEPOINTS> savepoints to roll back to after a reorg. [default: 2]"
+  )]
+  pub(crate) max_savepoints: Option<u32>,
   #[arg(long, help = "Minify JSON output.")]
   pub(crate) minify: bool,
   #[arg(
@@ -72,6 +91,16 @@ pub struct Options {
     help = "Do not index inscriptions."
   )]
   pub(crate) no_index_inscriptions: bool,
+  #[arg(
+    long,
+    help = "Only create savepoints within <SAVEPOINT_CHAIN_TIP_DISTANCE> blocks of the chain tip. [default: MAX_SAVEPOINTS * SAVEPOINT_INTERVAL + 1]"
+  )]
+  pub(crate) savepoint_chain_tip_distance: Option<u32>,
+  #[arg(
+    long,
+    help = "Create a savepoint every <SAVEPOINT_INTERVAL> blocks. [default: 10]"
+  )]
+  pub(crate) savepoint_interval: Option<u32>,
   #[arg(
     long,
     help = "Require basic HTTP authentication with <SERVER_PASSWORD>. Credentials are sent in cleartext. Consider using authentication in conjunction with HTTPS."
@@ -3,17 +3,28 @@ use {
   super::*,
 };
 
-pub use {edict::Edict, rune::Rune, rune_id::RuneId, runestone::Runestone};
+pub use {
+  edict::Edict,
+  etching::Etching,
+  flaw::Flaw,
+  mint::Mint,
+  rune::Rune,
+  rune_id::RuneId,
+  runestone::{Report, Runestone},
+};
 
-pub(crate) use {etching::Etching, mint::Mint, pile::Pile, spaced_rune::SpacedRune};
+pub(crate) use {allocation::Allocation, pile::Pile, spaced_rune::SpacedRune};
 
 pub const MAX_DIVISIBILITY: u8 = 38;
 pub(crate) const MAX_LIMIT: u128 = 1 << 64;
+pub(crate) const MAX_RUNESTONE_SIZE: usize = 82;
 const RESERVED: u128 = 6402364363415443603228541259936211926;
 
+mod allocation;
 mod edict;
 mod etching;
 mod flag;
+mod flaw;
 mod mint;
 mod pile;
 mod rune;
@@ -5426,4 +5437,107 @@ mod tests {
       )],
     );
   }
+
+  #[test]
+  fn rune_activity_is_recorded_per_block() {
+    let context = Context::builder().arg("--index-runes").build();
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            mint: Some(Mint {
+              limit: Some(1000),
+              ..Default::default()
+            }),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          claim: Some(u128::from(id)),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          burn: true,
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let etched = RuneActivity {
+      etched: true,
+      ..Default::default()
+    };
+
+    let minted = RuneActivity {
+      minted: 1000,
+      mints: 1,
+      transferred: 1000,
+      transfers: 1,
+      ..Default::default()
+    };
+
+    let burned = RuneActivity {
+      burned: 1000,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      context.index.get_rune_activity(id, 0, u32::MAX).unwrap(),
+      [(2, etched), (3, minted), (4, burned)]
+        .into_iter()
+        .collect(),
+    );
+
+    assert_eq!(
+      context.index.get_rune_activity(id, 3, 3).unwrap(),
+      [(3, minted)].into_iter().collect(),
+    );
+
+    let runes = context.index.get_rune_activity_in_block(3).unwrap();
+
+    assert_eq!(runes.len(), 1);
+    assert_eq!(runes[0].0.rune, Rune(RUNE));
+    assert_eq!(runes[0].1, minted);
+
+    assert!(context
+      .index
+      .get_rune_activity_in_block(1)
+      .unwrap()
+      .is_empty());
+  }
 }
@@ -0,0 +1,153 @@
+use super::*;
+
+/// Where the runes in a transaction's inputs, any claim, and any etched runes
+/// end up, keyed by rune ID.
+pub(crate) struct Allocation {
+  pub(crate) burned: HashMap<u128, u128>,
+  pub(crate) etched: u128,
+  pub(crate) outputs: Vec<HashMap<u128, u128>>,
+}
+
+impl Allocation {
+  pub(crate) fn new(
+    tx: &Transaction,
+    runestone: Option<&Runestone>,
+    mut unallocated: HashMap<u128, u128>,
+    etched: Option<(u128, u128)>,
+  ) -> Self {
+    let burn = runestone.map_or(false, |runestone| runestone.burn);
+
+    let mut outputs: Vec<HashMap<u128, u128>> = vec![HashMap::new(); tx.output.len()];
+
+    let (etched_id, mut etched_balance) = etched.unwrap_or_default();
+
+    if let Some(runestone) = runestone.filter(|_| !burn) {
+      for Edict { id, amount, output } in runestone.edicts.iter().copied() {
+        let Ok(output) = usize::try_from(output) else {
+          continue;
+        };
+
+        // Skip edicts not referring to valid outputs
+        if output > tx.output.len() {
+          continue;
+        }
+
+        let (balance, id) = if id == 0 {
+          // If this edict allocates new issuance runes, skip it
+          // if no issuance was present, or if the issuance was invalid.
+          // Additionally, replace ID 0 with the newly assigned ID, and
+          // get the unallocated balance of the issuance.
+          if etched.is_none() {
+            continue;
+          }
+
+          (&mut etched_balance, etched_id)
+        } else {
+          // Get the unallocated balance of the given ID
+          match unallocated.get_mut(&id) {
+            Some(balance) => (balance, id),
+            None => continue,
+          }
+        };
+
+        let mut allocate = |balance: &mut u128, amount: u128, output: usize| {
+          if amount > 0 {
+            *balance -= amount;
+            *outputs[output].entry(id).or_default() += amount;
+          }
+        };
+
+        if output == tx.output.len() {
+          // find non-OP_RETURN outputs
+          let destinations = tx
+            .output
+            .iter()
+            .enumerate()
+            .filter_map(|(output, tx_out)| (!tx_out.script_pubkey.is_op_return()).then_some(output))
+            .collect::<Vec<usize>>();
+
+          if destinations.is_empty() {
+            continue;
+          }
+
+          if amount == 0 {
+            // if amount is zero, divide balance between eligible outputs
+            let amount = *balance / destinations.len() as u128;
+            let remainder = usize::try_from(*balance % destinations.len() as u128).unwrap();
+
+            for (i, output) in destinations.iter().enumerate() {
+              allocate(
+                balance,
+                if i < remainder { amount + 1 } else { amount },
+                *output,
+              );
+            }
+          } else {
+            // if amount is non-zero, distribute amount to eligible outputs
+            for output in destinations {
+              allocate(balance, amount.min(*balance), output);
+            }
+          }
+        } else {
+          // Get the allocatable amount
+          let amount = if amount == 0 {
+            *balance
+          } else {
+            amount.min(*balance)
+          };
+
+          allocate(balance, amount, output);
+        }
+      }
+    }
+
+    let mut burned: HashMap<u128, u128> = HashMap::new();
+
+    if burn {
+      for (id, balance) in unallocated {
+        *burned.entry(id).or_default() += balance;
+      }
+    } else {
+      // assign all un-allocated runes to the default output, or the first non
+      // OP_RETURN output if there is no default, or if the default output is
+      // too large
+      if let Some(vout) = runestone
+        .and_then(|runestone| runestone.default_output)
+        .and_then(|default| usize::try_from(default).ok())
+        .filter(|vout| *vout < outputs.len())
+        .or_else(|| {
+          tx.output
+            .iter()
+            .position(|tx_out| !tx_out.script_pubkey.is_op_return())
+        })
+      {
+        for (id, balance) in unallocated {
+          if balance > 0 {
+            *outputs[vout].entry(id).or_default() += balance;
+          }
+        }
+      } else {
+        for (id, balance) in unallocated {
+          if balance > 0 {
+            *burned.entry(id).or_default() += balance;
+          }
+        }
+      }
+    }
+
+    // runes sent to OP_RETURN outputs are burned
+    for (balances, tx_out) in outputs.iter_mut().zip(&tx.output) {
+      if tx_out.script_pubkey.is_op_return() {
+        for (id, balance) in balances.drain() {
+          *burned.entry(id).or_default() += balance;
+        }
+      }
+    }
+
+    Self {
+      burned,
+      etched: etched_balance,
+      outputs,
+    }
+  }
+}
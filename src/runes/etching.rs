@@ -1,6 +1,6 @@
 use super::*;
 
-#[derive(Default, Serialize, Debug, PartialEq, Copy, Clone)]
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq, Copy, Clone)]
 pub struct Etching {
   pub divisibility: u8,
   pub mint: Option<Mint>,
@@ -8,3 +8,17 @@ pub struct Etching {
   pub spacers: u32,
   pub symbol: Option<char>,
 }
+
+impl Etching {
+  pub(crate) fn balance(&self) -> u128 {
+    if let Some(mint) = self.mint {
+      if mint.term == Some(0) {
+        0
+      } else {
+        mint.limit.unwrap_or(MAX_LIMIT)
+      }
+    } else {
+      u128::MAX
+    }
+  }
+}
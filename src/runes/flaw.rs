@@ -0,0 +1,96 @@
+use super::*;
+
+/// Flaws for which `burns` returns true cause input runes to be burned.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Flaw {
+  BurnFlag,
+  BurnTag,
+  Divisibility(u128),
+  EdictOutput {
+    edict: usize,
+    output: u128,
+    outputs: usize,
+  },
+  InvalidScript(String),
+  Spacers(u128),
+  Symbol(u128),
+  TruncatedVarint,
+  UnrecognizedEvenTag(u128),
+  UnrecognizedFlags(u128),
+  UnrecognizedOddTag(u128),
+}
+
+impl Flaw {
+  pub fn burns(&self) -> bool {
+    matches!(
+      self,
+      Self::BurnFlag | Self::BurnTag | Self::UnrecognizedEvenTag(_) | Self::UnrecognizedFlags(_)
+    )
+  }
+}
+
+impl Display for Flaw {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::BurnFlag => write!(f, "burn flag set"),
+      Self::BurnTag => write!(f, "burn tag set"),
+      Self::Divisibility(divisibility) => write!(
+        f,
+        "divisibility {divisibility} is greater than {MAX_DIVISIBILITY}, using 0"
+      ),
+      Self::EdictOutput {
+        edict,
+        output,
+        outputs,
+      } => write!(
+        f,
+        "edict {edict} allocates to output {output}, but transaction only has {outputs} outputs, edict ignored"
+      ),
+      Self::InvalidScript(err) => write!(f, "runestone script could not be parsed: {err}"),
+      Self::Spacers(spacers) => write!(f, "spacers {spacers:#b} out of range, ignored"),
+      Self::Symbol(symbol) => write!(f, "symbol {symbol} is not a valid character, ignored"),
+      Self::TruncatedVarint => write!(f, "payload ends with truncated varint"),
+      Self::UnrecognizedEvenTag(tag) => write!(f, "unrecognized even tag {tag}"),
+      Self::UnrecognizedFlags(flags) => write!(f, "unrecognized flags {flags:#b}"),
+      Self::UnrecognizedOddTag(tag) => write!(f, "unrecognized odd tag {tag}, ignored"),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn burns() {
+    assert!(Flaw::BurnFlag.burns());
+    assert!(Flaw::BurnTag.burns());
+    assert!(Flaw::UnrecognizedEvenTag(20).burns());
+    assert!(Flaw::UnrecognizedFlags(4).burns());
+    assert!(!Flaw::UnrecognizedOddTag(21).burns());
+    assert!(!Flaw::TruncatedVarint.burns());
+    assert!(!Flaw::Divisibility(39).burns());
+  }
+
+  #[test]
+  fn serde() {
+    assert_eq!(
+      serde_json::to_string(&Flaw::UnrecognizedEvenTag(20)).unwrap(),
+      r#"{"unrecognized_even_tag":20}"#
+    );
+    assert_eq!(
+      serde_json::to_string(&Flaw::BurnTag).unwrap(),
+      r#""burn_tag""#
+    );
+    assert_eq!(
+      serde_json::from_str::<Flaw>(r#"{"edict_output":{"edict":1,"output":5,"outputs":2}}"#)
+        .unwrap(),
+      Flaw::EdictOutput {
+        edict: 1,
+        output: 5,
+        outputs: 2,
+      }
+    );
+  }
+}
@@ -65,6 +65,27 @@ impl Rune {
     Rune(start - ((start - end) * remainder / u128::from(INTERVAL)))
   }
 
+  pub(crate) fn unlock_height(self, chain: Chain) -> Option<Height> {
+    if self.is_reserved() {
+      return None;
+    }
+
+    let mut low = 0;
+    let mut high = chain.first_rune_height() + SUBSIDY_HALVING_INTERVAL;
+
+    while low < high {
+      let middle = low + (high - low) / 2;
+
+      if self >= Self::minimum_at_height(chain, Height(middle)) {
+        high = middle;
+      } else {
+        low = middle + 1;
+      }
+    }
+
+    Some(Height(low))
+  }
+
   pub(crate) fn is_reserved(self) -> bool {
     self.0 >= RESERVED
   }
@@ -313,6 +334,49 @@ mod tests {
     case(Chain::Regtest, 1, "ZZXZUDIVTVQA");
   }
 
+  #[test]
+  fn unlock_height() {
+    #[track_caller]
+    fn case(chain: Chain, rune: &str, height: Option<u32>) {
+      assert_eq!(
+        rune.parse::<Rune>().unwrap().unlock_height(chain),
+        height.map(Height),
+      );
+    }
+
+    case(Chain::Mainnet, "AAAAAAAAAAAAA", Some(0));
+    case(
+      Chain::Mainnet,
+      "ZZYZXBRKWXVA",
+      Some(SUBSIDY_HALVING_INTERVAL * 4),
+    );
+    case(
+      Chain::Mainnet,
+      "ZZXZUDIVTVQA",
+      Some(SUBSIDY_HALVING_INTERVAL * 4 + 1),
+    );
+    case(Chain::Mainnet, "A", Some(SUBSIDY_HALVING_INTERVAL * 5 - 1));
+    case(Chain::Regtest, "ZZYZXBRKWXVA", Some(0));
+    case(Chain::Regtest, "ZZXZUDIVTVQA", Some(1));
+    case(Chain::Regtest, "AAAAAAAAAAAAAAAAAAAAAAAAAAA", None);
+
+    for chain in [
+      Chain::Mainnet,
+      Chain::Testnet,
+      Chain::Signet,
+      Chain::Regtest,
+    ] {
+      for rune in ["A", "AB", "BCGDE", "AAAAAAAA", "ZZZZZZZZZZZZZZZZZZZZZZZZZZ"] {
+        let rune = rune.parse::<Rune>().unwrap();
+        let height = rune.unlock_height(chain).unwrap();
+        assert!(rune >= Rune::minimum_at_height(chain, height));
+        if height.0 > 0 {
+          assert!(rune < Rune::minimum_at_height(chain, Height(height.0 - 1)));
+        }
+      }
+    }
+  }
+
   #[test]
   fn serde() {
     let rune = Rune(0);
@@ -2,7 +2,13 @@ use super::*;
 
 const MAX_SPACERS: u32 = 0b00000111_11111111_11111111_11111111;
 
-#[derive(Default, Serialize, Debug, PartialEq)]
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq)]
+pub struct Report {
+  pub flaws: Vec<Flaw>,
+  pub runestone: Option<Runestone>,
+}
+
+#[derive(Default, Serialize, Deserialize, Debug, PartialEq)]
 pub struct Runestone {
   pub burn: bool,
   pub claim: Option<u128>,
@@ -53,15 +59,53 @@ impl Runestone {
     Self::decipher(transaction).ok().flatten()
   }
 
+  /// Like `from_transaction`, but reports every flaw found.
+  pub fn diagnose(transaction: &Transaction) -> Report {
+    match Self::decipher_with_flaws(transaction) {
+      Ok(Some((runestone, flaws))) => Report {
+        flaws,
+        runestone: Some(runestone),
+      },
+      Ok(None) => Report::default(),
+      Err(err) => Report {
+        flaws: vec![Flaw::InvalidScript(err.to_string())],
+        runestone: None,
+      },
+    }
+  }
+
   fn decipher(transaction: &Transaction) -> Result<Option<Self>, script::Error> {
+    Ok(Self::decipher_with_flaws(transaction)?.map(|(runestone, _flaws)| runestone))
+  }
+
+  fn decipher_with_flaws(
+    transaction: &Transaction,
+  ) -> Result<Option<(Self, Vec<Flaw>)>, script::Error> {
     let Some(payload) = Runestone::payload(transaction)? else {
       return Ok(None);
     };
 
+    let mut flaws = Vec::new();
+
+    // a varint is terminated by a byte without the continuation bit set
+    if payload.last().map_or(false, |byte| byte & 0b1000_0000 != 0) {
+      flaws.push(Flaw::TruncatedVarint);
+    }
+
     let integers = Runestone::integers(&payload);
 
     let Message { mut fields, edicts } = Message::from_integers(&integers);
 
+    for (i, edict) in edicts.iter().enumerate() {
+      if usize::try_from(edict.output).map_or(true, |output| output > transaction.output.len()) {
+        flaws.push(Flaw::EdictOutput {
+          edict: i,
+          output: edict.output,
+          outputs: transaction.output.len(),
+        });
+      }
+    }
+
     let claim = Tag::Claim.take(&mut fields);
 
     let deadline = Tag::Deadline
@@ -74,8 +118,17 @@ impl Runestone {
 
     let divisibility = Tag::Divisibility
       .take(&mut fields)
-      .and_then(|divisibility| u8::try_from(divisibility).ok())
-      .and_then(|divisibility| (divisibility <= MAX_DIVISIBILITY).then_some(divisibility))
+      .and_then(|divisibility| {
+        let valid = u8::try_from(divisibility)
+          .ok()
+          .filter(|divisibility| *divisibility <= MAX_DIVISIBILITY);
+
+        if valid.is_none() {
+          flaws.push(Flaw::Divisibility(divisibility));
+        }
+
+        valid
+      })
       .unwrap_or_default();
 
     let limit = Tag::Limit
@@ -86,14 +139,28 @@ impl Runestone {
 
     let spacers = Tag::Spacers
       .take(&mut fields)
-      .and_then(|spacers| u32::try_from(spacers).ok())
-      .and_then(|spacers| (spacers <= MAX_SPACERS).then_some(spacers))
+      .and_then(|spacers| {
+        let valid = u32::try_from(spacers)
+          .ok()
+          .filter(|spacers| *spacers <= MAX_SPACERS);
+
+        if valid.is_none() {
+          flaws.push(Flaw::Spacers(spacers));
+        }
+
+        valid
+      })
       .unwrap_or_default();
 
-    let symbol = Tag::Symbol
-      .take(&mut fields)
-      .and_then(|symbol| u32::try_from(symbol).ok())
-      .and_then(char::from_u32);
+    let symbol = Tag::Symbol.take(&mut fields).and_then(|symbol| {
+      let valid = u32::try_from(symbol).ok().and_then(char::from_u32);
+
+      if valid.is_none() {
+        flaws.push(Flaw::Symbol(symbol));
+      }
+
+      valid
+    });
 
     let term = Tag::Term
       .take(&mut fields)
@@ -121,16 +188,42 @@ impl Runestone {
       None
     };
 
-    Ok(Some(Self {
-      burn: flags != 0 || fields.keys().any(|tag| tag % 2 == 0),
-      claim,
-      default_output,
-      edicts,
-      etching,
-    }))
+    if Flag::Burn.take(&mut flags) {
+      flaws.push(Flaw::BurnFlag);
+    }
+
+    if flags != 0 {
+      flaws.push(Flaw::UnrecognizedFlags(flags));
+    }
+
+    let mut tags = fields.keys().copied().collect::<Vec<u128>>();
+    tags.sort();
+
+    for tag in tags {
+      if Tag::Burn == tag {
+        flaws.push(Flaw::BurnTag);
+      } else if Tag::Nop == tag {
+        continue;
+      } else if tag % 2 == 0 {
+        flaws.push(Flaw::UnrecognizedEvenTag(tag));
+      } else {
+        flaws.push(Flaw::UnrecognizedOddTag(tag));
+      }
+    }
+
+    Ok(Some((
+      Self {
+        burn: flaws.iter().any(Flaw::burns),
+        claim,
+        default_output,
+        edicts,
+        etching,
+      },
+      flaws,
+    )))
   }
 
-  pub(crate) fn encipher(&self) -> ScriptBuf {
+  pub fn encipher(&self) -> ScriptBuf {
     let mut payload = Vec::new();
 
     if let Some(etching) = self.etching {
@@ -305,6 +398,172 @@ mod tests {
     );
   }
 
+  fn diagnose(payload: &[u8], outputs: usize) -> Report {
+    let payload: &PushBytes = payload.try_into().unwrap();
+
+    let mut output = vec![
+      TxOut {
+        script_pubkey: ScriptBuf::new(),
+        value: 0,
+      };
+      outputs
+    ];
+
+    output[0] = TxOut {
+      script_pubkey: script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_slice(b"RUNE_TEST")
+        .push_slice(payload)
+        .into_script(),
+      value: 0,
+    };
+
+    Runestone::diagnose(&Transaction {
+      input: Vec::new(),
+      output,
+      lock_time: LockTime::ZERO,
+      version: 2,
+    })
+  }
+
+  #[test]
+  fn diagnose_reports_script_errors() {
+    assert_eq!(
+      Runestone::diagnose(&Transaction {
+        input: Vec::new(),
+        output: vec![TxOut {
+          script_pubkey: ScriptBuf::from_bytes(vec![opcodes::all::OP_PUSHBYTES_4.to_u8()]),
+          value: 0,
+        }],
+        lock_time: LockTime::ZERO,
+        version: 2,
+      }),
+      Report {
+        flaws: vec![Flaw::InvalidScript("unexpected end of script".into())],
+        runestone: None,
+      },
+    );
+  }
+
+  #[test]
+  fn diagnose_reports_nothing_without_runestone() {
+    assert_eq!(
+      Runestone::diagnose(&Transaction {
+        input: Vec::new(),
+        output: Vec::new(),
+        lock_time: LockTime::ZERO,
+        version: 2,
+      }),
+      Report::default(),
+    );
+  }
+
+  #[test]
+  fn diagnose_reports_nothing_for_valid_runestone() {
+    let report = diagnose(&payload(&[Tag::Flags.into(), Flag::Etch.mask()]), 1);
+    assert!(report.flaws.is_empty());
+    assert!(!report.runestone.unwrap().burn);
+  }
+
+  #[test]
+  fn diagnose_reports_burn_flaws() {
+    let report = diagnose(
+      &payload(&[
+        Tag::Flags.into(),
+        Flag::Etch.mask() | Flag::Burn.mask() | 1 << 5,
+        Tag::Burn.into(),
+        0,
+        22,
+        0,
+        20,
+        0,
+        Tag::Nop.into(),
+        0,
+      ]),
+      1,
+    );
+
+    assert!(report.runestone.unwrap().burn);
+
+    assert_eq!(
+      report.flaws,
+      [
+        Flaw::BurnFlag,
+        Flaw::UnrecognizedFlags(1 << 5),
+        Flaw::UnrecognizedEvenTag(20),
+        Flaw::UnrecognizedEvenTag(22),
+        Flaw::BurnTag,
+      ]
+    );
+  }
+
+  #[test]
+  fn diagnose_reports_ignored_fields() {
+    let report = diagnose(
+      &payload(&[
+        Tag::Flags.into(),
+        Flag::Etch.mask(),
+        Tag::Divisibility.into(),
+        u128::from(MAX_DIVISIBILITY) + 1,
+        Tag::Spacers.into(),
+        u128::from(MAX_SPACERS) + 1,
+        Tag::Symbol.into(),
+        0xD800,
+        21,
+        0,
+      ]),
+      1,
+    );
+
+    let runestone = report.runestone.unwrap();
+
+    assert!(!runestone.burn);
+
+    assert_eq!(
+      runestone.etching,
+      Some(Etching {
+        divisibility: 0,
+        spacers: 0,
+        symbol: None,
+        ..Default::default()
+      })
+    );
+
+    assert_eq!(
+      report.flaws,
+      [
+        Flaw::Divisibility(u128::from(MAX_DIVISIBILITY) + 1),
+        Flaw::Spacers(u128::from(MAX_SPACERS) + 1),
+        Flaw::Symbol(0xD800),
+        Flaw::UnrecognizedOddTag(21),
+      ]
+    );
+  }
+
+  #[test]
+  fn diagnose_reports_edicts_past_outputs() {
+    let report = diagnose(&payload(&[Tag::Body.into(), 1, 1, 2, 0, 1, 3, 0, 1, 4]), 3);
+
+    assert!(!report.runestone.unwrap().burn);
+
+    assert_eq!(
+      report.flaws,
+      [Flaw::EdictOutput {
+        edict: 2,
+        output: 4,
+        outputs: 3,
+      }]
+    );
+  }
+
+  #[test]
+  fn diagnose_reports_truncated_varints() {
+    let mut payload = payload(&[Tag::Flags.into(), Flag::Etch.mask()]);
+    payload.push(0b1000_0000);
+
+    assert_eq!(diagnose(&payload, 1).flaws, [Flaw::TruncatedVarint]);
+  }
+
   #[test]
   fn deciphering_transaction_with_no_outputs_returns_none() {
     assert_eq!(
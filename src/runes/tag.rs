@@ -10,7 +10,6 @@ pub(super) enum Tag {
   Deadline = 10,
   DefaultOutput = 12,
   Claim = 14,
-  #[allow(unused)]
   Burn = 126,
 
   Divisibility = 1,
@@ -7,6 +7,7 @@ pub struct Settings {
   bitcoin_rpc_password: Option<String>,
   bitcoin_rpc_url: Option<String>,
   bitcoin_rpc_username: Option<String>,
+  bitcoin_zmq_url: Option<String>,
   chain: Option<Chain>,
   commit_interval: Option<usize>,
   config: Option<PathBuf>,
@@ -17,16 +18,23 @@ pub struct Settings {
   height_limit: Option<u32>,
   hidden: Option<HashSet<InscriptionId>>,
   index: Option<PathBuf>,
+  index_addresses: bool,
   index_cache_size: Option<usize>,
+  index_from_blk_files: bool,
   index_runes: bool,
   index_sats: bool,
   index_spent_sats: bool,
   index_transactions: bool,
+  index_transfers: bool,
   integration_test: bool,
+  max_savepoints: Option<u32>,
   no_index_inscriptions: bool,
+  savepoint_chain_tip_distance: Option<u32>,
+  savepoint_interval: Option<u32>,
   server_password: Option<String>,
   server_url: Option<String>,
   server_username: Option<String>,
+  webhooks: Option<Vec<Webhook>>,
 }
 
 impl Settings {
@@ -111,6 +119,7 @@ impl Settings {
       bitcoin_rpc_password: self.bitcoin_rpc_password.or(source.bitcoin_rpc_password),
       bitcoin_rpc_url: self.bitcoin_rpc_url.or(source.bitcoin_rpc_url),
       bitcoin_rpc_username: self.bitcoin_rpc_username.or(source.bitcoin_rpc_username),
+      bitcoin_zmq_url: self.bitcoin_zmq_url.or(source.bitcoin_zmq_url),
       chain: self.chain.or(source.chain),
       commit_interval: self.commit_interval.or(source.commit_interval),
       config: self.config.or(source.config),
@@ -131,16 +140,25 @@ impl Settings {
           .collect(),
       ),
       index: self.index.or(source.index),
+      index_addresses: self.index_addresses || source.index_addresses,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_from_blk_files: self.index_from_blk_files || source.index_from_blk_files,
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
       index_spent_sats: self.index_spent_sats || source.index_spent_sats,
       index_transactions: self.index_transactions || source.index_transactions,
+      index_transfers: self.index_transfers || source.index_transfers,
       integration_test: self.integration_test || source.integration_test,
+      max_savepoints: self.max_savepoints.or(source.max_savepoints),
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
+      savepoint_chain_tip_distance: self
+        .savepoint_chain_tip_distance
+        .or(source.savepoint_chain_tip_distance),
+      savepoint_interval: self.savepoint_interval.or(source.savepoint_interval),
       server_password: self.server_password.or(source.server_password),
       server_url: self.server_url.or(source.server_url),
       server_username: self.server_username.or(source.server_username),
+      webhooks: self.webhooks.or(source.webhooks),
     }
   }
 
@@ -150,6 +168,7 @@ impl Settings {
       bitcoin_rpc_password: options.bitcoin_rpc_password,
       bitcoin_rpc_url: options.bitcoin_rpc_url,
       bitcoin_rpc_username: options.bitcoin_rpc_username,
+      bitcoin_zmq_url: options.bitcoin_zmq_url,
       chain: options
         .signet
         .then_some(Chain::Signet)
@@ -165,16 +184,23 @@ impl Settings {
       height_limit: options.height_limit,
       hidden: None,
       index: options.index,
+      index_addresses: options.index_addresses,
       index_cache_size: options.index_cache_size,
+      index_from_blk_files: options.index_from_blk_files,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
       index_spent_sats: options.index_spent_sats,
       index_transactions: options.index_transactions,
+      index_transfers: options.index_transfers,
       integration_test: options.integration_test,
+      max_savepoints: options.max_savepoints,
       no_index_inscriptions: options.no_index_inscriptions,
+      savepoint_chain_tip_distance: options.savepoint_chain_tip_distance,
+      savepoint_interval: options.savepoint_interval,
       server_password: options.server_password,
       server_url: None,
       server_username: options.server_username,
+      webhooks: None,
     }
   }
 
@@ -233,6 +259,7 @@ impl Settings {
       bitcoin_rpc_password: get_string("BITCOIN_RPC_PASSWORD"),
       bitcoin_rpc_url: get_string("BITCOIN_RPC_URL"),
       bitcoin_rpc_username: get_string("BITCOIN_RPC_USERNAME"),
+      bitcoin_zmq_url: get_string("BITCOIN_ZMQ_URL"),
       chain: get_chain("CHAIN")?,
       commit_interval: get_usize("COMMIT_INTERVAL")?,
       config: get_path("CONFIG"),
@@ -243,16 +270,23 @@ impl Settings {
       height_limit: get_u32("HEIGHT_LIMIT")?,
       hidden: inscriptions("HIDDEN")?,
       index: get_path("INDEX"),
+      index_addresses: get_bool("INDEX_ADDRESSES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_from_blk_files: get_bool("INDEX_FROM_BLK_FILES"),
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
       index_spent_sats: get_bool("INDEX_SPENT_SATS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
+      index_transfers: get_bool("INDEX_TRANSFERS"),
       integration_test: get_bool("INTEGRATION_TEST"),
+      max_savepoints: get_u32("MAX_SAVEPOINTS")?,
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
+      savepoint_chain_tip_distance: get_u32("SAVEPOINT_CHAIN_TIP_DISTANCE")?,
+      savepoint_interval: get_u32("SAVEPOINT_INTERVAL")?,
       server_password: get_string("SERVER_PASSWORD"),
       server_url: get_string("SERVER_URL"),
       server_username: get_string("SERVER_USERNAME"),
+      webhooks: None,
     })
   }
 
@@ -262,6 +296,7 @@ impl Settings {
       bitcoin_rpc_password: None,
       bitcoin_rpc_url: Some(rpc_url.into()),
       bitcoin_rpc_username: None,
+      bitcoin_zmq_url: None,
       chain: Some(Chain::Regtest),
       commit_interval: None,
       config: None,
@@ -272,16 +307,23 @@ impl Settings {
       height_limit: None,
       hidden: None,
       index: None,
+      index_addresses: false,
       index_cache_size: None,
+      index_from_blk_files: false,
       index_runes: true,
       index_sats: true,
       index_spent_sats: false,
       index_transactions: false,
+      index_transfers: false,
       integration_test: false,
+      max_savepoints: None,
       no_index_inscriptions: false,
+      savepoint_chain_tip_distance: None,
+      savepoint_interval: None,
       server_password: None,
       server_url: Some(server_url.into()),
       server_username: None,
+      webhooks: None,
     }
   }
 
@@ -318,6 +360,17 @@ impl Settings {
       None => data_dir.join("index.redb"),
     };
 
+    let max_savepoints = self.max_savepoints.unwrap_or(2);
+
+    ensure!(max_savepoints > 0, "max savepoints must be at least 1");
+
+    let savepoint_interval = self.savepoint_interval.unwrap_or(10);
+
+    ensure!(
+      savepoint_interval > 0,
+      "savepoint interval must be at least 1"
+    );
+
     Ok(Self {
       bitcoin_data_dir: Some(bitcoin_data_dir),
       bitcoin_rpc_password: self.bitcoin_rpc_password,
@@ -328,6 +381,7 @@ impl Settings {
           .unwrap_or_else(|| format!("127.0.0.1:{}", chain.default_rpc_port())),
       ),
       bitcoin_rpc_username: self.bitcoin_rpc_username,
+      bitcoin_zmq_url: self.bitcoin_zmq_url,
       chain: Some(chain),
       commit_interval: Some(self.commit_interval.unwrap_or(5000)),
       config: None,
@@ -344,6 +398,7 @@ impl Settings {
       height_limit: self.height_limit,
       hidden: self.hidden,
       index: Some(index),
+      index_addresses: self.index_addresses,
       index_cache_size: Some(match self.index_cache_size {
         Some(index_cache_size) => index_cache_size,
         None => {
@@ -352,15 +407,25 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_from_blk_files: self.index_from_blk_files,
       index_runes: self.index_runes,
       index_sats: self.index_sats,
       index_spent_sats: self.index_spent_sats,
       index_transactions: self.index_transactions,
+      index_transfers: self.index_transfers,
       integration_test: self.integration_test,
+      max_savepoints: Some(max_savepoints),
       no_index_inscriptions: self.no_index_inscriptions,
+      savepoint_chain_tip_distance: Some(self.savepoint_chain_tip_distance.unwrap_or_else(|| {
+        max_savepoints
+          .saturating_mul(savepoint_interval)
+          .saturating_add(1)
+      })),
+      savepoint_interval: Some(savepoint_interval),
       server_password: self.server_password,
       server_url: self.server_url,
       server_username: self.server_username,
+      webhooks: self.webhooks,
     })
   }
 
@@ -445,6 +510,17 @@ impl Settings {
     Ok(client)
   }
 
+  pub(crate) fn bitcoin_zmq_url(&self) -> Option<&str> {
+    self.bitcoin_zmq_url.as_deref()
+  }
+
+  pub(crate) fn bitcoin_blocks_dir(&self) -> PathBuf {
+    self
+      .chain()
+      .join_with_data_dir(self.bitcoin_data_dir.as_ref().unwrap())
+      .join("blocks")
+  }
+
   pub(crate) fn chain(&self) -> Chain {
     self.chain.unwrap()
   }
@@ -506,10 +582,18 @@ impl Settings {
     self.index.as_ref().unwrap()
   }
 
+  pub(crate) fn index_addresses(&self) -> bool {
+    self.index_addresses
+  }
+
   pub(crate) fn index_inscriptions(&self) -> bool {
     !self.no_index_inscriptions
   }
 
+  pub(crate) fn index_from_blk_files(&self) -> bool {
+    self.index_from_blk_files
+  }
+
   pub(crate) fn index_runes(&self) -> bool {
     self.index_runes
   }
@@ -530,6 +614,10 @@ impl Settings {
     self.index_transactions
   }
 
+  pub(crate) fn index_transfers(&self) -> bool {
+    self.index_transfers
+  }
+
   pub(crate) fn integration_test(&self) -> bool {
     self.integration_test
   }
@@ -542,6 +630,22 @@ impl Settings {
       .unwrap_or_default()
   }
 
+  pub(crate) fn max_savepoints(&self) -> u32 {
+    self.max_savepoints.unwrap()
+  }
+
+  pub(crate) fn savepoint_chain_tip_distance(&self) -> u32 {
+    self.savepoint_chain_tip_distance.unwrap()
+  }
+
+  pub(crate) fn savepoint_interval(&self) -> u32 {
+    self.savepoint_interval.unwrap()
+  }
+
+  pub(crate) fn webhooks(&self) -> &[Webhook] {
+    self.webhooks.as_deref().unwrap_or_default()
+  }
+
   pub(crate) fn bitcoin_rpc_url(&self, wallet_name: Option<String>) -> String {
     let base_url = self.bitcoin_rpc_url.as_ref().unwrap();
     match wallet_name {
@@ -711,6 +815,42 @@ mod tests {
       }));
   }
 
+  #[test]
+  fn savepoint_defaults() {
+    let settings = parse(&[]);
+
+    assert_eq!(settings.max_savepoints(), 2);
+    assert_eq!(settings.savepoint_interval(), 10);
+    assert_eq!(settings.savepoint_chain_tip_distance(), 21);
+
+    let settings = parse(&["--max-savepoints=5", "--savepoint-interval=4"]);
+
+    assert_eq!(settings.savepoint_chain_tip_distance(), 21);
+
+    let settings = parse(&["--max-savepoints=5", "--savepoint-chain-tip-distance=100"]);
+
+    assert_eq!(settings.savepoint_chain_tip_distance(), 100);
+  }
+
+  #[test]
+  fn savepoint_settings_must_be_nonzero() {
+    assert_eq!(
+      Settings::from_options(Options::try_parse_from(["ord", "--max-savepoints=0"]).unwrap())
+        .or_defaults()
+        .unwrap_err()
+        .to_string(),
+      "max savepoints must be at least 1",
+    );
+
+    assert_eq!(
+      Settings::from_options(Options::try_parse_from(["ord", "--savepoint-interval=0"]).unwrap())
+        .or_defaults()
+        .unwrap_err()
+        .to_string(),
+      "savepoint interval must be at least 1",
+    );
+  }
+
   #[test]
   fn mainnet_cookie_file_path() {
     let cookie_file = parse(&[]).cookie_file().unwrap().display().to_string();
@@ -983,6 +1123,7 @@ mod tests {
       ("BITCOIN_RPC_PASSWORD", "bitcoin password"),
       ("BITCOIN_RPC_URL", "url"),
       ("BITCOIN_RPC_USERNAME", "bitcoin username"),
+      ("BITCOIN_ZMQ_URL", "tcp://127.0.0.1:28332"),
       ("CHAIN", "signet"),
       ("COMMIT_INTERVAL", "1"),
       ("CONFIG", "config"),
@@ -993,13 +1134,19 @@ mod tests {
       ("HEIGHT_LIMIT", "3"),
       ("HIDDEN", "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 703e5f7c49d82aab99e605af306b9a30e991e57d42f982908a962a81ac439832i0"),
       ("INDEX", "index"),
+      ("INDEX_ADDRESSES", "1"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_FROM_BLK_FILES", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
       ("INDEX_SPENT_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
+      ("INDEX_TRANSFERS", "1"),
       ("INTEGRATION_TEST", "1"),
+      ("MAX_SAVEPOINTS", "5"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
+      ("SAVEPOINT_CHAIN_TIP_DISTANCE", "6"),
+      ("SAVEPOINT_INTERVAL", "7"),
       ("SERVER_PASSWORD", "server password"),
       ("SERVER_URL", "server url"),
       ("SERVER_USERNAME", "server username"),
@@ -1015,6 +1162,7 @@ mod tests {
         bitcoin_rpc_password: Some("bitcoin password".into()),
         bitcoin_rpc_url: Some("url".into()),
         bitcoin_rpc_username: Some("bitcoin username".into()),
+        bitcoin_zmq_url: Some("tcp://127.0.0.1:28332".into()),
         chain: Some(Chain::Signet),
         commit_interval: Some(1),
         config: Some("config".into()),
@@ -1036,16 +1184,23 @@ mod tests {
           .collect()
         ),
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_from_blk_files: true,
         index_runes: true,
         index_sats: true,
         index_spent_sats: true,
         index_transactions: true,
+        index_transfers: true,
         integration_test: true,
+        max_savepoints: Some(5),
         no_index_inscriptions: true,
+        savepoint_chain_tip_distance: Some(6),
+        savepoint_interval: Some(7),
         server_password: Some("server password".into()),
         server_url: Some("server url".into()),
         server_username: Some("server username".into()),
+        webhooks: None,
       }
     );
   }
@@ -1060,6 +1215,7 @@ mod tests {
           "--bitcoin-rpc-password=bitcoin password",
           "--bitcoin-rpc-url=url",
           "--bitcoin-rpc-username=bitcoin username",
+          "--bitcoin-zmq-url=tcp://127.0.0.1:28332",
           "--chain=signet",
           "--commit-interval=1",
           "--config=config",
@@ -1068,14 +1224,20 @@ mod tests {
           "--data-dir=/data/dir",
           "--first-inscription-height=2",
           "--height-limit=3",
+          "--index-addresses",
           "--index-cache-size=4",
+          "--index-from-blk-files",
           "--index-runes",
           "--index-sats",
           "--index-spent-sats",
           "--index-transactions",
+          "--index-transfers",
           "--index=index",
           "--integration-test",
+          "--max-savepoints=5",
           "--no-index-inscriptions",
+          "--savepoint-chain-tip-distance=6",
+          "--savepoint-interval=7",
           "--server-password=server password",
           "--server-username=server username",
         ])
@@ -1086,6 +1248,7 @@ mod tests {
         bitcoin_rpc_password: Some("bitcoin password".into()),
         bitcoin_rpc_url: Some("url".into()),
         bitcoin_rpc_username: Some("bitcoin username".into()),
+        bitcoin_zmq_url: Some("tcp://127.0.0.1:28332".into()),
         chain: Some(Chain::Signet),
         commit_interval: Some(1),
         config: Some("config".into()),
@@ -1096,16 +1259,23 @@ mod tests {
         height_limit: Some(3),
         hidden: None,
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_from_blk_files: true,
         index_runes: true,
         index_sats: true,
         index_spent_sats: true,
         index_transactions: true,
+        index_transfers: true,
         integration_test: true,
+        max_savepoints: Some(5),
         no_index_inscriptions: true,
+        savepoint_chain_tip_distance: Some(6),
+        savepoint_interval: Some(7),
         server_password: Some("server password".into()),
         server_url: None,
         server_username: Some("server username".into()),
+        webhooks: None,
       }
     );
   }
@@ -8,6 +8,7 @@ pub mod find;
 pub mod index;
 pub mod list;
 pub mod parse;
+pub mod rune_name;
 pub mod runes;
 pub(crate) mod server;
 mod settings;
@@ -35,8 +36,10 @@ pub(crate) enum Subcommand {
   List(list::List),
   #[command(about = "Parse a satoshi from ordinal notation")]
   Parse(parse::Parse),
-  #[command(about = "List all runes")]
-  Runes,
+  #[command(about = "Check whether rune names are etched, reserved, or locked")]
+  RuneName(rune_name::RuneName),
+  #[command(about = "List runes")]
+  Runes(runes::Runes),
   #[command(about = "Run the explorer server")]
   Server(server::Server),
   #[command(about = "Display settings")]
@@ -64,12 +67,14 @@ impl Subcommand {
       Self::Index(index) => index.run(settings),
       Self::List(list) => list.run(settings),
       Self::Parse(parse) => parse.run(),
-      Self::Runes => runes::run(settings),
+      Self::RuneName(rune_name) => rune_name.run(settings),
+      Self::Runes(runes) => runes.run(settings),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&settings)?);
+        let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+        let index = Arc::new(server.open_index(&settings, Some(event_sender))?);
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(settings, index, handle)
+        server.run(settings, index, event_receiver, handle)
       }
       Self::Settings => settings::run(settings),
       Self::Subsidy(subsidy) => subsidy.run(),
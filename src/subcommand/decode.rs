@@ -1,13 +1,47 @@
-use super::*;
+use {
+  super::*,
+  crate::{index::PendingRunes, runes::Report},
+};
 
-#[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
+#[derive(Serialize, PartialEq, Deserialize, Debug)]
 pub struct CompactOutput {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub flows: Option<Flows>,
   pub inscriptions: Vec<CompactInscription>,
+  pub runestone: Option<Runestone>,
+  pub runestone_flaws: Vec<Flaw>,
 }
 
-#[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
+#[derive(Serialize, PartialEq, Deserialize, Debug)]
 pub struct RawOutput {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub flows: Option<Flows>,
   pub inscriptions: Vec<ParsedEnvelope>,
+  pub runestone: Option<Runestone>,
+  pub runestone_flaws: Vec<Flaw>,
+}
+
+#[derive(Serialize, PartialEq, Deserialize, Debug)]
+pub struct Input {
+  pub inscriptions: Vec<InscriptionId>,
+  pub outpoint: OutPoint,
+  pub runes: BTreeMap<SpacedRune, Pile>,
+  pub value: u64,
+}
+
+#[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Destination {
+  Fee { offset: u64 },
+  Output { vout: u32, offset: u64 },
+}
+
+#[derive(Serialize, PartialEq, Deserialize, Debug)]
+pub struct Flows {
+  pub burned: BTreeMap<SpacedRune, Pile>,
+  pub inputs: Vec<Input>,
+  pub inscriptions: BTreeMap<InscriptionId, Destination>,
+  pub outputs: Vec<BTreeMap<SpacedRune, Pile>>,
 }
 
 #[derive(Serialize, Eq, PartialEq, Deserialize, Debug)]
@@ -71,6 +105,11 @@ pub(crate) struct Decode {
     help = "Serialize inscriptions in a compact, human-readable format."
   )]
   compact: bool,
+  #[arg(
+    long,
+    help = "Use the index to show the inscriptions and runes in each input, and where they would be sent."
+  )]
+  flows: bool,
 }
 
 impl Decode {
@@ -87,16 +126,450 @@ impl Decode {
 
     let inscriptions = ParsedEnvelope::from_transaction(&transaction);
 
+    let Report {
+      flaws: runestone_flaws,
+      runestone,
+    } = Runestone::diagnose(&transaction);
+
+    let flows = if self.flows {
+      let index = Index::open(&settings)?;
+      index.update()?;
+      Some(Self::flows(&index, &transaction, runestone.as_ref())?)
+    } else {
+      None
+    };
+
     if self.compact {
       Ok(Some(Box::new(CompactOutput {
+        flows,
         inscriptions: inscriptions
           .clone()
           .into_iter()
           .map(|inscription| inscription.payload.try_into())
           .collect::<Result<Vec<CompactInscription>>>()?,
+        runestone,
+        runestone_flaws,
       })))
     } else {
-      Ok(Some(Box::new(RawOutput { inscriptions })))
+      Ok(Some(Box::new(RawOutput {
+        flows,
+        inscriptions,
+        runestone,
+        runestone_flaws,
+      })))
     }
   }
+
+  fn flows(
+    index: &Index,
+    transaction: &Transaction,
+    runestone: Option<&Runestone>,
+  ) -> Result<Flows> {
+    let mut inputs = Vec::new();
+    let mut inscriptions = BTreeMap::new();
+
+    let output_value = transaction
+      .output
+      .iter()
+      .map(|tx_out| tx_out.value)
+      .sum::<u64>();
+
+    let mut offset = 0;
+
+    for tx_in in &transaction.input {
+      let outpoint = tx_in.previous_output;
+
+      if outpoint.is_null() {
+        continue;
+      }
+
+      let value = index
+        .get_transaction(outpoint.txid)?
+        .and_then(|transaction| {
+          transaction
+            .output
+            .into_iter()
+            .nth(outpoint.vout.try_into().unwrap())
+        })
+        .ok_or_else(|| anyhow!("input {outpoint} not found"))?
+        .value;
+
+      let mut input = Input {
+        inscriptions: Vec::new(),
+        outpoint,
+        runes: BTreeMap::new(),
+        value,
+      };
+
+      for (satpoint, inscription_id) in index.get_inscriptions_on_output_with_satpoints(outpoint)? {
+        let sat = offset + satpoint.offset;
+
+        let mut start = 0;
+        let mut destination = Destination::Fee {
+          offset: sat.saturating_sub(output_value),
+        };
+
+        for (vout, tx_out) in transaction.output.iter().enumerate() {
+          if sat < start + tx_out.value {
+            destination = Destination::Output {
+              vout: vout.try_into().unwrap(),
+              offset: sat - start,
+            };
+            break;
+          }
+          start += tx_out.value;
+        }
+
+        input.inscriptions.push(inscription_id);
+        inscriptions.insert(inscription_id, destination);
+      }
+
+      if index.has_rune_index() {
+        input.runes = index
+          .get_rune_balances_for_outpoint(outpoint)?
+          .into_iter()
+          .collect();
+      }
+
+      offset += value;
+      inputs.push(input);
+    }
+
+    let PendingRunes { burned, outputs } = if index.has_rune_index() {
+      index.get_pending_runes(transaction, runestone)?
+    } else {
+      PendingRunes {
+        burned: BTreeMap::new(),
+        outputs: vec![BTreeMap::new(); transaction.output.len()],
+      }
+    };
+
+    Ok(Flows {
+      burned,
+      inputs,
+      inscriptions,
+      outputs,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::testing::Context,
+    bitcoin::{ScriptBuf, Sequence, TxIn},
+  };
+
+  const RUNE: u128 = 99246114928149462;
+
+  #[test]
+  fn flows_trace_inscriptions_and_runes() {
+    let context = Context::builder()
+      .arg("--index-runes")
+      .chain(Chain::Regtest)
+      .build();
+
+    context.mine_blocks(1);
+
+    let etching = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let reveal = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    let inscription_id = InscriptionId {
+      txid: reveal,
+      index: 0,
+    };
+
+    let inputs = [
+      OutPoint {
+        txid: etching,
+        vout: 0,
+      },
+      OutPoint {
+        txid: reveal,
+        vout: 0,
+      },
+    ];
+
+    let values = inputs.map(|outpoint| {
+      context
+        .index
+        .get_transaction(outpoint.txid)
+        .unwrap()
+        .unwrap()
+        .output[0]
+        .value
+    });
+
+    let runestone = Runestone {
+      edicts: vec![
+        Edict {
+          id: id.into(),
+          amount: 100,
+          output: 1,
+        },
+        Edict {
+          id: id.into(),
+          amount: 5,
+          output: 2,
+        },
+      ],
+      ..Default::default()
+    };
+
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .iter()
+        .map(|outpoint| TxIn {
+          previous_output: *outpoint,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: vec![
+        TxOut {
+          value: values[0],
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: values[1] - 1000,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 0,
+          script_pubkey: runestone.encipher(),
+        },
+      ],
+    };
+
+    let spaced_rune = SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 0,
+    };
+
+    let pile = |amount| Pile {
+      amount,
+      divisibility: 0,
+      symbol: None,
+    };
+
+    assert_eq!(
+      Decode::flows(&context.index, &transaction, Some(&runestone)).unwrap(),
+      Flows {
+        burned: [(spaced_rune, pile(5))].into_iter().collect(),
+        inputs: vec![
+          Input {
+            inscriptions: Vec::new(),
+            outpoint: inputs[0],
+            runes: [(spaced_rune, pile(u128::MAX))].into_iter().collect(),
+            value: values[0],
+          },
+          Input {
+            inscriptions: vec![inscription_id],
+            outpoint: inputs[1],
+            runes: BTreeMap::new(),
+            value: values[1],
+          },
+        ],
+        inscriptions: [(inscription_id, Destination::Output { vout: 1, offset: 0 })]
+          .into_iter()
+          .collect(),
+        outputs: vec![
+          [(spaced_rune, pile(u128::MAX - 105))].into_iter().collect(),
+          [(spaced_rune, pile(100))].into_iter().collect(),
+          BTreeMap::new(),
+        ],
+      }
+    );
+
+    let burn = Runestone {
+      burn: true,
+      ..Default::default()
+    };
+
+    let mut transaction = transaction;
+    transaction.output.truncate(1);
+    transaction.output.push(TxOut {
+      value: 0,
+      script_pubkey: burn.encipher(),
+    });
+
+    let flows = Decode::flows(&context.index, &transaction, Some(&burn)).unwrap();
+
+    assert_eq!(
+      flows.burned,
+      [(spaced_rune, pile(u128::MAX))].into_iter().collect()
+    );
+
+    assert_eq!(
+      flows.inscriptions[&inscription_id],
+      Destination::Fee { offset: 0 }
+    );
+  }
+
+  #[test]
+  fn flows_include_claims_and_etchings() {
+    let context = Context::builder()
+      .arg("--index-runes")
+      .chain(Chain::Regtest)
+      .build();
+
+    context.mine_blocks(1);
+
+    let etching = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 10,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            mint: Some(Mint {
+              limit: Some(1000),
+              ..Default::default()
+            }),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    let outpoint = OutPoint {
+      txid: etching,
+      vout: 0,
+    };
+
+    let value = context
+      .index
+      .get_transaction(etching)
+      .unwrap()
+      .unwrap()
+      .output[0]
+      .value;
+
+    let runestone = Runestone {
+      claim: Some(id.into()),
+      edicts: vec![Edict {
+        id: 0,
+        amount: 7,
+        output: 1,
+      }],
+      etching: Some(Etching {
+        divisibility: 1,
+        rune: Some(Rune(RUNE + 1)),
+        symbol: Some('x'),
+        ..Default::default()
+      }),
+      ..Default::default()
+    };
+
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![
+        TxOut {
+          value: value - 1000,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 0,
+          script_pubkey: ScriptBuf::new(),
+        },
+        TxOut {
+          value: 0,
+          script_pubkey: runestone.encipher(),
+        },
+      ],
+    };
+
+    let flows = Decode::flows(&context.index, &transaction, Some(&runestone)).unwrap();
+
+    assert_eq!(flows.burned, BTreeMap::new());
+
+    assert_eq!(
+      flows.outputs,
+      vec![
+        [(
+          SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0,
+          },
+          Pile {
+            amount: 1010,
+            divisibility: 0,
+            symbol: None,
+          }
+        )]
+        .into_iter()
+        .collect(),
+        [(
+          SpacedRune {
+            rune: Rune(RUNE + 1),
+            spacers: 0,
+          },
+          Pile {
+            amount: 7,
+            divisibility: 1,
+            symbol: Some('x'),
+          }
+        )]
+        .into_iter()
+        .collect(),
+        BTreeMap::new(),
+      ]
+    );
+  }
 }
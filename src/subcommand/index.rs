@@ -2,7 +2,11 @@ use super::*;
 
 mod export;
 pub mod info;
+pub mod migrate;
+mod restore;
+pub mod snapshot;
 mod update;
+pub mod verify;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
@@ -10,8 +14,16 @@ pub(crate) enum IndexSubcommand {
   Export(export::Export),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Upgrade the index to the current schema in place")]
+  Migrate(migrate::Migrate),
+  #[command(about = "Install an index snapshot")]
+  Restore(restore::Restore),
+  #[command(about = "Write a consistent copy of the index")]
+  Snapshot(snapshot::Snapshot),
   #[command(about = "Update the index", alias = "run")]
   Update,
+  #[command(about = "Check index invariants")]
+  Verify(verify::Verify),
 }
 
 impl IndexSubcommand {
@@ -19,7 +31,11 @@ impl IndexSubcommand {
     match self {
       Self::Export(export) => export.run(settings),
       Self::Info(info) => info.run(settings),
+      Self::Migrate(migrate) => migrate.run(settings),
+      Self::Restore(restore) => restore.run(settings),
+      Self::Snapshot(snapshot) => snapshot.run(settings),
       Self::Update => update::run(settings),
+      Self::Verify(verify) => verify.run(settings),
     }
   }
 }
@@ -0,0 +1,28 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Migrate {
+  #[arg(long, help = "Print migrations without running them.")]
+  dry_run: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+  pub description: String,
+  pub from: u64,
+  pub to: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub dry_run: bool,
+  pub from: u64,
+  pub steps: Vec<Step>,
+  pub to: u64,
+}
+
+impl Migrate {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    Ok(Some(Box::new(Index::migrate(&settings, self.dry_run)?)))
+  }
+}
@@ -0,0 +1,15 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Restore {
+  #[arg(help = "Restore snapshot from directory <PATH>.")]
+  path: PathBuf,
+}
+
+impl Restore {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    Ok(Some(Box::new(Index::restore(
+      &settings, &self.path, false,
+    )?)))
+  }
+}
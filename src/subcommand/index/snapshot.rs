@@ -0,0 +1,32 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Snapshot {
+  #[arg(help = "Write snapshot to directory <PATH>, which must not exist.")]
+  path: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+  pub block_hash: Option<BlockHash>,
+  pub chain: Chain,
+  pub height: Option<u32>,
+  pub index_addresses: bool,
+  pub index_inscriptions: bool,
+  pub index_runes: bool,
+  pub index_sats: bool,
+  pub index_spent_sats: bool,
+  pub index_transactions: bool,
+  pub index_transfers: bool,
+  pub schema_version: u64,
+}
+
+impl Snapshot {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
+
+    Ok(Some(Box::new(index.snapshot(&self.path)?)))
+  }
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+const MAX_ERRORS: usize = 100;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Verify {
+  #[arg(
+    long,
+    help = "Cross-check <SAMPLE> randomly chosen unspent outputs against Bitcoin Core."
+  )]
+  sample: Option<usize>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Check {
+  pub checked: u64,
+  pub errors: Vec<String>,
+  pub failed: u64,
+}
+
+impl Check {
+  pub(crate) fn fail(&mut self, error: String) {
+    self.failed += 1;
+    if self.errors.len() < MAX_ERRORS {
+      self.errors.push(error);
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub checks: BTreeMap<String, Check>,
+  pub ok: bool,
+}
+
+impl Verify {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
+
+    let checks = index.verify(self.sample)?;
+
+    Ok(Some(Box::new(Output {
+      ok: checks.values().all(|check| check.failed == 0),
+      checks,
+    })))
+  }
+}
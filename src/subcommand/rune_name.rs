@@ -0,0 +1,29 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct RuneName {
+  #[arg(required = true, help = "Check availability of rune <NAMES>.")]
+  names: Vec<SpacedRune>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub runes: Vec<api::RuneAvailability>,
+}
+
+impl RuneName {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    ensure!(
+      index.has_rune_index(),
+      "`ord rune-name` requires index created with `--index-runes` flag",
+    );
+
+    index.update()?;
+
+    Ok(Some(Box::new(Output {
+      runes: index.rune_availability(&self.names)?,
+    })))
+  }
+}
@@ -1,10 +1,54 @@
-use super::*;
+use {super::*, clap::ValueEnum};
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Sort {
+  Burned,
+  Mints,
+  #[default]
+  Number,
+  Supply,
+  Timestamp,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Order {
+  #[default]
+  Asc,
+  Desc,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Parser, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Runes {
+  #[arg(long, help = "Only list runes that can be minted in the next block.")]
+  #[serde(default)]
+  pub(crate) mintable: bool,
+  #[arg(long, value_enum, help = "List runes in <ORDER> order. [default: asc]")]
+  pub(crate) order: Option<Order>,
+  #[arg(long, help = "Only list page <PAGE> of runes, 100 per page.")]
+  pub(crate) page: Option<u32>,
+  #[arg(
+    long,
+    help = "Only list runes whose name starts with <PREFIX>. May contain `.` or `•` as spacers."
+  )]
+  pub(crate) prefix: Option<String>,
+  #[arg(long, value_enum, help = "Sort runes by <SORT>. [default: number]")]
+  pub(crate) sort: Option<Sort>,
+}
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Output {
   pub runes: BTreeMap<Rune, RuneInfo>,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ListOutput {
+  pub more: bool,
+  pub runes: Vec<RuneInfo>,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct RuneInfo {
   pub burned: u128,
@@ -23,58 +67,307 @@ pub struct RuneInfo {
   pub timestamp: DateTime<Utc>,
 }
 
-pub(crate) fn run(settings: Settings) -> SubcommandResult {
-  let index = Index::open(&settings)?;
+impl Runes {
+  pub(crate) const PAGE_SIZE: usize = 100;
+
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    ensure!(
+      index.has_rune_index(),
+      "`ord runes` requires index created with `--index-runes` flag",
+    );
+
+    index.update()?;
+
+    let (entries, more) = index.runes_matching(&self)?;
+
+    let runes = entries.into_iter().map(
+      |(
+        id,
+        RuneEntry {
+          burned,
+          divisibility,
+          etching,
+          mint,
+          mints,
+          number,
+          rune,
+          spacers,
+          supply,
+          symbol,
+          timestamp,
+        },
+      )| RuneInfo {
+        burned,
+        divisibility,
+        etching,
+        height: id.height,
+        id,
+        index: id.index,
+        mint,
+        mints,
+        number,
+        rune,
+        spacers,
+        supply,
+        symbol,
+        timestamp: crate::timestamp(timestamp),
+      },
+    );
+
+    if self == Self::default() {
+      Ok(Some(Box::new(Output {
+        runes: runes.map(|info| (info.rune, info)).collect(),
+      })))
+    } else {
+      Ok(Some(Box::new(ListOutput {
+        more,
+        runes: runes.collect(),
+      })))
+    }
+  }
+
+  pub(crate) fn matches(&self, entry: &RuneEntry, height: u32, timestamp: u32) -> bool {
+    if self.mintable && !entry.mintable(height, timestamp) {
+      return false;
+    }
+
+    if let Some(prefix) = &self.prefix {
+      let prefix = prefix
+        .chars()
+        .filter(|c| *c != '.' && *c != '•')
+        .collect::<String>()
+        .to_uppercase();
+
+      if !entry.rune.to_string().starts_with(&prefix) {
+        return false;
+      }
+    }
+
+    true
+  }
+
+  pub(crate) fn sort(&self, entries: &mut [(RuneId, RuneEntry)]) {
+    entries.sort_by(|(a_id, a), (b_id, b)| {
+      let ordering = match self.sort.unwrap_or_default() {
+        Sort::Burned => a.burned.cmp(&b.burned),
+        Sort::Mints => a.mints.cmp(&b.mints),
+        Sort::Number => a.number.cmp(&b.number),
+        Sort::Supply => a.supply.cmp(&b.supply),
+        Sort::Timestamp => a.timestamp.cmp(&b.timestamp),
+      }
+      .then_with(|| a_id.cmp(b_id));
+
+      match self.order.unwrap_or_default() {
+        Order::Asc => ordering,
+        Order::Desc => ordering.reverse(),
+      }
+    });
+  }
+
+  pub(crate) fn paginate(
+    &self,
+    entries: Vec<(RuneId, RuneEntry)>,
+  ) -> (Vec<(RuneId, RuneEntry)>, bool) {
+    let Some(page) = self.page else {
+      return (entries, false);
+    };
+
+    let start = usize::try_from(page)
+      .unwrap()
+      .saturating_mul(Self::PAGE_SIZE);
+
+    let more = entries.len() > start.saturating_add(Self::PAGE_SIZE);
+
+    (
+      entries
+        .into_iter()
+        .skip(start)
+        .take(Self::PAGE_SIZE)
+        .collect(),
+      more,
+    )
+  }
+
+  pub(crate) fn query_string(&self, page: u32) -> String {
+    let mut query = vec![format!("page={page}")];
+
+    if self.mintable {
+      query.push("mintable=true".into());
+    }
+
+    if let Some(order) = self.order {
+      query.push(format!(
+        "order={}",
+        order.to_possible_value().unwrap().get_name()
+      ));
+    }
+
+    if let Some(prefix) = &self.prefix {
+      query.push(format!("prefix={prefix}"));
+    }
+
+    if let Some(sort) = self.sort {
+      query.push(format!(
+        "sort={}",
+        sort.to_possible_value().unwrap().get_name()
+      ));
+    }
+
+    query.join("&")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn entries() -> Vec<(RuneId, RuneEntry)> {
+    (0..3u64)
+      .map(|n| {
+        (
+          RuneId {
+            height: 1,
+            index: u16::try_from(n).unwrap(),
+          },
+          RuneEntry {
+            burned: u128::from((n * 2) % 3),
+            mint: (n == 1).then_some(MintEntry {
+              deadline: None,
+              end: Some(10),
+              limit: Some(1),
+            }),
+            number: n,
+            rune: Rune(u128::from(n) + 26),
+            spacers: 1,
+            supply: u128::from(3 - n),
+            ..Default::default()
+          },
+        )
+      })
+      .collect()
+  }
+
+  fn numbers(entries: &[(RuneId, RuneEntry)]) -> Vec<u64> {
+    entries.iter().map(|(_, entry)| entry.number).collect()
+  }
+
+  #[test]
+  fn sort() {
+    let mut entries = entries();
+
+    Runes::default().sort(&mut entries);
+    assert_eq!(numbers(&entries), [0, 1, 2]);
+
+    Runes {
+      order: Some(Order::Desc),
+      ..Default::default()
+    }
+    .sort(&mut entries);
+    assert_eq!(numbers(&entries), [2, 1, 0]);
 
-  ensure!(
-    index.has_rune_index(),
-    "`ord runes` requires index created with `--index-runes` flag",
-  );
+    Runes {
+      sort: Some(Sort::Supply),
+      ..Default::default()
+    }
+    .sort(&mut entries);
+    assert_eq!(numbers(&entries), [2, 1, 0]);
 
-  index.update()?;
+    Runes {
+      sort: Some(Sort::Burned),
+      order: Some(Order::Desc),
+      ..Default::default()
+    }
+    .sort(&mut entries);
+    assert_eq!(numbers(&entries), [1, 2, 0]);
+  }
 
-  Ok(Some(Box::new(Output {
-    runes: index
-      .runes()?
-      .into_iter()
-      .map(
-        |(
-          id,
+  #[test]
+  fn filter() {
+    let entries = entries();
+
+    let matching = |runes: Runes, height: u32| {
+      entries
+        .iter()
+        .filter(|(_, entry)| runes.matches(entry, height, 0))
+        .map(|(_, entry)| entry.number)
+        .collect::<Vec<u64>>()
+    };
+
+    assert_eq!(matching(Runes::default(), 0), [0, 1, 2]);
+
+    let mintable = || Runes {
+      mintable: true,
+      ..Default::default()
+    };
+
+    assert_eq!(matching(mintable(), 9), [1]);
+    assert_eq!(matching(mintable(), 10), [] as [u64; 0]);
+
+    let prefix = |prefix: &str| Runes {
+      prefix: Some(prefix.into()),
+      ..Default::default()
+    };
+
+    assert_eq!(matching(prefix("A"), 0), [0, 1, 2]);
+    assert_eq!(matching(prefix("AB"), 0), [1]);
+    assert_eq!(matching(prefix("a.b"), 0), [1]);
+    assert_eq!(matching(prefix("A•C"), 0), [2]);
+    assert_eq!(matching(prefix("AD"), 0), [] as [u64; 0]);
+  }
+
+  #[test]
+  fn paginate() {
+    let entries = (0..250u64)
+      .map(|n| {
+        (
+          RuneId {
+            height: 1,
+            index: 0,
+          },
           RuneEntry {
-            burned,
-            divisibility,
-            etching,
-            mint,
-            mints,
-            number,
-            rune,
-            spacers,
-            supply,
-            symbol,
-            timestamp,
+            number: n,
+            ..Default::default()
           },
-        )| {
-          (
-            rune,
-            RuneInfo {
-              burned,
-              divisibility,
-              etching,
-              height: id.height,
-              id,
-              index: id.index,
-              mint,
-              mints,
-              number,
-              rune,
-              spacers,
-              supply,
-              symbol,
-              timestamp: crate::timestamp(timestamp),
-            },
-          )
-        },
+        )
+      })
+      .collect::<Vec<(RuneId, RuneEntry)>>();
+
+    let page = |page| {
+      let (entries, more) = Runes {
+        page,
+        ..Default::default()
+      }
+      .paginate(entries.clone());
+
+      (
+        entries.len(),
+        entries.first().map(|(_, entry)| entry.number),
+        more,
       )
-      .collect::<BTreeMap<Rune, RuneInfo>>(),
-  })))
+    };
+
+    assert_eq!(page(None), (250, Some(0), false));
+    assert_eq!(page(Some(0)), (100, Some(0), true));
+    assert_eq!(page(Some(2)), (50, Some(200), false));
+    assert_eq!(page(Some(3)), (0, None, false));
+  }
+
+  #[test]
+  fn query_string() {
+    assert_eq!(Runes::default().query_string(1), "page=1");
+
+    assert_eq!(
+      Runes {
+        mintable: true,
+        order: Some(Order::Desc),
+        page: Some(0),
+        prefix: Some("A•B".into()),
+        sort: Some(Sort::Supply),
+      }
+      .query_string(2),
+      "page=2&mintable=true&order=desc&prefix=A•B&sort=supply"
+    );
+  }
 }
@@ -3,17 +3,25 @@ use {
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
     error::{OptionExt, ServerError, ServerResult},
+    event_stream::{EventStream, EventsQuery},
+    replica::Replica,
   },
   super::*,
   crate::{
+    index::event::Event,
+    mempool::Mempool,
+    runes::Report,
     server_config::ServerConfig,
     templates::{
-      BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml, InputHtml,
-      InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent, PageHtml,
-      PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
-      PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
-      RangeHtml, RareTxt, RuneBalancesHtml, RuneHtml, RunesHtml, SatHtml, TransactionHtml,
+      AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, Concentration,
+      HomeHtml, InputHtml, InscriptionHistoryHtml, InscriptionHtml, InscriptionTransfer,
+      InscriptionsBlockHtml, InscriptionsHtml, MempoolHtml, OutputHtml, PageContent, PageHtml,
+      PendingInscriptionHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
+      PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
+      PreviewVideoHtml, RangeHtml, RareTxt, RuneBalancesHtml, RuneHolder, RuneHoldersHtml,
+      RuneHtml, RunesHtml, SatHtml, TransactionHtml,
     },
+    webhook::Dispatcher,
   },
   axum::{
     body,
@@ -35,7 +43,10 @@ use {
   std::{cmp::Ordering, io::Read, str, sync::Arc},
   tokio_stream::StreamExt,
   tower_http::{
-    compression::CompressionLayer,
+    compression::{
+      predicate::{DefaultPredicate, NotForContentType, Predicate},
+      CompressionLayer,
+    },
     cors::{Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
     validate_request::ValidateRequestHeaderLayer,
@@ -45,7 +56,23 @@ use {
 mod accept_encoding;
 mod accept_json;
 mod error;
+mod event_stream;
 pub(crate) mod query;
+mod replica;
+mod zmq;
+
+const RUNE_AVAILABILITY_BATCH_SIZE: usize = 100;
+
+#[derive(Deserialize)]
+struct RuneActivityQuery {
+  from: Option<u32>,
+  to: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct RuneAvailabilityQuery {
+  names: String,
+}
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -126,6 +153,11 @@ pub struct Server {
   pub(crate) redirect_http_to_https: bool,
   #[arg(long, alias = "nosync", help = "Do not update the index.")]
   pub(crate) no_sync: bool,
+  #[arg(
+    long,
+    help = "Serve an existing index without updating it, reopening it when the index file is replaced. With --snapshot-dir, follow the snapshots written there by another server."
+  )]
+  pub(crate) read_only: bool,
   #[arg(
     long,
     help = "Proxy `/content/INSCRIPTION_ID` requests to `<CONTENT_PROXY>/content/INSCRIPTION_ID` if the inscription is not present on current chain."
@@ -137,34 +169,187 @@ pub struct Server {
     help = "Poll Bitcoin Core every <POLLING_INTERVAL>."
   )]
   pub(crate) polling_interval: humantime::Duration,
+  #[arg(
+    long,
+    help = "Track unconfirmed transactions in Bitcoin Core's mempool, and show the inscriptions and runes they would create or move."
+  )]
+  pub(crate) mempool: bool,
+  #[arg(
+    long,
+    help = "Periodically write index snapshots to <SNAPSHOT_DIR>, keeping only the latest. With --read-only, install new snapshots from <SNAPSHOT_DIR> instead."
+  )]
+  pub(crate) snapshot_dir: Option<PathBuf>,
+  #[arg(
+    long,
+    default_value = "1000",
+    requires = "snapshot_dir",
+    value_parser = clap::value_parser!(u32).range(1..),
+    help = "Write a snapshot every <SNAPSHOT_INTERVAL> blocks."
+  )]
+  pub(crate) snapshot_interval: u32,
 }
 
 impl Server {
-  pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+  pub fn open_index(
+    &self,
+    settings: &Settings,
+    event_sender: Option<tokio::sync::mpsc::Sender<Event>>,
+  ) -> Result<Index> {
+    if self.read_only {
+      Replica::open_index(settings, self.snapshot_dir.as_deref())
+    } else {
+      Index::open_with_event_sender(settings, event_sender)
+    }
+  }
+
+  pub fn run(
+    self,
+    settings: Settings,
+    index: Arc<Index>,
+    event_receiver: tokio::sync::mpsc::Receiver<Event>,
+    handle: Handle,
+  ) -> SubcommandResult {
     Runtime::new()?.block_on(async {
+      let event_stream = EventStream::spawn(event_receiver, index.block_count()?);
+
       let index_clone = index.clone();
       let integration_test = settings.integration_test();
 
+      let block_notifications = settings
+        .bitcoin_zmq_url()
+        .map(zmq::parse_url)
+        .transpose()?
+        .map(zmq::spawn);
+
+      let replica = if self.read_only {
+        Some(Arc::new(Replica::new(
+          index.clone(),
+          settings.clone(),
+          self.snapshot_dir.clone(),
+        )?))
+      } else {
+        None
+      };
+
+      let replica_clone = replica.clone();
+
       let index_thread = thread::spawn(move || loop {
         if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
           break;
         }
 
-        if !self.no_sync {
+        if let Some(replica) = &replica_clone {
+          if let Err(error) = replica.refresh() {
+            log::warn!("Refreshing read-only index: {error}");
+          }
+        } else if !self.no_sync {
           if let Err(error) = index_clone.update() {
             log::warn!("Updating index: {error}");
           }
         }
 
-        thread::sleep(if integration_test {
+        let polling_interval = if integration_test {
           Duration::from_millis(100)
         } else {
           self.polling_interval.into()
-        });
+        };
+
+        // with ZMQ, polling is a fallback in case notifications are missed
+        match &block_notifications {
+          Some(block_notifications) => {
+            if block_notifications.recv_timeout(polling_interval).is_ok() {
+              while block_notifications.try_recv().is_ok() {}
+            }
+          }
+          None => thread::sleep(polling_interval),
+        }
       });
 
       INDEXER.lock().unwrap().replace(index_thread);
 
+      // webhook cursors are stored in the index, so only writers dispatch
+      if !settings.webhooks().is_empty() && !self.read_only {
+        let mut dispatcher = Dispatcher::new(
+          settings.webhooks(),
+          settings.chain(),
+          index.has_address_index(),
+        )?;
+        let index = index.clone();
+        let polling_interval = self.polling_interval;
+
+        thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          if let Err(error) = dispatcher.dispatch(&index) {
+            log::warn!("Dispatching webhooks: {error}");
+          }
+
+          thread::sleep(if integration_test {
+            Duration::from_millis(100)
+          } else {
+            polling_interval.into()
+          });
+        });
+      }
+
+      // snapshots can take a while, so they're written on their own thread
+      // to avoid holding up index updates
+      if let Some(snapshot_dir) = self.snapshot_dir.clone().filter(|_| !self.read_only) {
+        let index = index.clone();
+        let polling_interval = self.polling_interval;
+        let snapshot_interval = self.snapshot_interval;
+        let mut last_snapshot = None;
+
+        thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          if let Err(error) =
+            Self::snapshot(&index, &snapshot_dir, snapshot_interval, &mut last_snapshot)
+          {
+            log::warn!("Writing index snapshot: {error}");
+          }
+
+          thread::sleep(if integration_test {
+            Duration::from_millis(100)
+          } else {
+            polling_interval.into()
+          });
+        });
+      }
+
+      let mempool = self.mempool.then(|| Arc::new(Mempool::default()));
+
+      if let Some(mempool) = mempool.clone() {
+        let index = index.clone();
+        let replica = replica.clone();
+        let polling_interval = self.polling_interval;
+
+        thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+
+          let index = match &replica {
+            Some(replica) => replica.index(),
+            None => index.clone(),
+          };
+
+          if let Err(error) = mempool.update(&index) {
+            log::warn!("Updating mempool: {error}");
+          }
+
+          thread::sleep(if integration_test {
+            Duration::from_millis(100)
+          } else {
+            polling_interval.into()
+          });
+        });
+      }
+
       let settings = Arc::new(settings);
       let acme_domains = self.acme_domains()?;
 
@@ -180,6 +365,7 @@ impl Server {
 
       let router = Router::new()
         .route("/", get(Self::home))
+        .route("/address/:address", get(Self::address))
         .route("/block/:query", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
@@ -197,11 +383,16 @@ impl Server {
         .route("/collections", get(Self::collections))
         .route("/collections/:page", get(Self::collections_paginated))
         .route("/content/:inscription_id", get(Self::content))
+        .route("/events", get(Self::events))
         .route("/faq", get(Self::faq))
         .route("/favicon.ico", get(Self::favicon))
         .route("/feed.xml", get(Self::feed))
         .route("/input/:block/:transaction/:input", get(Self::input))
         .route("/inscription/:inscription_query", get(Self::inscription))
+        .route(
+          "/inscription/:inscription_query/history",
+          get(Self::inscription_history),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions/:page", get(Self::inscriptions_paginated))
         .route(
@@ -213,6 +404,7 @@ impl Server {
           get(Self::inscriptions_in_block_paginated),
         )
         .route("/install.sh", get(Self::install_script))
+        .route("/mempool", get(Self::mempool))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
         .route("/preview/:inscription_id", get(Self::preview))
@@ -228,12 +420,19 @@ impl Server {
           "/r/inscription/:inscription_id",
           get(Self::inscription_recursive),
         )
+        .route(
+          "/r/inscription/:inscription_id/history",
+          get(Self::inscription_history_recursive),
+        )
         .route("/r/children/:inscription_id", get(Self::children_recursive))
         .route(
           "/r/children/:inscription_id/:page",
           get(Self::children_recursive_paginated),
         )
         .route("/r/metadata/:inscription_id", get(Self::metadata))
+        .route("/r/rune/:rune/activity", get(Self::rune_activity))
+        .route("/r/rune-availability", get(Self::rune_availability_batch))
+        .route("/r/rune-availability/:name", get(Self::rune_availability))
         .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
         .route(
           "/r/sat/:sat_number/:page",
@@ -246,6 +445,11 @@ impl Server {
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/:rune", get(Self::rune))
+        .route("/rune/:rune/holders", get(Self::rune_holders))
+        .route(
+          "/rune/:rune/holders/:page",
+          get(Self::rune_holders_paginated),
+        )
         .route("/runes", get(Self::runes))
         .route("/runes/balances", get(Self::runes_balances))
         .route("/sat/:sat", get(Self::sat))
@@ -253,8 +457,24 @@ impl Server {
         .route("/search/*query", get(Self::search_by_path))
         .route("/static/*path", get(Self::static_asset))
         .route("/status", get(Self::status))
-        .route("/tx/:txid", get(Self::transaction))
-        .layer(Extension(index))
+        .route("/tx/:txid", get(Self::transaction));
+
+      // read-only servers may reopen the index, so handlers get whichever index
+      // is current when the request arrives
+      let router = match replica {
+        Some(replica) => router.layer(axum::middleware::from_fn(
+          move |mut request: http::Request<body::Body>,
+                next: axum::middleware::Next<body::Body>| {
+            request.extensions_mut().insert(replica.index());
+            next.run(request)
+          },
+        )),
+        None => router.layer(Extension(index)),
+      };
+
+      let router = router
+        .layer(Extension(event_stream))
+        .layer(Extension(mempool))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -270,7 +490,9 @@ impl Server {
             .allow_methods([http::Method::GET])
             .allow_origin(Any),
         )
-        .layer(CompressionLayer::new())
+        .layer(CompressionLayer::new().compress_when(
+          DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+        ))
         .with_state(server_config);
 
       let router = if let Some((username, password)) = settings.credentials() {
@@ -332,6 +554,55 @@ impl Server {
     })
   }
 
+  fn snapshot(
+    index: &Index,
+    snapshot_dir: &std::path::Path,
+    interval: u32,
+    last_snapshot: &mut Option<u32>,
+  ) -> Result {
+    let Some(height) = index.block_height()?.map(|height| height.n()) else {
+      return Ok(());
+    };
+
+    if last_snapshot.map_or(false, |last| height < last + interval) {
+      return Ok(());
+    }
+
+    let path = snapshot_dir.join(height.to_string());
+
+    if !path.exists() {
+      log::info!("Writing index snapshot to {}", path.display());
+
+      // snapshots are renamed into place, so interrupted ones are never used
+      let partial = snapshot_dir.join(format!("{height}.partial"));
+
+      if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+      }
+
+      index.snapshot(&partial)?;
+
+      fs::rename(&partial, &path)?;
+    }
+
+    *last_snapshot = Some(height);
+
+    for entry in fs::read_dir(snapshot_dir)? {
+      let entry = entry?;
+
+      if entry
+        .file_name()
+        .to_str()
+        .and_then(|name| name.parse::<u32>().ok())
+        .map_or(false, |other| other < height)
+      {
+        fs::remove_dir_all(entry.path())?;
+      }
+    }
+
+    Ok(())
+  }
+
   fn spawn(
     &self,
     settings: &Settings,
@@ -535,6 +806,76 @@ impl Server {
     Redirect::to(&format!("/sat/{sat}"))
   }
 
+  async fn address(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<String>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      if !index.has_address_index() {
+        return Err(ServerError::NotFound(
+          "this server has no address index".to_string(),
+        ));
+      }
+
+      let address = address
+        .parse::<Address<NetworkUnchecked>>()
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let mut outputs = Vec::new();
+      let mut inscriptions = Vec::new();
+      let mut runes_balances = BTreeMap::<SpacedRune, Pile>::new();
+      let mut sat_balance = 0;
+      let mut sat_ranges = index.has_sat_index().then(Vec::new);
+
+      for (outpoint, txout) in index.get_address_info(&address)? {
+        outputs.push(outpoint);
+        sat_balance += txout.value;
+        inscriptions.extend(index.get_inscriptions_on_output(outpoint)?);
+
+        for (spaced_rune, pile) in index.get_rune_balances_for_outpoint(outpoint)? {
+          runes_balances
+            .entry(spaced_rune)
+            .and_modify(|balance| balance.amount += pile.amount)
+            .or_insert(pile);
+        }
+
+        if let Some(sat_ranges) = sat_ranges.as_mut() {
+          sat_ranges.extend(index.list(outpoint)?.unwrap_or_default());
+        }
+      }
+
+      let runes_balances = runes_balances
+        .into_iter()
+        .collect::<Vec<(SpacedRune, Pile)>>();
+
+      Ok(if accept_json {
+        Json(api::AddressInfo {
+          outputs,
+          inscriptions,
+          sat_balance,
+          runes_balances,
+          sat_ranges,
+        })
+        .into_response()
+      } else {
+        AddressHtml {
+          address,
+          inscriptions,
+          outputs,
+          runes_balances,
+          sat_balance,
+          sat_ranges,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn output(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -661,23 +1002,208 @@ impl Server {
     })
   }
 
+  async fn rune_activity(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    Query(query): Query<RuneActivityQuery>,
+  ) -> ServerResult<Json<api::RuneLedger>> {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let rune = match rune_query {
+        query::Rune::SpacedRune(spaced_rune) => spaced_rune.rune,
+        query::Rune::RuneId(rune_id) => index
+          .get_rune_by_id(rune_id)?
+          .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      };
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let from = query.from.unwrap_or(id.height);
+      let to = query.to.unwrap_or(u32::MAX);
+
+      if from > to {
+        return Err(ServerError::BadRequest(format!(
+          "`from` height {from} is greater than `to` height {to}"
+        )));
+      }
+
+      Ok(Json(api::RuneLedger {
+        activity: index.get_rune_activity(id, from, to)?,
+        id,
+        rune: entry.spaced_rune(),
+      }))
+    })
+  }
+
+  async fn rune_availability(
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(name)): Path<DeserializeFromStr<SpacedRune>>,
+  ) -> ServerResult<Json<api::RuneAvailability>> {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      Ok(Json(index.rune_availability(&[name])?.remove(0)))
+    })
+  }
+
+  async fn rune_availability_batch(
+    Extension(index): Extension<Arc<Index>>,
+    Query(query): Query<RuneAvailabilityQuery>,
+  ) -> ServerResult<Json<Vec<api::RuneAvailability>>> {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let names = query
+        .names
+        .split(',')
+        .map(|name| {
+          name
+            .parse::<SpacedRune>()
+            .map_err(|err| ServerError::BadRequest(format!("invalid rune `{name}`: {err}")))
+        })
+        .collect::<ServerResult<Vec<SpacedRune>>>()?;
+
+      if names.len() > RUNE_AVAILABILITY_BATCH_SIZE {
+        return Err(ServerError::BadRequest(format!(
+          "at most {RUNE_AVAILABILITY_BATCH_SIZE} names may be checked at once"
+        )));
+      }
+
+      Ok(Json(index.rune_availability(&names)?))
+    })
+  }
+
+  async fn rune_holders(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(rune_query): Path<DeserializeFromStr<query::Rune>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    Self::rune_holders_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((rune_query, 0)),
+      AcceptJson(accept_json),
+    )
+    .await
+  }
+
+  async fn rune_holders_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(rune_query), page_index)): Path<(
+      DeserializeFromStr<query::Rune>,
+      u32,
+    )>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      if !index.has_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no rune index".to_string(),
+        ));
+      }
+
+      let rune = match rune_query {
+        query::Rune::SpacedRune(spaced_rune) => spaced_rune.rune,
+        query::Rune::RuneId(rune_id) => index
+          .get_rune_by_id(rune_id)?
+          .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      };
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let rune_holders = index.get_rune_holders(id)?;
+      let balances = &rune_holders.balances;
+
+      let page_size = RuneHoldersHtml::PAGE_SIZE;
+
+      let start = usize::try_from(page_index)
+        .unwrap()
+        .saturating_mul(page_size);
+
+      let holders = balances
+        .iter()
+        .skip(start)
+        .take(page_size)
+        .map(|(outpoint, amount)| {
+          Ok(RuneHolder {
+            address: index
+              .get_output_address(*outpoint)?
+              .map(|address| address.to_string()),
+            amount: *amount,
+            outpoint: *outpoint,
+          })
+        })
+        .collect::<Result<Vec<RuneHolder>>>()?;
+
+      let top = balances
+        .iter()
+        .take(100)
+        .map(|(_outpoint, amount)| *amount)
+        .collect::<Vec<u128>>();
+
+      let holders = RuneHoldersHtml {
+        concentration: Concentration::new(&top),
+        entry,
+        held: rune_holders.held,
+        holders,
+        id,
+        more: balances.len() > start.saturating_add(page_size),
+        outputs: balances.len(),
+        page_index,
+      };
+
+      Ok(if accept_json {
+        Json(holders).into_response()
+      } else {
+        holders.page(server_config).into_response()
+      })
+    })
+  }
+
   async fn runes(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Query(mut query): Query<super::runes::Runes>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
     task::block_in_place(|| {
+      let page_index = *query.page.get_or_insert(0);
+
+      let (entries, more) = index.runes_matching(&query)?;
+
+      let runes = RunesHtml {
+        entries,
+        more,
+        next: more.then(|| format!("/runes?{}", query.query_string(page_index + 1))),
+        page_index,
+        prev: page_index
+          .checked_sub(1)
+          .map(|prev| format!("/runes?{}", query.query_string(prev))),
+      };
+
       Ok(if accept_json {
-        Json(api::Runes {
-          entries: index.runes()?,
-        })
-        .into_response()
+        Json(runes).into_response()
       } else {
-        RunesHtml {
-          entries: index.runes()?,
-        }
-        .page(server_config)
-        .into_response()
+        runes.page(server_config).into_response()
       })
     })
   }
@@ -699,6 +1225,14 @@ impl Server {
     })
   }
 
+  async fn events(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(event_stream): Extension<Arc<EventStream>>,
+    Query(query): Query<EventsQuery>,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| event_stream::events(&index, &event_stream, query))
+  }
+
   async fn home(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -770,6 +1304,8 @@ impl Server {
         }
       };
 
+      let runes = index.get_rune_activity_in_block(height)?;
+
       Ok(if accept_json {
         let inscriptions = index.get_inscriptions_in_block(height)?;
         Json(api::Block::new(
@@ -777,6 +1313,10 @@ impl Server {
           Height(height),
           Self::index_height(&index)?,
           inscriptions,
+          runes
+            .into_iter()
+            .map(|(entry, activity)| (entry.spaced_rune(), activity))
+            .collect(),
         ))
         .into_response()
       } else {
@@ -788,6 +1328,7 @@ impl Server {
           Self::index_height(&index)?,
           total_num,
           featured_inscriptions,
+          runes,
         )
         .page(server_config)
         .into_response()
@@ -798,6 +1339,7 @@ impl Server {
   async fn transaction(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     Path(txid): Path<Txid>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
@@ -808,11 +1350,21 @@ impl Server {
 
       let inscription_count = index.inscription_count(txid)?;
 
+      let pending = mempool.and_then(|mempool| mempool.get(txid));
+
+      let Report {
+        flaws: runestone_flaws,
+        runestone,
+      } = Runestone::diagnose(&transaction);
+
       Ok(if accept_json {
         Json(api::Transaction {
           chain: server_config.chain,
           etching: index.get_etching(txid)?,
           inscription_count,
+          pending,
+          runestone,
+          runestone_flaws,
           transaction,
           txid,
         })
@@ -822,6 +1374,9 @@ impl Server {
           chain: server_config.chain,
           etching: index.get_etching(txid)?,
           inscription_count,
+          pending,
+          runestone,
+          runestone_flaws,
           transaction,
           txid,
         }
@@ -906,14 +1461,33 @@ impl Server {
     })
   }
 
-  async fn status(
+  async fn mempool(
     Extension(server_config): Extension<Arc<ServerConfig>>,
-    Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
-    task::block_in_place(|| {
-      Ok(if accept_json {
-        Json(index.status()?).into_response()
+    let mempool = mempool.ok_or_not_found(|| "mempool tracking")?;
+
+    let mempool = MempoolHtml {
+      count: mempool.count(),
+      transactions: mempool.active(),
+    };
+
+    Ok(if accept_json {
+      Json(mempool).into_response()
+    } else {
+      mempool.page(server_config).into_response()
+    })
+  }
+
+  async fn status(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      Ok(if accept_json {
+        Json(index.status()?).into_response()
       } else {
         index.status()?.page(server_config).into_response()
       })
@@ -1454,12 +2028,36 @@ impl Server {
   async fn inscription(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
+    Extension(mempool): Extension<Option<Arc<Mempool>>>,
     Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
     AcceptJson(accept_json): AcceptJson,
   ) -> ServerResult<Response> {
     task::block_in_place(|| {
-      let info = Index::inscription_info(&index, query)?
-        .ok_or_not_found(|| format!("inscription {query}"))?;
+      let Some(info) = Index::inscription_info(&index, query)? else {
+        if let (query::Inscription::Id(id), Some(mempool)) = (query, mempool.as_ref()) {
+          if let Some(pending) = Self::pending_inscription(&index, mempool, id)? {
+            return Ok(if accept_json {
+              Json(api::PendingInscription {
+                content_length: pending.inscription.content_length(),
+                content_type: pending.inscription.content_type().map(|s| s.to_string()),
+                delegate: pending.inscription.delegate(),
+                id,
+                metaprotocol: pending.inscription.metaprotocol().map(|s| s.to_string()),
+                parent: pending.inscription.parent(),
+              })
+              .into_response()
+            } else {
+              pending.page(server_config).into_response()
+            });
+          }
+        }
+
+        return Err(ServerError::NotFound(format!(
+          "inscription {query} not found"
+        )));
+      };
+
+      let pending_transfer = mempool.and_then(|mempool| mempool.transfer(info.entry.id));
 
       Ok(if accept_json {
         Json(api::Inscription {
@@ -1487,6 +2085,7 @@ impl Server {
           next: info.next,
           number: info.entry.inscription_number,
           parent: info.parent,
+          pending_transfer,
           previous: info.previous,
           rune: info.rune,
           sat: info.entry.sat,
@@ -1502,12 +2101,14 @@ impl Server {
           children: info.children,
           fee: info.entry.fee,
           height: info.entry.height,
+          history: index.has_transfer_index(),
           inscription: info.inscription,
           id: info.entry.id,
           number: info.entry.inscription_number,
           next: info.next,
           output: info.output,
           parent: info.parent,
+          pending_transfer,
           previous: info.previous,
           rune: info.rune,
           sat: info.entry.sat,
@@ -1520,6 +2121,116 @@ impl Server {
     })
   }
 
+  async fn inscription_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(query)): Path<DeserializeFromStr<query::Inscription>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult<Response> {
+    task::block_in_place(|| {
+      let history = Self::transfer_history(&server_config, &index, query)?;
+
+      Ok(if accept_json {
+        Json(history).into_response()
+      } else {
+        history.page(server_config).into_response()
+      })
+    })
+  }
+
+  async fn inscription_history_recursive(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Json<api::InscriptionHistory>> {
+    task::block_in_place(|| {
+      Ok(Json(Self::transfer_history(
+        &server_config,
+        &index,
+        query::Inscription::Id(inscription_id),
+      )?))
+    })
+  }
+
+  fn transfer_history(
+    server_config: &ServerConfig,
+    index: &Index,
+    query: query::Inscription,
+  ) -> ServerResult<InscriptionHistoryHtml> {
+    if !index.has_transfer_index() {
+      return Err(ServerError::NotFound(
+        "this server has no transfer index".to_string(),
+      ));
+    }
+
+    let id = match query {
+      query::Inscription::Id(id) => id,
+      query::Inscription::Number(number) => index
+        .get_inscription_id_by_inscription_number(number)?
+        .ok_or_not_found(|| format!("inscription {number}"))?,
+    };
+
+    let entry = index
+      .get_inscription_entry(id)?
+      .ok_or_not_found(|| format!("inscription {id}"))?;
+
+    let transfers = index
+      .get_inscription_transfers(id)?
+      .ok_or_not_found(|| format!("inscription {id}"))?
+      .into_iter()
+      .map(|transfer| InscriptionTransfer {
+        address: transfer
+          .output
+          .as_ref()
+          .and_then(|output| {
+            server_config
+              .chain
+              .address_from_script(&output.script_pubkey)
+              .ok()
+          })
+          .map(|address| address.to_string()),
+        height: transfer.height,
+        new_satpoint: transfer.new_satpoint,
+        old_satpoint: transfer.old_satpoint,
+        txid: transfer.txid,
+        value: transfer.output.map(|output| output.value),
+      })
+      .collect();
+
+    Ok(InscriptionHistoryHtml {
+      id,
+      number: entry.inscription_number,
+      transfers,
+    })
+  }
+
+  fn pending_inscription(
+    index: &Index,
+    mempool: &Mempool,
+    id: InscriptionId,
+  ) -> ServerResult<Option<PendingInscriptionHtml>> {
+    if !mempool
+      .get(id.txid)
+      .map_or(false, |pending| pending.inscriptions.contains(&id))
+    {
+      return Ok(None);
+    }
+
+    let Some(transaction) = index.get_transaction(id.txid)? else {
+      return Ok(None);
+    };
+
+    Ok(
+      ParsedEnvelope::from_transaction(&transaction)
+        .into_iter()
+        .nth(usize::try_from(id.index).unwrap())
+        .map(|envelope| PendingInscriptionHtml {
+          id,
+          inscription: envelope.payload,
+        }),
+    )
+  }
+
   async fn collections(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1786,6 +2497,7 @@ mod tests {
   use {
     super::*,
     crate::runes::{Edict, Etching, Rune, Runestone},
+    bitcoin::WPubkeyHash,
     reqwest::Url,
     serde::de::DeserializeOwned,
     std::net::TcpListener,
@@ -1899,8 +2611,10 @@ mod tests {
       args.push("--http-port".into());
       args.push(port.to_string());
 
-      args.push("--polling-interval".into());
-      args.push("100ms".into());
+      if !self.server_args.contains_key("--polling-interval") {
+        args.push("--polling-interval".into());
+        args.push("100ms".into());
+      }
 
       for (arg, value) in self.server_args {
         args.push(arg);
@@ -1921,13 +2635,18 @@ mod tests {
         .or_defaults()
         .unwrap();
 
-      let index = Arc::new(Index::open(&settings).unwrap());
+      let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+      let index = Arc::new(server.open_index(&settings, Some(event_sender)).unwrap());
       let ord_server_handle = Handle::new();
 
       {
         let index = index.clone();
         let ord_server_handle = ord_server_handle.clone();
-        thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+        thread::spawn(|| {
+          server
+            .run(settings, index, event_receiver, ord_server_handle)
+            .unwrap()
+        });
       }
 
       while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -1965,6 +2684,10 @@ mod tests {
       self.server_flag("--https")
     }
 
+    fn index_addresses(self) -> Self {
+      self.ord_flag("--index-addresses")
+    }
+
     fn index_runes(self) -> Self {
       self.ord_flag("--index-runes")
     }
@@ -2079,6 +2802,21 @@ mod tests {
       assert_eq!(response.headers().get(header::LOCATION).unwrap(), location);
     }
 
+    #[track_caller]
+    fn wait_for_mempool(&self, count: usize) -> api::Mempool {
+      for _ in 0..200 {
+        let mempool = self.get_json::<api::Mempool>("/mempool");
+
+        if mempool.count == count {
+          return mempool;
+        }
+
+        thread::sleep(Duration::from_millis(50));
+      }
+
+      panic!("mempool never contained {count} transactions");
+    }
+
     fn mine_blocks(&self, n: u64) -> Vec<Block> {
       let blocks = self.bitcoin_rpc_server.mine_blocks(n);
       self.index.update().unwrap();
@@ -2468,37 +3206,50 @@ mod tests {
   }
 
   #[test]
-  fn runes_are_displayed_on_runes_page() {
+  fn rune_holders_are_ranked_by_balance() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
+      .index_addresses()
       .build();
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/runes",
-      StatusCode::OK,
-      ".*<title>Runes</title>.*<h1>Runes</h1>\n<ul>\n</ul>.*",
-    );
+    server.assert_response_regex("/rune/2:1/holders", StatusCode::NOT_FOUND, ".*");
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(1, 0, 0, Witness::new())],
       op_return: Some(
         Runestone {
-          edicts: vec![Edict {
-            id: 0,
-            amount: u128::MAX,
-            output: 0,
-          }],
+          edicts: vec![
+            Edict {
+              id: 0,
+              amount: 100,
+              output: 0,
+            },
+            Edict {
+              id: 0,
+              amount: 300,
+              output: 1,
+            },
+            Edict {
+              id: 0,
+              amount: 600,
+              output: 2,
+            },
+          ],
           etching: Some(Etching {
             rune: Some(Rune(RUNE)),
+            symbol: Some('%'),
             ..Default::default()
           }),
           ..Default::default()
         }
         .encipher(),
       ),
+      fee: 2,
+      outputs: 3,
+      recipient: Some(address()),
       ..Default::default()
     });
 
@@ -2509,38 +3260,81 @@ mod tests {
       index: 1,
     };
 
-    assert_eq!(
-      server.index.runes().unwrap(),
-      [(
-        id,
-        RuneEntry {
-          etching: txid,
-          rune: Rune(RUNE),
-          supply: u128::MAX,
-          timestamp: 2,
-          ..Default::default()
-        }
-      )]
-    );
+    let holders = server.get_json::<api::RuneHolders>("/rune/AAAAAAAAAAAAA/holders");
 
+    assert_eq!(holders.id, id);
+    assert_eq!(holders.held, 1000);
+    assert_eq!(holders.outputs, 3);
+    assert!(!holders.more);
     assert_eq!(
-      server.index.get_rune_balances().unwrap(),
-      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+      holders.concentration,
+      Concentration {
+        top_1: 600,
+        top_10: 1000,
+        top_100: 1000,
+      }
     );
+    assert_eq!(
+      holders
+        .holders
+        .iter()
+        .map(|holder| (holder.outpoint, holder.amount))
+        .collect::<Vec<(OutPoint, u128)>>(),
+      [
+        (OutPoint { txid, vout: 2 }, 600),
+        (OutPoint { txid, vout: 1 }, 300),
+        (OutPoint { txid, vout: 0 }, 100),
+      ]
+    );
+    assert!(holders
+      .holders
+      .iter()
+      .all(|holder| holder.address.is_some()));
 
     server.assert_response_regex(
-      "/runes",
+      "/rune/2:1/holders",
       StatusCode::OK,
-      ".*<title>Runes</title>.*
-<h1>Runes</h1>
-<ul>
-  <li><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></li>
-</ul>.*",
+      format!(
+        ".*<title>Rune AAAAAAAAAAAAA Holders</title>.*
+  <dt>holding outputs</dt>
+  <dd>3</dd>
+  <dt>held</dt>
+  <dd>1000\u{00A0}%</dd>
+  <dt>top output</dt>
+  <dd>600\u{00A0}% \\(60.00%\\)</dd>
+.*
+    <td>1</td>
+    <td class=monospace><a href=/output/{txid}:2>{txid}:2</a></td>
+.*
+prev
+next
+.*"
+      ),
     );
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 2, Witness::new())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let holders = server.get_json::<api::RuneHolders>("/rune/2:1/holders");
+
+    assert_eq!(holders.outputs, 3);
+    assert_eq!(holders.held, 1000);
+    assert_eq!(holders.holders[0].amount, 600);
+    assert_ne!(holders.holders[0].outpoint.txid, txid);
+
+    let holders = server.get_json::<api::RuneHolders>("/rune/2:1/holders/1");
+
+    assert!(holders.holders.is_empty());
+    assert!(!holders.more);
+    assert_eq!(holders.page_index, 1);
   }
 
   #[test]
-  fn runes_are_displayed_on_rune_page() {
+  fn runes_are_displayed_on_runes_page() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
@@ -2548,12 +3342,14 @@ mod tests {
 
     server.mine_blocks(1);
 
-    let rune = Rune(RUNE);
-
-    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+    server.assert_response_regex(
+      "/runes",
+      StatusCode::OK,
+      ".*<title>Runes</title>.*<h1>Runes</h1>\n<ul>\n</ul>.*",
+    );
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      inputs: &[(1, 0, 0, Witness::new())],
       op_return: Some(
         Runestone {
           edicts: vec![Edict {
@@ -2562,8 +3358,7 @@ mod tests {
             output: 0,
           }],
           etching: Some(Etching {
-            rune: Some(rune),
-            symbol: Some('%'),
+            rune: Some(Rune(RUNE)),
             ..Default::default()
           }),
           ..Default::default()
@@ -2586,9 +3381,8 @@ mod tests {
         id,
         RuneEntry {
           etching: txid,
-          rune,
+          rune: Rune(RUNE),
           supply: u128::MAX,
-          symbol: Some('%'),
           timestamp: 2,
           ..Default::default()
         }
@@ -2601,57 +3395,18 @@ mod tests {
     );
 
     server.assert_response_regex(
-      format!("/rune/{rune}"),
-      StatusCode::OK,
-      format!(
-        ".*<title>Rune AAAAAAAAAAAAA</title>.*
-<h1>AAAAAAAAAAAAA</h1>
-<iframe .* src=/preview/{txid}i0></iframe>
-<dl>
-  <dt>number</dt>
-  <dd>0</dd>
-  <dt>timestamp</dt>
-  <dd><time>1970-01-01 00:00:02 UTC</time></dd>
-  <dt>id</dt>
-  <dd>2:1</dd>
-  <dt>etching block height</dt>
-  <dd><a href=/block/2>2</a></dd>
-  <dt>etching transaction index</dt>
-  <dd>1</dd>
-  <dt>mint</dt>
-  <dd>no</dd>
-  <dt>supply</dt>
-  <dd>340282366920938463463374607431768211455\u{00A0}%</dd>
-  <dt>burned</dt>
-  <dd>0\u{00A0}%</dd>
-  <dt>divisibility</dt>
-  <dd>0</dd>
-  <dt>symbol</dt>
-  <dd>%</dd>
-  <dt>etching</dt>
-  <dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
-  <dt>parent</dt>
-  <dd><a class=monospace href=/inscription/{txid}i0>{txid}i0</a></dd>
-</dl>
-.*"
-      ),
-    );
-
-    server.assert_response_regex(
-      format!("/inscription/{txid}i0"),
+      "/runes",
       StatusCode::OK,
-      ".*
-<dl>
-  .*
-  <dt>rune</dt>
-  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
-</dl>
-.*",
+      ".*<title>Runes</title>.*
+<h1>Runes</h1>
+<ul>
+  <li><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></li>
+</ul>.*",
     );
   }
 
   #[test]
-  fn runes_are_spaced() {
+  fn rune_activity_is_shown_on_block_pages_and_by_rune() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
@@ -2659,23 +3414,24 @@ mod tests {
 
     server.mine_blocks(1);
 
-    let rune = Rune(RUNE);
-
-    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+    server.assert_response_regex(
+      "/r/rune/AAAAAAAAAAAAA/activity",
+      StatusCode::NOT_FOUND,
+      ".*",
+    );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
       op_return: Some(
         Runestone {
           edicts: vec![Edict {
             id: 0,
-            amount: u128::MAX,
+            amount: 1000,
             output: 0,
           }],
           etching: Some(Etching {
-            rune: Some(rune),
+            rune: Some(Rune(RUNE)),
             symbol: Some('%'),
-            spacers: 1,
             ..Default::default()
           }),
           ..Default::default()
@@ -2692,66 +3448,745 @@ mod tests {
       index: 1,
     };
 
-    assert_eq!(
-      server.index.runes().unwrap(),
-      [(
-        id,
-        RuneEntry {
-          etching: txid,
-          rune,
-          supply: u128::MAX,
-          symbol: Some('%'),
-          timestamp: 2,
-          spacers: 1,
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: u128::from(id),
+            amount: 400,
+            output: 1,
+          }],
           ..Default::default()
         }
-      )]
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let spaced_rune = SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 0,
+    };
+
+    let etched = RuneActivity {
+      etched: true,
+      transferred: 1000,
+      transfers: 1,
+      ..Default::default()
+    };
+
+    let transferred = RuneActivity {
+      burned: 400,
+      transferred: 600,
+      transfers: 1,
+      ..Default::default()
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::Block>("/block/2").runes,
+      [(spaced_rune, etched)],
     );
 
+    pretty_assert_eq!(
+      server.get_json::<api::Block>("/block/3").runes,
+      [(spaced_rune, transferred)],
+    );
+
+    assert!(server.get_json::<api::Block>("/block/1").runes.is_empty());
+
+    server.assert_response_regex(
+      "/block/3",
+      StatusCode::OK,
+      ".*<h2>1 Rune</h2>.*
+  <tr>
+    <td><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></td>
+    <td>false</td>
+    <td>0</td>
+    <td>0\u{A0}%</td>
+    <td>1</td>
+    <td>600\u{A0}%</td>
+    <td>400\u{A0}%</td>
+  </tr>.*",
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneLedger>("/r/rune/AAAAAAAAAAAAA/activity"),
+      api::RuneLedger {
+        activity: [(2, etched), (3, transferred)].into_iter().collect(),
+        id,
+        rune: spaced_rune,
+      }
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::RuneLedger>("/r/rune/2:1/activity?from=3")
+        .activity,
+      [(3, transferred)].into_iter().collect(),
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::RuneLedger>("/r/rune/2:1/activity?from=0&to=2")
+        .activity,
+      [(2, etched)].into_iter().collect(),
+    );
+
+    server.assert_response(
+      "/r/rune/2:1/activity?from=3&to=2",
+      StatusCode::BAD_REQUEST,
+      "`from` height 3 is greater than `to` height 2",
+    );
+  }
+
+  #[test]
+  fn rune_availability() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneAvailability>("/r/rune-availability/AAAAAAAAAAAAA"),
+      api::RuneAvailability {
+        available: false,
+        etched: Some(RuneId {
+          height: 2,
+          index: 1
+        }),
+        reserved: false,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0
+        },
+        unlock_height: Some(0),
+        unlock_time: Some(timestamp(1296688602)),
+        unlocked: true,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<Vec<api::RuneAvailability>>(
+        "/r/rune-availability?names=AAAAAAAAAAAAB,AAAAAAAAAAAA,AAAAAAAAAAAAAAAAAAAAAAAAAAA"
+      ),
+      [
+        api::RuneAvailability {
+          available: true,
+          etched: None,
+          reserved: false,
+          rune: SpacedRune {
+            rune: Rune(RUNE + 1),
+            spacers: 0
+          },
+          unlock_height: Some(0),
+          unlock_time: Some(timestamp(1296688602)),
+          unlocked: true,
+        },
+        api::RuneAvailability {
+          available: false,
+          etched: None,
+          reserved: false,
+          rune: "AAAAAAAAAAAA".parse().unwrap(),
+          unlock_height: Some(17499),
+          unlock_time: Some(timestamp(2 + 600 * (17499 - 2))),
+          unlocked: false,
+        },
+        api::RuneAvailability {
+          available: false,
+          etched: None,
+          reserved: true,
+          rune: "AAAAAAAAAAAAAAAAAAAAAAAAAAA".parse().unwrap(),
+          unlock_height: None,
+          unlock_time: None,
+          unlocked: false,
+        },
+      ]
+    );
+
+    server.assert_response(
+      "/r/rune-availability?names=AAAAAAAAAAAAB,0",
+      StatusCode::BAD_REQUEST,
+      "invalid rune `0`: invalid character",
+    );
+  }
+
+  #[test]
+  fn runes_page_can_be_sorted_filtered_and_paginated() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(2);
+
+    for (i, mint) in [None, Some(Mint::default())].into_iter().enumerate() {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(i + 1, 0, 0, Witness::new())],
+        op_return: Some(
+          Runestone {
+            edicts: vec![Edict {
+              id: 0,
+              amount: u128::try_from(i + 1).unwrap() * 1000,
+              output: 0,
+            }],
+            etching: Some(Etching {
+              mint,
+              rune: Some(Rune(RUNE + u128::try_from(i).unwrap())),
+              ..Default::default()
+            }),
+            ..Default::default()
+          }
+          .encipher(),
+        ),
+        ..Default::default()
+      });
+
+      server.mine_blocks(1);
+    }
+
+    let runes = |query: &str| {
+      server
+        .get_json::<api::Runes>(format!("/runes{query}"))
+        .entries
+        .into_iter()
+        .map(|(_, entry)| entry.rune)
+        .collect::<Vec<Rune>>()
+    };
+
+    assert_eq!(runes(""), [Rune(RUNE), Rune(RUNE + 1)]);
+    assert_eq!(runes("?order=desc"), [Rune(RUNE + 1), Rune(RUNE)]);
+    assert_eq!(
+      runes("?sort=timestamp&order=desc"),
+      [Rune(RUNE + 1), Rune(RUNE)]
+    );
+    assert_eq!(runes("?mintable=true"), [Rune(RUNE + 1)]);
+    assert_eq!(runes("?prefix=AAAAAAAAAAAAB"), [Rune(RUNE + 1)]);
+    assert_eq!(runes("?prefix=B"), []);
+    assert_eq!(runes("?page=1"), []);
+
+    let runes = server.get_json::<api::Runes>("/runes?page=1");
+
+    assert_eq!(runes.page_index, 1);
+    assert!(!runes.more);
+
+    server.assert_response_regex(
+      "/runes?page=1&sort=supply",
+      StatusCode::OK,
+      ".*<a class=prev href=/runes\\?page=0&amp;sort=supply>prev</a>\nnext.*",
+    );
+
+    server.assert_response_regex("/runes?sort=foo", StatusCode::BAD_REQUEST, ".*");
+  }
+
+  #[test]
+  fn runes_are_displayed_on_rune_page() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(rune),
+            symbol: Some('%'),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    assert_eq!(
+      server.index.runes().unwrap(),
+      [(
+        id,
+        RuneEntry {
+          etching: txid,
+          rune,
+          supply: u128::MAX,
+          symbol: Some('%'),
+          timestamp: 2,
+          ..Default::default()
+        }
+      )]
+    );
+
+    assert_eq!(
+      server.index.get_rune_balances().unwrap(),
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    );
+
+    server.assert_response_regex(
+      format!("/rune/{rune}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Rune AAAAAAAAAAAAA</title>.*
+<h1>AAAAAAAAAAAAA</h1>
+<iframe .* src=/preview/{txid}i0></iframe>
+<dl>
+  <dt>number</dt>
+  <dd>0</dd>
+  <dt>timestamp</dt>
+  <dd><time>1970-01-01 00:00:02 UTC</time></dd>
+  <dt>id</dt>
+  <dd>2:1</dd>
+  <dt>etching block height</dt>
+  <dd><a href=/block/2>2</a></dd>
+  <dt>etching transaction index</dt>
+  <dd>1</dd>
+  <dt>mint</dt>
+  <dd>no</dd>
+  <dt>supply</dt>
+  <dd>340282366920938463463374607431768211455\u{00A0}%</dd>
+  <dt>burned</dt>
+  <dd>0\u{00A0}%</dd>
+  <dt>holders</dt>
+  <dd><a href=/rune/2:1/holders>holders</a></dd>
+  <dt>divisibility</dt>
+  <dd>0</dd>
+  <dt>symbol</dt>
+  <dd>%</dd>
+  <dt>etching</dt>
+  <dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
+  <dt>parent</dt>
+  <dd><a class=monospace href=/inscription/{txid}i0>{txid}i0</a></dd>
+</dl>
+.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{txid}i0"),
+      StatusCode::OK,
+      ".*
+<dl>
+  .*
+  <dt>rune</dt>
+  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
+</dl>
+.*",
+    );
+  }
+
+  #[test]
+  fn runes_are_spaced() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(rune),
+            symbol: Some('%'),
+            spacers: 1,
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    assert_eq!(
+      server.index.runes().unwrap(),
+      [(
+        id,
+        RuneEntry {
+          etching: txid,
+          rune,
+          supply: u128::MAX,
+          symbol: Some('%'),
+          timestamp: 2,
+          spacers: 1,
+          ..Default::default()
+        }
+      )]
+    );
+
+    assert_eq!(
+      server.index.get_rune_balances().unwrap(),
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    );
+
+    server.assert_response_regex(
+      format!("/rune/{rune}"),
+      StatusCode::OK,
+      r".*<title>Rune A•AAAAAAAAAAAA</title>.*<h1>A•AAAAAAAAAAAA</h1>.*",
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{txid}i0"),
+      StatusCode::OK,
+      ".*<dt>rune</dt>.*<dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>.*",
+    );
+
+    server.assert_response_regex(
+      "/runes",
+      StatusCode::OK,
+      ".*<li><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></li>.*",
+    );
+
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      ".*
+  <dt>etching</dt>
+  <dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>
+.*",
+    );
+
+    server.assert_response_regex(
+      format!("/output/{txid}:0"),
+      StatusCode::OK,
+      ".*<tr>
+        <td><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></td>
+        <td>340282366920938463463374607431768211455\u{00A0}%</td>
+      </tr>.*",
+    );
+  }
+
+  #[test]
+  fn transactions_link_to_etching() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/runes",
+      StatusCode::OK,
+      ".*<title>Runes</title>.*<h1>Runes</h1>\n<ul>\n</ul>.*",
+    );
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    assert_eq!(
+      server.index.runes().unwrap(),
+      [(
+        id,
+        RuneEntry {
+          etching: txid,
+          rune: Rune(RUNE),
+          supply: u128::MAX,
+          timestamp: 2,
+          ..Default::default()
+        }
+      )]
+    );
+
+    assert_eq!(
+      server.index.get_rune_balances().unwrap(),
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    );
+
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      ".*
+  <dt>etching</dt>
+  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
+.*",
+    );
+  }
+
+  #[test]
+  fn address_page_requires_address_index() {
+    TestServer::new().assert_response(
+      "/address/bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
+    );
+  }
+
+  #[test]
+  fn address_page_rejects_address_for_other_network() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .build()
+      .assert_response_regex(
+        "/address/bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq",
+        StatusCode::BAD_REQUEST,
+        ".*",
+      );
+  }
+
+  #[test]
+  fn address_page_lists_unspent_outputs() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .index_runes()
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let address = Chain::Regtest
+      .address_from_script(&ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros()))
+      .unwrap();
+
+    let rune = Rune(RUNE);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            divisibility: 1,
+            rune: Some(rune),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      recipient: Some(address.clone()),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let output = OutPoint { txid, vout: 0 };
+
+    server.assert_response_regex(
+      format!("/address/{address}"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Address {address}</title>.*<h1>Address <span class=monospace>{address}</span></h1>
+<dl>
+  <dt>sat balance</dt><dd>5000000000</dd>
+  <dt>runes</dt>
+  <dd>
+    <table>
+      <tr>
+        <th>rune</th>
+        <th>balance</th>
+      </tr>
+      <tr>
+        <td><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></td>
+        <td>34028236692093846346337460743176821145.5</td>
+      </tr>
+    </table>
+  </dd>
+</dl>
+<h2>1 Output</h2>
+<ul class=monospace>
+  <li><a href=/output/{output}>{output}</a></li>
+</ul>
+<h2>1 Sat Range</h2>
+.*"
+      ),
+    );
+
+    assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}")),
+      api::AddressInfo {
+        outputs: vec![output],
+        inscriptions: Vec::new(),
+        sat_balance: 5000000000,
+        runes_balances: vec![(
+          SpacedRune {
+            rune: Rune(RUNE),
+            spacers: 0
+          },
+          Pile {
+            amount: 340282366920938463463374607431768211455,
+            divisibility: 1,
+            symbol: None,
+          }
+        )],
+        sat_ranges: Some(vec![(5000000000, 10000000000)]),
+      }
+    );
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}")),
+      api::AddressInfo {
+        outputs: Vec::new(),
+        inscriptions: Vec::new(),
+        sat_balance: 0,
+        runes_balances: Vec::new(),
+        sat_ranges: Some(Vec::new()),
+      }
+    );
+  }
+
+  fn read_events(
+    response: reqwest::blocking::Response,
+    n: usize,
+  ) -> Vec<(String, serde_json::Value)> {
+    let mut events = Vec::new();
+    let mut kind = None;
+
+    for line in io::BufRead::lines(io::BufReader::new(response)) {
+      let line = line.unwrap();
+
+      if let Some(event) = line.strip_prefix("event:") {
+        kind = Some(event.to_string());
+      } else if let Some(data) = line.strip_prefix("data:") {
+        events.push((kind.take().unwrap(), serde_json::from_str(data).unwrap()));
+
+        if events.len() == n {
+          break;
+        }
+      }
+    }
+
+    events
+  }
+
+  #[test]
+  fn events_stream_inscriptions() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let response = server.get("/events?from_height=1");
+
+    assert_eq!(response.status(), StatusCode::OK);
     assert_eq!(
-      server.index.get_rune_balances().unwrap(),
-      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
     );
 
-    server.assert_response_regex(
-      format!("/rune/{rune}"),
-      StatusCode::OK,
-      r".*<title>Rune A•AAAAAAAAAAAA</title>.*<h1>A•AAAAAAAAAAAA</h1>.*",
-    );
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
 
-    server.assert_response_regex(
-      format!("/inscription/{txid}i0"),
-      StatusCode::OK,
-      ".*<dt>rune</dt>.*<dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>.*",
-    );
+    server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/runes",
-      StatusCode::OK,
-      ".*<li><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></li>.*",
-    );
+    let inscription_id = InscriptionId { txid, index: 0 };
 
-    server.assert_response_regex(
-      format!("/tx/{txid}"),
-      StatusCode::OK,
-      ".*
-  <dt>etching</dt>
-  <dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>
-.*",
-    );
+    let events = read_events(response, 3);
 
-    server.assert_response_regex(
-      format!("/output/{txid}:0"),
-      StatusCode::OK,
-      ".*<tr>
-        <td><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></td>
-        <td>340282366920938463463374607431768211455\u{00A0}%</td>
-      </tr>.*",
-    );
+    assert_eq!(events[0].0, "block_committed");
+    assert_eq!(events[0].1["block_height"], 1);
+
+    assert_eq!(events[1].0, "inscription_created");
+    assert_eq!(events[1].1["type"], "inscription_created");
+    assert_eq!(events[1].1["block_height"], 2);
+    assert_eq!(events[1].1["inscription_id"], inscription_id.to_string());
+
+    assert_eq!(events[2].0, "block_committed");
+    assert_eq!(events[2].1["block_height"], 2);
   }
 
   #[test]
-  fn transactions_link_to_etching() {
+  fn events_stream_can_be_filtered() {
     let server = TestServer::builder()
       .chain(Chain::Regtest)
       .index_runes()
@@ -2759,14 +4194,8 @@ mod tests {
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/runes",
-      StatusCode::OK,
-      ".*<title>Runes</title>.*<h1>Runes</h1>\n<ul>\n</ul>.*",
-    );
-
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, Witness::new())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
       op_return: Some(
         Runestone {
           edicts: vec![Edict {
@@ -2787,37 +4216,44 @@ mod tests {
 
     server.mine_blocks(1);
 
-    let id = RuneId {
-      height: 2,
-      index: 1,
-    };
+    let events = read_events(
+      server.get("/events?from_height=0&kind=rune_etched,rune_transferred&rune=AAAAAAAAAAAAA"),
+      2,
+    );
 
-    assert_eq!(
-      server.index.runes().unwrap(),
-      [(
-        id,
-        RuneEntry {
-          etching: txid,
-          rune: Rune(RUNE),
-          supply: u128::MAX,
-          timestamp: 2,
-          ..Default::default()
-        }
-      )]
+    assert_eq!(events[0].0, "rune_etched");
+    assert_eq!(events[0].1["rune_id"], "2:1");
+    assert_eq!(events[0].1["txid"], txid.to_string());
+    assert_eq!(events[1].0, "rune_transferred");
+    assert_eq!(events[1].1["outpoint"], format!("{txid}:0"));
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    let events = read_events(
+      server.get(format!(
+        "/events?from_height=2&inscription_id={inscription_id}"
+      )),
+      2,
     );
 
-    assert_eq!(
-      server.index.get_rune_balances().unwrap(),
-      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    assert_eq!(events[0].0, "inscription_created");
+    assert_eq!(events[1].0, "block_committed");
+  }
+
+  #[test]
+  fn events_stream_rejects_invalid_filters() {
+    let server = TestServer::new();
+
+    server.assert_response(
+      "/events?kind=foo",
+      StatusCode::BAD_REQUEST,
+      "unknown event kind `foo`",
     );
 
-    server.assert_response_regex(
-      format!("/tx/{txid}"),
-      StatusCode::OK,
-      ".*
-  <dt>etching</dt>
-  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
-.*",
+    server.assert_response(
+      "/events?rune=AAAAAAAAAAAAA",
+      StatusCode::NOT_FOUND,
+      "this server has no rune index",
     );
   }
 
@@ -4012,129 +5448,534 @@ mod tests {
     let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("application/pdf", "hello").to_witness(),
-      )],
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("application/pdf", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<canvas data-inscription={inscription_id}></canvas>.*"),
+    );
+  }
+
+  #[test]
+  fn markdown_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/markdown", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<html lang=en data-inscription={inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn image_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-inline'",
+      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+    );
+  }
+
+  #[test]
+  fn iframe_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/html;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
+      "hello",
+    );
+  }
+
+  #[test]
+  fn unknown_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+      StatusCode::OK,
+      "default-src 'self'",
+      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    );
+  }
+
+  #[test]
+  fn video_preview() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn inscription_history_requires_index_transfers() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.assert_response(
+      format!("/inscription/{id}/history"),
+      StatusCode::NOT_FOUND,
+      "this server has no transfer index",
+    );
+
+    server.assert_response(
+      format!("/r/inscription/{id}/history"),
+      StatusCode::NOT_FOUND,
+      "this server has no transfer index",
+    );
+  }
+
+  #[test]
+  fn inscription_history() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .ord_flag("--index-transfers")
+      .build();
+
+    server.mine_blocks(1);
+
+    let inscribe = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
+    let id = InscriptionId {
+      txid: inscribe,
+      index: 0,
+    };
+
     server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
+      format!("/inscription/{id}"),
       StatusCode::OK,
-      format!(r".*<canvas data-inscription={inscription_id}></canvas>.*"),
+      format!(r".*<dt>history</dt>\s*<dd><a href=/inscription/{id}/history>transfers</a></dd>.*"),
     );
-  }
 
-  #[test]
-  fn markdown_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    server.mine_blocks(1);
+    server.assert_response_regex(
+      "/inscription/0/history",
+      StatusCode::OK,
+      ".*<title>Inscription 0 History</title>.*<p>No transfers recorded.</p>.*",
+    );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/markdown", "hello").to_witness())],
+    let transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
+    let expected = api::InscriptionHistory {
+      id,
+      number: 0,
+      transfers: vec![InscriptionTransfer {
+        address: None,
+        height: 3,
+        new_satpoint: SatPoint {
+          outpoint: OutPoint {
+            txid: transfer,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        old_satpoint: SatPoint {
+          outpoint: OutPoint {
+            txid: inscribe,
+            vout: 0,
+          },
+          offset: 0,
+        },
+        txid: transfer,
+        value: Some(50 * COIN_VALUE),
+      }],
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/inscription/{id}/history")),
+      expected,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/r/inscription/{id}/history")),
+      expected,
+    );
+
     server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
+      format!("/inscription/{id}/history"),
       StatusCode::OK,
-      format!(r".*<html lang=en data-inscription={inscription_id}>.*"),
+      format!(
+        ".*<dt>transfers</dt>\\s*<dd>1</dd>.*\
+        <td><a href=/block/3>3</a></td>\\s*\
+        <td><a class=monospace href=/tx/{transfer}>{transfer}</a></td>\\s*\
+        <td class=monospace>{inscribe}:0:0</td>\\s*\
+        <td class=monospace>{transfer}:0:0</td>\\s*\
+        <td>5000000000</td>.*"
+      ),
+    );
+
+    server.assert_response(
+      format!("/r/inscription/{}/history", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
     );
   }
 
   #[test]
-  fn image_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+  fn mempool_page_requires_mempool_flag() {
+    TestServer::new().assert_response(
+      "/mempool",
+      StatusCode::NOT_FOUND,
+      "mempool tracking not found",
+    );
+  }
+
+  #[test]
+  fn pending_inscriptions_and_transfers_are_shown() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--mempool")
+      .build();
+
     server.mine_blocks(1);
 
+    pretty_assert_eq!(
+      server.wait_for_mempool(0),
+      api::Mempool {
+        count: 0,
+        transactions: Vec::new(),
+      }
+    );
+
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
-    server.mine_blocks(1);
+    let id = InscriptionId { txid, index: 0 };
 
-    server.assert_response_csp(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      "default-src 'self' 'unsafe-inline'",
-      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+    let pending = api::PendingTransaction {
+      etching: None,
+      inscriptions: vec![id],
+      runes: Vec::new(),
+      transfers: Vec::new(),
+      txid,
+    };
+
+    pretty_assert_eq!(
+      server.wait_for_mempool(1),
+      api::Mempool {
+        count: 1,
+        transactions: vec![pending.clone()],
+      }
     );
-  }
 
-  #[test]
-  fn iframe_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
-    server.mine_blocks(1);
+    assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{txid}"))
+        .pending,
+      Some(pending),
+    );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("text/html;charset=utf-8", "hello").to_witness(),
-      )],
-      ..Default::default()
-    });
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      format!(
+        ".*<p>This transaction is unconfirmed.*</p>.*<dt>pending inscriptions</dt>\\s*<dd>\\s*<ul>\\s*<li><a class=monospace href=/inscription/{id}>{id}</a></li>.*"
+      ),
+    );
 
-    server.mine_blocks(1);
+    pretty_assert_eq!(
+      server.get_json::<api::PendingInscription>(format!("/inscription/{id}")),
+      api::PendingInscription {
+        content_length: Some(5),
+        content_type: Some("text/foo".into()),
+        delegate: None,
+        id,
+        metaprotocol: None,
+        parent: None,
+      },
+    );
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
       StatusCode::OK,
-      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
-      "hello",
+      format!(
+        ".*<title>Unconfirmed Inscription {id}</title>.*<dt>content type</dt>\\s*<dd>text/foo</dd>.*"
+      ),
     );
-  }
 
-  #[test]
-  fn unknown_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
     server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    server.wait_for_mempool(0);
+
+    assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{txid}"))
+        .pending,
+      None,
+    );
+
+    assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{id}"))
+        .pending_transfer,
+      None,
+    );
+
+    let transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
       ..Default::default()
     });
 
-    server.mine_blocks(1);
+    let location = SatPoint {
+      outpoint: OutPoint {
+        txid: transfer,
+        vout: 0,
+      },
+      offset: 0,
+    };
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
+    server.wait_for_mempool(1);
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Inscription>(format!("/inscription/{id}"))
+        .pending_transfer,
+      Some(api::PendingTransfer {
+        id,
+        location: Some(location),
+        txid: transfer,
+      }),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
       StatusCode::OK,
-      "default-src 'self'",
-      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+      format!(
+        ".*<title>Inscription 0</title>.*<dt>pending transfer</dt>\\s*<dd>\\s*unconfirmed in <a class=monospace href=/tx/{transfer}>{transfer}</a>\\s*to <span class=monospace>{location}</span>\\s*</dd>.*"
+      ),
     );
   }
 
   #[test]
-  fn video_preview() {
-    let server = TestServer::builder().chain(Chain::Regtest).build();
+  fn pending_rune_transfers_and_etchings_are_shown() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .server_flag("--mempool")
+      .build();
+
     server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: 1000,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            symbol: Some('%'),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
+    let transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: RuneId {
+              height: 2,
+              index: 1,
+            }
+            .into(),
+            amount: 400,
+            output: 1,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      outputs: 2,
+      ..Default::default()
+    });
+
+    let etching = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE + 1)),
+            spacers: 1,
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.wait_for_mempool(2);
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{transfer}"))
+        .pending,
+      Some(api::PendingTransaction {
+        etching: None,
+        inscriptions: Vec::new(),
+        runes: vec![
+          api::PendingRuneTransfer {
+            location: Some(OutPoint {
+              txid: transfer,
+              vout: 0,
+            }),
+            pile: Pile {
+              amount: 600,
+              divisibility: 0,
+              symbol: Some('%'),
+            },
+            rune: SpacedRune {
+              rune: Rune(RUNE),
+              spacers: 0,
+            },
+          },
+          api::PendingRuneTransfer {
+            location: Some(OutPoint {
+              txid: transfer,
+              vout: 1,
+            }),
+            pile: Pile {
+              amount: 400,
+              divisibility: 0,
+              symbol: Some('%'),
+            },
+            rune: SpacedRune {
+              rune: Rune(RUNE),
+              spacers: 0,
+            },
+          },
+        ],
+        transfers: Vec::new(),
+        txid: transfer,
+      }),
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::Transaction>(format!("/tx/{etching}"))
+        .pending,
+      Some(api::PendingTransaction {
+        etching: Some(SpacedRune {
+          rune: Rune(RUNE + 1),
+          spacers: 1,
+        }),
+        inscriptions: Vec::new(),
+        runes: Vec::new(),
+        transfers: Vec::new(),
+        txid: etching,
+      }),
+    );
+
     server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
+      "/mempool",
       StatusCode::OK,
-      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+      ".*<dt>unconfirmed transactions</dt>\\s*<dd>2</dd>.*<div>etching A•AAAAAAAAAAAB</div>.*",
+    );
+
+    server.assert_response_regex(
+      "/mempool",
+      StatusCode::OK,
+      ".*<div><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a> 600\u{A0}% to output 0</div>\\s*<div><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a> 400\u{A0}% to output 1</div>.*",
     );
   }
 
@@ -5683,4 +7524,64 @@ next
       PreviewUnknownHtml.to_string(),
     );
   }
+
+  #[test]
+  fn zmq_block_notifications_trigger_index_updates() {
+    let publisher = zmq::tests::Publisher::new();
+
+    let server = TestServer::builder()
+      .ord_option("--bitcoin-zmq-url", &publisher.url)
+      .server_option("--polling-interval", "1h")
+      .build();
+
+    let mut connection = publisher.accept();
+
+    server.bitcoin_rpc_server.mine_blocks(1);
+
+    connection.publish("hashblock", &[0; 32]);
+
+    for _ in 0..100 {
+      if server.index.block_count().unwrap() == 2 {
+        return;
+      }
+
+      thread::sleep(Duration::from_millis(100));
+    }
+
+    panic!("index was not updated after block notification");
+  }
+
+  #[test]
+  fn snapshots_are_written_every_interval_and_pruned() {
+    let server = TestServer::new();
+
+    let snapshot_dir = server.tempdir.path().join("snapshots");
+    fs::create_dir(&snapshot_dir).unwrap();
+
+    let snapshots = || {
+      let mut snapshots = fs::read_dir(&snapshot_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<String>>();
+      snapshots.sort();
+      snapshots
+    };
+
+    let mut last_snapshot = None;
+
+    Server::snapshot(&server.index, &snapshot_dir, 2, &mut last_snapshot).unwrap();
+    assert_eq!(snapshots(), ["0"]);
+
+    server.mine_blocks(1);
+
+    Server::snapshot(&server.index, &snapshot_dir, 2, &mut last_snapshot).unwrap();
+    assert_eq!(snapshots(), ["0"]);
+
+    server.mine_blocks(1);
+
+    Server::snapshot(&server.index, &snapshot_dir, 2, &mut last_snapshot).unwrap();
+    assert_eq!(snapshots(), ["2"]);
+    assert_eq!(last_snapshot, Some(2));
+    assert!(snapshot_dir.join("2/manifest.json").exists());
+  }
 }
@@ -0,0 +1,296 @@
+use {
+  super::*,
+  crate::index::event::Event,
+  axum::response::sse::{Event as SseEvent, KeepAlive, Sse},
+  std::collections::VecDeque,
+  tokio::sync::{broadcast, mpsc},
+};
+
+const HISTORY_LIMIT: usize = 10_000;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct EventsQuery {
+  from_height: Option<u32>,
+  inscription_id: Option<InscriptionId>,
+  kind: Option<String>,
+  parent: Option<InscriptionId>,
+  rune: Option<String>,
+}
+
+struct History {
+  events: VecDeque<Event>,
+  first_height: u32,
+}
+
+pub(super) struct EventStream {
+  history: Mutex<History>,
+  sender: broadcast::Sender<Event>,
+}
+
+impl EventStream {
+  /// Events for blocks below `first_height` were indexed before the stream
+  /// was started and can't be replayed.
+  pub(super) fn spawn(mut receiver: mpsc::Receiver<Event>, first_height: u32) -> Arc<Self> {
+    let (sender, _) = broadcast::channel(HISTORY_LIMIT);
+
+    let event_stream = Arc::new(Self {
+      history: Mutex::new(History {
+        events: VecDeque::new(),
+        first_height,
+      }),
+      sender,
+    });
+
+    {
+      let event_stream = event_stream.clone();
+      tokio::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+          event_stream.publish(event);
+        }
+      });
+    }
+
+    event_stream
+  }
+
+  fn publish(&self, event: Event) {
+    let mut history = self.history.lock().unwrap();
+
+    if history.events.len() == HISTORY_LIMIT {
+      if let Some(oldest) = history.events.pop_front() {
+        history.first_height = history.first_height.max(oldest.block_height() + 1);
+      }
+    }
+
+    history.events.push_back(event.clone());
+
+    // there are no subscribers if sending fails, which is fine
+    self.sender.send(event).ok();
+  }
+
+  fn subscribe(
+    &self,
+    from_height: Option<u32>,
+  ) -> ServerResult<(Vec<Event>, broadcast::Receiver<Event>)> {
+    let history = self.history.lock().unwrap();
+
+    let receiver = self.sender.subscribe();
+
+    let Some(from_height) = from_height else {
+      return Ok((Vec::new(), receiver));
+    };
+
+    if from_height < history.first_height {
+      return Err(ServerError::BadRequest(format!(
+        "events before height {} are not available",
+        history.first_height
+      )));
+    }
+
+    Ok((
+      history
+        .events
+        .iter()
+        .filter(|event| event.block_height() >= from_height)
+        .cloned()
+        .collect(),
+      receiver,
+    ))
+  }
+}
+
+struct EventFilter {
+  inscription_id: Option<InscriptionId>,
+  kinds: Option<HashSet<String>>,
+  parent: Option<InscriptionId>,
+  rune_id: Option<RuneId>,
+}
+
+impl EventFilter {
+  fn new(query: &EventsQuery, index: &Index) -> ServerResult<Self> {
+    let kinds = query
+      .kind
+      .as_ref()
+      .map(|kinds| {
+        kinds
+          .split(',')
+          .map(|kind| {
+            if Event::KINDS.contains(&kind) {
+              Ok(kind.to_string())
+            } else {
+              Err(ServerError::BadRequest(format!(
+                "unknown event kind `{kind}`"
+              )))
+            }
+          })
+          .collect::<ServerResult<HashSet<String>>>()
+      })
+      .transpose()?;
+
+    let rune_id = match &query.rune {
+      Some(rune) => {
+        if !index.has_rune_index() {
+          return Err(ServerError::NotFound(
+            "this server has no rune index".to_string(),
+          ));
+        }
+
+        Some(if let Ok(rune_id) = rune.parse::<RuneId>() {
+          rune_id
+        } else {
+          let spaced_rune = rune
+            .parse::<SpacedRune>()
+            .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+          index
+            .rune(spaced_rune.rune)?
+            .map(|(rune_id, _entry, _parent)| rune_id)
+            .ok_or_not_found(|| format!("rune {spaced_rune}"))?
+        })
+      }
+      None => None,
+    };
+
+    Ok(Self {
+      inscription_id: query.inscription_id,
+      kinds,
+      parent: query.parent,
+      rune_id,
+    })
+  }
+
+  fn matches(&self, event: &Event) -> bool {
+    if let Some(kinds) = &self.kinds {
+      if !kinds.contains(event.kind()) {
+        return false;
+      }
+    }
+
+    match event {
+      Event::BlockCommitted { .. } | Event::ReorgRolledBack { .. } => true,
+      Event::InscriptionCreated {
+        inscription_id,
+        parent_inscription_id,
+        ..
+      } => {
+        self.rune_id.is_none()
+          && self.inscription_id.map_or(true, |id| id == *inscription_id)
+          && self
+            .parent
+            .map_or(true, |parent| Some(parent) == *parent_inscription_id)
+      }
+      Event::InscriptionTransferred { inscription_id, .. } => {
+        self.rune_id.is_none()
+          && self.parent.is_none()
+          && self.inscription_id.map_or(true, |id| id == *inscription_id)
+      }
+      Event::RuneBurned { rune_id, .. }
+      | Event::RuneEtched { rune_id, .. }
+      | Event::RuneMinted { rune_id, .. }
+      | Event::RuneTransferred { rune_id, .. } => {
+        self.inscription_id.is_none()
+          && self.parent.is_none()
+          && self.rune_id.map_or(true, |id| id == *rune_id)
+      }
+    }
+  }
+}
+
+pub(super) fn events(
+  index: &Index,
+  event_stream: &EventStream,
+  query: EventsQuery,
+) -> ServerResult<Response> {
+  let filter = EventFilter::new(&query, index)?;
+
+  let (history, receiver) = event_stream.subscribe(query.from_height)?;
+
+  // a lagging subscriber ends the stream and may resume with `from_height`
+  let live = futures::stream::unfold(receiver, |mut receiver| async move {
+    receiver.recv().await.ok().map(|event| (event, receiver))
+  });
+
+  let stream = tokio_stream::iter(history)
+    .chain(live)
+    .filter(move |event| filter.matches(event))
+    .map(|event| {
+      SseEvent::default()
+        .event(event.kind())
+        .id(event.block_height().to_string())
+        .json_data(&event)
+    });
+
+  Ok(
+    Sse::new(stream)
+      .keep_alive(KeepAlive::default())
+      .into_response(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  async fn publish(heights: impl IntoIterator<Item = u32>, first_height: u32) -> Arc<EventStream> {
+    let (sender, receiver) = mpsc::channel(HISTORY_LIMIT + 1);
+
+    let event_stream = EventStream::spawn(receiver, first_height);
+
+    let mut last = None;
+    for block_height in heights {
+      sender
+        .send(Event::BlockCommitted { block_height })
+        .await
+        .unwrap();
+      last = Some(block_height);
+    }
+
+    while event_stream
+      .history
+      .lock()
+      .unwrap()
+      .events
+      .back()
+      .map(Event::block_height)
+      != last
+    {
+      tokio::task::yield_now().await;
+    }
+
+    event_stream
+  }
+
+  fn heights(event_stream: &EventStream, from_height: u32) -> ServerResult<Vec<u32>> {
+    event_stream
+      .subscribe(Some(from_height))
+      .map(|(events, _)| events.iter().map(|event| event.block_height()).collect())
+  }
+
+  #[tokio::test]
+  async fn replay_starts_at_first_height() {
+    let event_stream = publish([5, 6], 5).await;
+
+    assert_eq!(heights(&event_stream, 5).unwrap(), [5, 6]);
+    assert_eq!(heights(&event_stream, 6).unwrap(), [6]);
+
+    assert!(matches!(
+      heights(&event_stream, 4),
+      Err(ServerError::BadRequest(message))
+        if message == "events before height 5 are not available"
+    ));
+  }
+
+  #[tokio::test]
+  async fn replay_rejects_truncated_heights() {
+    let event_stream = publish(0..=u32::try_from(HISTORY_LIMIT).unwrap(), 0).await;
+
+    assert!(matches!(
+      heights(&event_stream, 0),
+      Err(ServerError::BadRequest(message))
+        if message == "events before height 1 are not available"
+    ));
+
+    assert_eq!(heights(&event_stream, 1).unwrap().len(), HISTORY_LIMIT);
+  }
+}
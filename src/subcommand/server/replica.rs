@@ -0,0 +1,186 @@
+use {super::*, std::sync::RwLock};
+
+type Fingerprint = (SystemTime, u64);
+
+fn fingerprint(path: &std::path::Path) -> Result<Fingerprint> {
+  let metadata = fs::metadata(path)?;
+  Ok((metadata.modified()?, metadata.len()))
+}
+
+fn latest_snapshot(snapshot_dir: &std::path::Path) -> Result<Option<(u32, PathBuf)>> {
+  let mut latest = None;
+
+  for entry in fs::read_dir(snapshot_dir)? {
+    let entry = entry?;
+
+    let Some(height) = entry
+      .file_name()
+      .to_str()
+      .and_then(|name| name.parse::<u32>().ok())
+    else {
+      continue;
+    };
+
+    if !entry.path().join("manifest.json").exists() {
+      continue;
+    }
+
+    if latest.as_ref().map_or(true, |(latest, _)| height > *latest) {
+      latest = Some((height, entry.path()));
+    }
+  }
+
+  Ok(latest)
+}
+
+/// A read-only index, reopened when the index file is replaced.
+pub(crate) struct Replica {
+  fingerprint: Mutex<Fingerprint>,
+  index: RwLock<Arc<Index>>,
+  settings: Settings,
+  snapshot_dir: Option<PathBuf>,
+}
+
+impl Replica {
+  pub(crate) fn open_index(
+    settings: &Settings,
+    snapshot_dir: Option<&std::path::Path>,
+  ) -> Result<Index> {
+    if let Some(snapshot_dir) = snapshot_dir {
+      if !settings.index().exists() {
+        if let Some((_, snapshot)) = latest_snapshot(snapshot_dir)? {
+          Index::restore(settings, &snapshot, false)?;
+        }
+      }
+    }
+
+    Index::open_read_only(settings)
+  }
+
+  pub(crate) fn new(
+    index: Arc<Index>,
+    settings: Settings,
+    snapshot_dir: Option<PathBuf>,
+  ) -> Result<Self> {
+    Ok(Self {
+      fingerprint: Mutex::new(fingerprint(settings.index())?),
+      index: RwLock::new(index),
+      settings,
+      snapshot_dir,
+    })
+  }
+
+  pub(crate) fn index(&self) -> Arc<Index> {
+    self.index.read().unwrap().clone()
+  }
+
+  pub(crate) fn refresh(&self) -> Result<bool> {
+    if let Some(snapshot_dir) = &self.snapshot_dir {
+      if let Some((height, snapshot)) = latest_snapshot(snapshot_dir)? {
+        if self.index().block_count()? < height + 1 {
+          log::info!("Installing index snapshot {}", snapshot.display());
+          Index::restore(&self.settings, &snapshot, true)?;
+        }
+      }
+    }
+
+    let mut fingerprint = self.fingerprint.lock().unwrap();
+
+    if self::fingerprint(self.settings.index())? == *fingerprint {
+      return Ok(false);
+    }
+
+    log::info!("Reopening index {}", self.settings.index().display());
+
+    let index = Arc::new(Index::open_read_only(&self.settings)?);
+
+    // opening the index writes its header, so the fingerprint is taken after
+    *fingerprint = self::fingerprint(self.settings.index())?;
+
+    *self.index.write().unwrap() = index;
+
+    Ok(true)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::index::testing::Context};
+
+  #[test]
+  fn replica_follows_snapshots() {
+    let context = Context::builder().chain(Chain::Regtest).build();
+
+    context.mine_blocks(1);
+
+    let snapshot_dir = context.tempdir.path().join("snapshots");
+
+    context.index.snapshot(&snapshot_dir.join("1")).unwrap();
+
+    let settings = Settings::from_options(
+      Options::try_parse_from([
+        "ord".to_string(),
+        "--regtest".to_string(),
+        format!("--bitcoin-rpc-url={}", context.rpc_server.url()),
+        format!(
+          "--cookie-file={}",
+          context.tempdir.path().join("cookie").display()
+        ),
+        format!(
+          "--index={}",
+          context.tempdir.path().join("replica.redb").display()
+        ),
+      ])
+      .unwrap(),
+    )
+    .or_defaults()
+    .unwrap();
+
+    let index = Replica::open_index(&settings, Some(&snapshot_dir)).unwrap();
+
+    assert_eq!(index.block_count().unwrap(), 2);
+    assert_eq!(index.status().unwrap().replica_lag, Some(0));
+
+    let replica = Replica::new(Arc::new(index), settings, Some(snapshot_dir.clone())).unwrap();
+
+    assert!(!replica.refresh().unwrap());
+
+    context.mine_blocks(2);
+
+    assert_eq!(replica.index().status().unwrap().replica_lag, Some(2));
+
+    context.index.snapshot(&snapshot_dir.join("3")).unwrap();
+    fs::create_dir(snapshot_dir.join("4")).unwrap();
+
+    assert!(replica.refresh().unwrap());
+    assert_eq!(replica.index().block_count().unwrap(), 4);
+    assert_eq!(replica.index().status().unwrap().replica_lag, Some(0));
+
+    assert!(!replica.refresh().unwrap());
+
+    assert_eq!(
+      replica.index().update().unwrap_err().to_string(),
+      "index is read-only"
+    );
+  }
+
+  #[test]
+  fn latest_snapshot_ignores_incomplete_snapshots() {
+    let tempdir = TempDir::new().unwrap();
+
+    assert_eq!(latest_snapshot(tempdir.path()).unwrap(), None);
+
+    for name in ["2", "10", "11", "11.partial", "foo"] {
+      fs::create_dir(tempdir.path().join(name)).unwrap();
+    }
+
+    for name in ["2", "10", "11.partial", "foo"] {
+      fs::write(tempdir.path().join(name).join("manifest.json"), "").unwrap();
+    }
+
+    assert_eq!(
+      latest_snapshot(tempdir.path()).unwrap(),
+      Some((10, tempdir.path().join("10")))
+    );
+  }
+}
@@ -0,0 +1,414 @@
+//! Minimal ZMTP 3.0 subscriber for Bitcoin Core's block notifications.
+
+use {
+  super::*,
+  std::{
+    io::Write,
+    net::TcpStream,
+    sync::mpsc::{self, Sender},
+  },
+};
+
+const COMMAND: u8 = 0x04;
+const LONG: u8 = 0x02;
+const MORE: u8 = 0x01;
+
+// the largest frame is a `rawblock` body, which can't exceed the maximum block
+// weight
+const MAX_FRAME_SIZE: u64 = 4_000_000;
+
+const TOPICS: &[&str] = &["hashblock", "rawblock"];
+
+fn mechanism() -> [u8; 20] {
+  let mut mechanism = [0; 20];
+  mechanism[..4].copy_from_slice(b"NULL");
+  mechanism
+}
+
+fn greeting() -> [u8; 64] {
+  let mut greeting = [0; 64];
+  greeting[0] = 0xff;
+  greeting[9] = 0x7f;
+  greeting[10] = 3;
+  greeting[12..32].copy_from_slice(&mechanism());
+  greeting
+}
+
+fn write_frame(stream: &mut impl Write, flags: u8, body: &[u8]) -> io::Result<()> {
+  if body.len() > usize::from(u8::MAX) {
+    stream.write_all(&[flags | LONG])?;
+    stream.write_all(&u64::try_from(body.len()).unwrap().to_be_bytes())?;
+  } else {
+    stream.write_all(&[flags, u8::try_from(body.len()).unwrap()])?;
+  }
+
+  stream.write_all(body)
+}
+
+fn read_frame(stream: &mut impl Read) -> io::Result<(u8, Vec<u8>)> {
+  let mut flags = [0];
+  stream.read_exact(&mut flags)?;
+  let flags = flags[0];
+
+  let size = if flags & LONG != 0 {
+    let mut size = [0; 8];
+    stream.read_exact(&mut size)?;
+    let size = u64::from_be_bytes(size);
+
+    if size > MAX_FRAME_SIZE {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("ZMTP frame of {size} bytes exceeds maximum of {MAX_FRAME_SIZE} bytes"),
+      ));
+    }
+
+    usize::try_from(size).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?
+  } else {
+    let mut size = [0];
+    stream.read_exact(&mut size)?;
+    size[0].into()
+  };
+
+  let mut body = vec![0; size];
+  stream.read_exact(&mut body)?;
+
+  Ok((flags, body))
+}
+
+fn ready(socket_type: &str) -> Vec<u8> {
+  let mut command = vec![5];
+  command.extend_from_slice(b"READY");
+  command.push(11);
+  command.extend_from_slice(b"Socket-Type");
+  command.extend_from_slice(&u32::try_from(socket_type.len()).unwrap().to_be_bytes());
+  command.extend_from_slice(socket_type.as_bytes());
+  command
+}
+
+fn handshake(stream: &mut TcpStream, socket_type: &str) -> Result {
+  stream.write_all(&greeting())?;
+
+  let mut greeting = [0; 64];
+  stream.read_exact(&mut greeting)?;
+
+  ensure!(
+    greeting[0] == 0xff && greeting[9] == 0x7f,
+    "invalid ZMTP greeting"
+  );
+
+  ensure!(
+    greeting[10] >= 3,
+    "unsupported ZMTP version {}.{}",
+    greeting[10],
+    greeting[11]
+  );
+
+  ensure!(
+    greeting[12..32] == mechanism(),
+    "unsupported ZMTP security mechanism"
+  );
+
+  write_frame(stream, COMMAND, &ready(socket_type))?;
+
+  let (flags, command) = read_frame(stream)?;
+
+  ensure!(
+    flags & COMMAND != 0 && command.starts_with(b"\x05READY"),
+    "expected ZMTP READY command"
+  );
+
+  Ok(())
+}
+
+pub(super) fn parse_url(url: &str) -> Result<String> {
+  let address = url
+    .strip_prefix("tcp://")
+    .with_context(|| format!("invalid ZMQ URL `{url}`: expected `tcp://<HOST>:<PORT>`"))?;
+
+  ensure!(
+    address
+      .rsplit_once(':')
+      .map(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+      .unwrap_or_default(),
+    "invalid ZMQ URL `{url}`: expected `tcp://<HOST>:<PORT>`"
+  );
+
+  Ok(address.into())
+}
+
+pub(super) struct Subscriber {
+  stream: TcpStream,
+}
+
+impl Subscriber {
+  pub(super) fn connect(address: &str, topics: &[&str]) -> Result<Self> {
+    let mut stream = TcpStream::connect(address)?;
+
+    handshake(&mut stream, "SUB")?;
+
+    // ZMTP 3.0 subscriptions are messages starting with 0x01
+    for topic in topics {
+      let mut subscription = vec![1];
+      subscription.extend_from_slice(topic.as_bytes());
+      write_frame(&mut stream, 0, &subscription)?;
+    }
+
+    Ok(Self { stream })
+  }
+
+  pub(super) fn recv(&mut self) -> Result<Vec<Vec<u8>>> {
+    let mut parts = Vec::new();
+
+    loop {
+      let (flags, body) = read_frame(&mut self.stream)?;
+
+      if flags & COMMAND != 0 {
+        continue;
+      }
+
+      parts.push(body);
+
+      if flags & MORE == 0 {
+        return Ok(parts);
+      }
+    }
+  }
+}
+
+/// Sends a message for each new block, and after each reconnect.
+pub(super) fn spawn(address: String) -> mpsc::Receiver<()> {
+  let (sender, receiver) = mpsc::channel();
+
+  thread::spawn(move || {
+    let mut connected = false;
+    let mut errors = 0;
+
+    while !SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+      let err = match Subscriber::connect(&address, TOPICS) {
+        Ok(mut subscriber) => {
+          log::info!("Subscribed to block notifications from ZMQ publisher at {address}");
+
+          errors = 0;
+
+          if connected && sender.send(()).is_err() {
+            break;
+          }
+
+          connected = true;
+
+          match notify(&mut subscriber, &sender) {
+            Ok(()) => break,
+            Err(err) => err,
+          }
+        }
+        Err(err) => err,
+      };
+
+      let seconds: u64 = 1 << errors;
+      errors = (errors + 1).min(5);
+      log::warn!("ZMQ subscription to {address} failed, reconnecting in {seconds}s: {err}");
+      thread::sleep(Duration::from_secs(seconds));
+    }
+  });
+
+  receiver
+}
+
+fn notify(subscriber: &mut Subscriber, sender: &Sender<()>) -> Result {
+  loop {
+    let message = subscriber.recv()?;
+
+    let is_block = message
+      .first()
+      .map(|topic| TOPICS.iter().any(|t| t.as_bytes() == topic))
+      .unwrap_or_default();
+
+    if is_block && sender.send(()).is_err() {
+      return Ok(());
+    }
+  }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+  use {super::*, std::net::TcpListener};
+
+  pub(crate) struct Publisher {
+    listener: TcpListener,
+    pub(crate) url: String,
+  }
+
+  pub(crate) struct Connection {
+    sequence: u32,
+    stream: TcpStream,
+    pub(crate) topics: Vec<String>,
+  }
+
+  impl Publisher {
+    pub(crate) fn new() -> Self {
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let url = format!("tcp://{}", listener.local_addr().unwrap());
+      Self { listener, url }
+    }
+
+    pub(crate) fn accept(&self) -> Connection {
+      // other tests may connect to a reused port, so skip connections that
+      // don't speak ZMTP
+      let mut stream = loop {
+        let (mut stream, _) = self.listener.accept().unwrap();
+
+        if handshake(&mut stream, "PUB").is_ok() {
+          break stream;
+        }
+      };
+
+      let mut topics = Vec::new();
+      for _ in TOPICS {
+        let (flags, body) = read_frame(&mut stream).unwrap();
+        assert_eq!(flags, 0);
+        assert_eq!(body[0], 1);
+        topics.push(String::from_utf8(body[1..].to_vec()).unwrap());
+      }
+
+      Connection {
+        sequence: 0,
+        stream,
+        topics,
+      }
+    }
+  }
+
+  impl Connection {
+    pub(crate) fn publish(&mut self, topic: &str, body: &[u8]) {
+      write_frame(&mut self.stream, MORE, topic.as_bytes()).unwrap();
+      write_frame(&mut self.stream, MORE, body).unwrap();
+      write_frame(&mut self.stream, 0, &self.sequence.to_le_bytes()).unwrap();
+      self.sequence += 1;
+    }
+  }
+
+  fn address(url: &str) -> String {
+    parse_url(url).unwrap()
+  }
+
+  #[test]
+  fn urls_must_be_tcp() {
+    assert_eq!(
+      parse_url("tcp://127.0.0.1:28332").unwrap(),
+      "127.0.0.1:28332"
+    );
+
+    assert_eq!(
+      parse_url("ipc:///tmp/bitcoind").unwrap_err().to_string(),
+      "invalid ZMQ URL `ipc:///tmp/bitcoind`: expected `tcp://<HOST>:<PORT>`",
+    );
+
+    assert_eq!(
+      parse_url("tcp://127.0.0.1").unwrap_err().to_string(),
+      "invalid ZMQ URL `tcp://127.0.0.1`: expected `tcp://<HOST>:<PORT>`",
+    );
+  }
+
+  #[test]
+  fn subscriber_receives_multipart_messages() {
+    let publisher = Publisher::new();
+    let address = address(&publisher.url);
+
+    let subscriber = thread::spawn(move || {
+      let mut subscriber = Subscriber::connect(&address, TOPICS).unwrap();
+      (subscriber.recv().unwrap(), subscriber.recv().unwrap())
+    });
+
+    let mut connection = publisher.accept();
+
+    assert_eq!(connection.topics, TOPICS);
+
+    connection.publish("hashblock", &[0; 32]);
+    connection.publish("rawblock", &[1; 1000]);
+
+    let (hashblock, rawblock) = subscriber.join().unwrap();
+
+    assert_eq!(
+      hashblock,
+      [
+        b"hashblock".to_vec(),
+        vec![0; 32],
+        0u32.to_le_bytes().to_vec()
+      ]
+    );
+
+    assert_eq!(
+      rawblock,
+      [
+        b"rawblock".to_vec(),
+        vec![1; 1000],
+        1u32.to_le_bytes().to_vec()
+      ]
+    );
+  }
+
+  #[test]
+  fn invalid_greetings_are_rejected() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+
+    thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      stream.write_all(&[0; 64]).unwrap();
+    });
+
+    assert_eq!(
+      Subscriber::connect(&address, TOPICS)
+        .err()
+        .unwrap()
+        .to_string(),
+      "invalid ZMTP greeting",
+    );
+  }
+
+  #[test]
+  fn oversized_frames_are_rejected() {
+    let mut frame = vec![LONG];
+    frame.extend_from_slice(&u64::MAX.to_be_bytes());
+
+    assert_eq!(
+      read_frame(&mut frame.as_slice()).unwrap_err().to_string(),
+      format!(
+        "ZMTP frame of {} bytes exceeds maximum of {MAX_FRAME_SIZE} bytes",
+        u64::MAX
+      ),
+    );
+
+    let mut frame = vec![LONG];
+    frame.extend_from_slice(&3u64.to_be_bytes());
+    frame.extend_from_slice(b"foo");
+
+    assert_eq!(
+      read_frame(&mut frame.as_slice()).unwrap(),
+      (LONG, b"foo".to_vec())
+    );
+  }
+
+  #[test]
+  fn notifications_are_sent_for_blocks_and_reconnects() {
+    let publisher = Publisher::new();
+
+    let receiver = spawn(address(&publisher.url));
+
+    let mut connection = publisher.accept();
+
+    connection.publish("hashblock", &[0; 32]);
+
+    receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+
+    drop(connection);
+
+    let mut connection = publisher.accept();
+
+    receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+
+    connection.publish("rawblock", &[0; 100]);
+
+    receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+  }
+}
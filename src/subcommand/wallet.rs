@@ -1,13 +1,15 @@
 use {
   super::*,
   crate::wallet::{
-    inscribe::{Batch, Batchfile, Mode},
+    etching::{CheckedEtching, EtchingParameters},
+    inscribe::{Batch, Batchfile, EtchingInfo, Mode},
     Wallet,
   },
   bitcoincore_rpc::bitcoincore_rpc_json::ListDescriptorsResult,
   reqwest::Url,
 };
 
+pub mod airdrop;
 pub mod balance;
 pub mod cardinals;
 pub mod create;
@@ -15,9 +17,11 @@ pub mod dump;
 pub mod etch;
 pub mod inscribe;
 pub mod inscriptions;
+pub mod mint;
 pub mod outputs;
 pub mod receive;
 pub mod restore;
+pub mod runes;
 pub mod sats;
 pub mod send;
 pub mod transactions;
@@ -40,6 +44,8 @@ pub(crate) struct WalletCommand {
 #[derive(Debug, Parser)]
 #[allow(clippy::large_enum_variant)]
 pub(crate) enum Subcommand {
+  #[command(about = "Send runes to many recipients")]
+  Airdrop(airdrop::Airdrop),
   #[command(about = "Get wallet balance")]
   Balance,
   #[command(about = "Create new wallet")]
@@ -52,10 +58,14 @@ pub(crate) enum Subcommand {
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
   Inscriptions,
+  #[command(about = "Mint rune")]
+  Mint(mint::Mint),
   #[command(about = "Generate receive address")]
   Receive,
   #[command(about = "Restore wallet")]
   Restore(restore::Restore),
+  #[command(subcommand, about = "Consolidate or split rune outputs")]
+  Runes(runes::RunesSubcommand),
   #[command(about = "List wallet satoshis")]
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
@@ -91,12 +101,15 @@ impl WalletCommand {
     )?;
 
     match self.subcommand {
+      Subcommand::Airdrop(airdrop) => airdrop.run(wallet),
       Subcommand::Balance => balance::run(wallet),
       Subcommand::Dump => dump::run(wallet),
       Subcommand::Etch(etch) => etch.run(wallet),
       Subcommand::Inscribe(inscribe) => inscribe.run(wallet),
       Subcommand::Inscriptions => inscriptions::run(wallet),
+      Subcommand::Mint(mint) => mint.run(wallet),
       Subcommand::Receive => receive::run(wallet),
+      Subcommand::Runes(runes) => runes.run(wallet),
       Subcommand::Sats(sats) => sats.run(wallet),
       Subcommand::Send(send) => send.run(wallet),
       Subcommand::Transactions(transactions) => transactions.run(wallet),
@@ -0,0 +1,534 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{policy::MAX_STANDARD_TX_WEIGHT, psbt::Psbt},
+  std::collections::HashSet,
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Airdrop {
+  #[arg(long, help = "Don't sign or broadcast transactions")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Send runes to recipients listed in CSV file <RECIPIENTS>, one `<ADDRESS>,<AMOUNT>` per line. At most 25 chained transactions are sent per run, and recipients left over are reported as `remaining`."
+  )]
+  recipients: PathBuf,
+  #[arg(long, help = "Airdrop rune <RUNE>. May contain `.` or `•`as spacers.")]
+  rune: SpacedRune,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Batch {
+  pub fee: u64,
+  pub psbt: String,
+  pub recipients: usize,
+  pub txid: Txid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub batches: Vec<Batch>,
+  pub remaining: usize,
+  pub rune: SpacedRune,
+  pub total: Pile,
+}
+
+// Bitcoin Core's default `-limitancestorcount`
+const MAX_CHAIN_LENGTH: usize = 25;
+
+impl Airdrop {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_rune_index(),
+      "`ord wallet airdrop` requires index created with `--index-runes` flag",
+    );
+
+    let (id, entry, _parent) = wallet
+      .get_rune(self.rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", self.rune.rune))?;
+
+    let recipients = Self::parse_recipients(
+      &fs::read_to_string(&self.recipients)
+        .with_context(|| format!("failed to read `{}`", self.recipients.display()))?,
+      wallet.chain(),
+      entry.divisibility,
+    )?;
+
+    ensure!(
+      !recipients.is_empty(),
+      "no recipients in `{}`",
+      self.recipients.display()
+    );
+
+    let total = recipients.iter().map(|(_, amount)| amount).sum::<u128>();
+
+    let bitcoin_client = wallet.bitcoin_client();
+    let unspent_outputs = wallet.utxos();
+    let inscriptions = wallet.inscriptions();
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    let inscribed_outputs = inscriptions
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<HashSet<OutPoint>>();
+
+    let mut input_runes = 0;
+    let mut input = Vec::new();
+
+    for output in &runic_outputs {
+      if inscribed_outputs.contains(output) {
+        continue;
+      }
+
+      let balance = wallet.get_rune_balance_in_output(output, entry.rune)?;
+
+      if balance > 0 {
+        input_runes += balance;
+        input.push(*output);
+      }
+
+      if input_runes >= total {
+        break;
+      }
+    }
+
+    ensure! {
+      input_runes >= total,
+      "insufficient `{}` balance, only {} in wallet",
+      self.rune,
+      Pile {
+        amount: input_runes,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol
+      },
+    }
+
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    // Each transaction after the first spends the rune change output of the
+    // previous one, so only the first is funded by the wallet. Its rune change
+    // output carries the postage and fees of the rest of the chain, which is
+    // cut at the ancestor limit. Room is left in the first transaction for
+    // one funding input.
+    let mut transactions = Self::batch(id, &change, &recipients, input.len() + 1)?;
+
+    transactions.truncate(MAX_CHAIN_LENGTH);
+
+    let batch_sizes = transactions
+      .iter()
+      .map(|transaction| transaction.output.len() - 2)
+      .collect::<Vec<usize>>();
+
+    let sent = batch_sizes.iter().sum::<usize>();
+
+    let total = recipients[..sent]
+      .iter()
+      .map(|(_, amount)| amount)
+      .sum::<u128>();
+
+    let mut change_value = TARGET_POSTAGE.to_sat();
+
+    for transaction in transactions.iter_mut().skip(1).rev() {
+      transaction.output[1].value = change_value;
+
+      change_value = transaction
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<u64>()
+        + self
+          .fee_rate
+          .fee(Self::signed(transaction, 1).vsize())
+          .to_sat();
+    }
+
+    transactions[0].output[1].value = change_value;
+
+    transactions[0].input = input
+      .into_iter()
+      .map(|previous_output| TxIn {
+        previous_output,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      })
+      .collect();
+
+    send::Send::lock_non_cardinal_outputs(
+      bitcoin_client,
+      inscriptions,
+      &runic_outputs,
+      unspent_outputs,
+    )?;
+
+    transactions[0] = consensus::encode::deserialize(&fund_raw_transaction(
+      bitcoin_client,
+      self.fee_rate,
+      &transactions[0],
+    )?)?;
+
+    ensure!(
+      Self::signed(&transactions[0], transactions[0].input.len())
+        .weight()
+        .to_wu()
+        <= u64::from(MAX_STANDARD_TX_WEIGHT),
+      "funded transaction would exceed the standard transaction weight, consolidate cardinal outputs and try again",
+    );
+
+    let mut batches = Vec::new();
+    let mut previous: Option<(Txid, TxOut)> = None;
+
+    for ((i, mut transaction), recipients) in transactions.into_iter().enumerate().zip(batch_sizes)
+    {
+      let psbt = if let Some((txid, txout)) = previous.take() {
+        transaction.input = vec![TxIn {
+          previous_output: OutPoint { txid, vout: 1 },
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::MAX,
+          witness: Witness::new(),
+        }];
+
+        let mut psbt = Psbt::from_unsigned_tx(transaction.clone())?;
+        psbt.inputs[0].witness_utxo = Some(txout);
+        psbt
+      } else {
+        Psbt::from_unsigned_tx(transaction.clone())?
+      };
+
+      let fee = if i == 0 {
+        transaction
+          .input
+          .iter()
+          .map(|txin| unspent_outputs.get(&txin.previous_output).unwrap().value)
+          .sum::<u64>()
+      } else {
+        psbt.inputs[0].witness_utxo.as_ref().unwrap().value
+      }
+      .checked_sub(
+        transaction
+          .output
+          .iter()
+          .map(|txout| txout.value)
+          .sum::<u64>(),
+      )
+      .unwrap();
+
+      let psbt = bitcoin_client
+        .wallet_process_psbt(
+          &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+          Some(!self.dry_run),
+          None,
+          None,
+        )?
+        .psbt;
+
+      let txid = if self.dry_run {
+        transaction.txid()
+      } else {
+        let signed_transaction = bitcoin_client
+          .finalize_psbt(&psbt, None)?
+          .hex
+          .ok_or_else(|| anyhow!("unable to sign transaction"))?;
+
+        bitcoin_client
+          .send_raw_transaction(&signed_transaction)
+          .with_context(|| {
+            if batches.is_empty() {
+              return "failed to broadcast first batch".into();
+            }
+
+            format!(
+              "failed to broadcast batch {}, after sending to the first {} recipients in {}",
+              i + 1,
+              batches
+                .iter()
+                .map(|batch: &Batch| batch.recipients)
+                .sum::<usize>(),
+              batches
+                .iter()
+                .map(|batch| batch.txid.to_string())
+                .collect::<Vec<String>>()
+                .join(", "),
+            )
+          })?
+      };
+
+      previous = Some((txid, transaction.output[1].clone()));
+
+      batches.push(Batch {
+        fee,
+        psbt,
+        recipients,
+        txid,
+      });
+    }
+
+    Ok(Some(Box::new(Output {
+      batches,
+      remaining: recipients.len() - sent,
+      rune: self.rune,
+      total: Pile {
+        amount: total,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol,
+      },
+    })))
+  }
+
+  fn parse_recipients(csv: &str, chain: Chain, divisibility: u8) -> Result<Vec<(Address, u128)>> {
+    let mut recipients = Vec::new();
+
+    for (i, line) in csv.lines().enumerate() {
+      let line = line.trim();
+
+      if line.is_empty() || (i == 0 && line.eq_ignore_ascii_case("address,amount")) {
+        continue;
+      }
+
+      let (address, amount) = (|| -> Result<(Address, u128)> {
+        let (address, amount) = line
+          .split_once(',')
+          .ok_or_else(|| anyhow!("expected `<ADDRESS>,<AMOUNT>`"))?;
+
+        let address = address
+          .trim()
+          .parse::<Address<NetworkUnchecked>>()?
+          .require_network(chain.network())?;
+
+        let amount = amount.trim().parse::<Decimal>()?.to_amount(divisibility)?;
+
+        ensure!(amount > 0, "amount must be greater than zero");
+
+        Ok((address, amount))
+      })()
+      .with_context(|| format!("invalid recipient on line {}", i + 1))?;
+
+      recipients.push((address, amount));
+    }
+
+    Ok(recipients)
+  }
+
+  fn batch(
+    id: RuneId,
+    change: &Script,
+    recipients: &[(Address, u128)],
+    first_inputs: usize,
+  ) -> Result<Vec<Transaction>> {
+    let mut transactions = Vec::new();
+
+    let mut recipients = recipients.iter().peekable();
+
+    while recipients.peek().is_some() {
+      let mut transaction = Self::transaction(id, change, &[])?;
+      let mut batch = Vec::new();
+
+      let first = transactions.is_empty();
+
+      while let Some(recipient) = recipients.peek() {
+        batch.push((*recipient).clone());
+
+        let candidate = Self::transaction(id, change, &batch)?;
+
+        let mut signed = Self::signed(&candidate, if first { first_inputs } else { 1 });
+
+        if first {
+          signed.output.push(TxOut {
+            script_pubkey: change.into(),
+            value: 0,
+          });
+        }
+
+        if candidate.output[0].script_pubkey.len() > MAX_RUNESTONE_SIZE
+          || signed.weight().to_wu() > u64::from(MAX_STANDARD_TX_WEIGHT)
+        {
+          ensure!(
+            batch.len() > 1,
+            "recipient too large for a single transaction"
+          );
+          break;
+        }
+
+        transaction = candidate;
+        recipients.next();
+      }
+
+      transactions.push(transaction);
+    }
+
+    Ok(transactions)
+  }
+
+  fn transaction(id: RuneId, change: &Script, batch: &[(Address, u128)]) -> Result<Transaction> {
+    let runestone = Runestone {
+      edicts: batch
+        .iter()
+        .enumerate()
+        .map(|(i, (_, amount))| Edict {
+          amount: *amount,
+          id: id.into(),
+          output: (i + 2).try_into().unwrap(),
+        })
+        .collect(),
+      ..Default::default()
+    };
+
+    Ok(Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: [
+        TxOut {
+          script_pubkey: runestone.encipher(),
+          value: 0,
+        },
+        TxOut {
+          script_pubkey: change.into(),
+          value: TARGET_POSTAGE.to_sat(),
+        },
+      ]
+      .into_iter()
+      .chain(batch.iter().map(|(address, _)| TxOut {
+        script_pubkey: address.script_pubkey(),
+        value: TARGET_POSTAGE.to_sat(),
+      }))
+      .collect(),
+    })
+  }
+
+  fn signed(transaction: &Transaction, inputs: usize) -> Transaction {
+    let mut transaction = transaction.clone();
+
+    transaction.input = vec![
+      TxIn {
+        previous_output: OutPoint::null(),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::from_slice(&[&[0; 64]]),
+      };
+      inputs
+    ];
+
+    transaction
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_recipients() {
+    let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+
+    assert_eq!(
+      Airdrop::parse_recipients(
+        &format!("address,amount\n{address},1.5\n\n {address} , 2 \n"),
+        Chain::Regtest,
+        1,
+      )
+      .unwrap()
+      .into_iter()
+      .map(|(address, amount)| (address.to_string(), amount))
+      .collect::<Vec<(String, u128)>>(),
+      [(address.to_string(), 15), (address.to_string(), 20)],
+    );
+
+    assert_eq!(
+      Airdrop::parse_recipients(&format!("{address},0"), Chain::Regtest, 0)
+        .unwrap_err()
+        .to_string(),
+      "invalid recipient on line 1",
+    );
+
+    assert_eq!(
+      Airdrop::parse_recipients(&format!("{address},1\n{address}"), Chain::Regtest, 0)
+        .unwrap_err()
+        .root_cause()
+        .to_string(),
+      "expected `<ADDRESS>,<AMOUNT>`",
+    );
+
+    assert!(Airdrop::parse_recipients(&format!("{address},1.5"), Chain::Regtest, 0).is_err());
+
+    assert!(Airdrop::parse_recipients(&format!("{address},1"), Chain::Mainnet, 0).is_err());
+  }
+
+  #[test]
+  fn recipients_are_split_into_standard_transactions() {
+    let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked();
+
+    let id = RuneId {
+      height: 840000,
+      index: 1,
+    };
+
+    let recipients = (0..100)
+      .map(|i| (address.clone(), 1000 + i))
+      .collect::<Vec<(Address, u128)>>();
+
+    let transactions = Airdrop::batch(id, &ScriptBuf::new(), &recipients, 1).unwrap();
+
+    assert!(transactions.len() > 1);
+
+    let mut recipient = 0;
+
+    for transaction in &transactions {
+      assert!(transaction.output[0].script_pubkey.len() <= MAX_RUNESTONE_SIZE);
+
+      let runestone = Runestone::diagnose(transaction).runestone.unwrap();
+
+      for (i, edict) in runestone.edicts.iter().enumerate() {
+        assert_eq!(edict.id, u128::from(id));
+        assert_eq!(edict.amount, recipients[recipient].1);
+        assert_eq!(edict.output, u128::try_from(i).unwrap() + 2);
+        recipient += 1;
+      }
+
+      assert_eq!(transaction.output.len(), runestone.edicts.len() + 2);
+    }
+
+    assert_eq!(recipient, recipients.len());
+
+    let mut recipients = recipients.clone();
+    recipients.push((address, 1));
+
+    assert!(Airdrop::batch(id, &ScriptBuf::new(), &recipients, 1)
+      .unwrap()
+      .iter()
+      .all(|transaction| transaction.output[0].script_pubkey.len() <= MAX_RUNESTONE_SIZE));
+  }
+
+  #[test]
+  fn batches_leave_room_for_inputs() {
+    let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked();
+
+    let id = RuneId {
+      height: 840000,
+      index: 1,
+    };
+
+    let recipients = (0..100)
+      .map(|i| (address.clone(), 1000 + i))
+      .collect::<Vec<(Address, u128)>>();
+
+    let transactions = Airdrop::batch(id, &ScriptBuf::new(), &recipients, 1735).unwrap();
+
+    assert!(transactions.len() > 1);
+
+    assert!(
+      Airdrop::signed(&transactions[0], 1735).weight().to_wu() <= u64::from(MAX_STANDARD_TX_WEIGHT)
+    );
+
+    assert!(transactions[0].output.len() < transactions[1].output.len());
+  }
+}
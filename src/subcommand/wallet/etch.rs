@@ -6,9 +6,21 @@ pub(crate) struct Etch {
   divisibility: u8,
   #[clap(long, help = "Etch with fee rate of <FEE_RATE> sats/vB.")]
   fee_rate: FeeRate,
+  #[clap(
+    long,
+    help = "Allow minting runes until unix timestamp <MINT_DEADLINE>."
+  )]
+  mint_deadline: Option<u32>,
+  #[clap(long, help = "Allow minting <MINT_LIMIT> runes per mint transaction.")]
+  mint_limit: Option<Decimal>,
+  #[clap(long, help = "Allow minting for <MINT_TERM> blocks after etching.")]
+  mint_term: Option<u32>,
   #[clap(long, help = "Etch rune <RUNE>. May contain `.` or `•`as spacers.")]
   rune: SpacedRune,
-  #[clap(long, help = "Set supply to <SUPPLY>.")]
+  #[clap(
+    long,
+    help = "Set supply to <SUPPLY>. For runes with open mints, this is the amount allocated to the etcher, which may not exceed the mint limit."
+  )]
   supply: Decimal,
   #[clap(long, help = "Set currency symbol to <SYMBOL>.")]
   symbol: char,
@@ -16,6 +28,7 @@ pub(crate) struct Etch {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Output {
+  pub entry: RuneEntry,
   pub rune: SpacedRune,
   pub transaction: Txid,
 }
@@ -27,49 +40,46 @@ impl Etch {
       "`ord wallet etch` requires index created with `--index-runes` flag",
     );
 
-    let SpacedRune { rune, spacers } = self.rune;
-
-    let bitcoin_client = wallet.bitcoin_client();
-
-    let count = bitcoin_client.get_block_count()?;
-
-    ensure!(
-      wallet.get_rune(rune)?.is_none(),
-      "rune `{}` has already been etched",
-      rune,
-    );
-
-    let minimum_at_height =
-      Rune::minimum_at_height(wallet.chain(), Height(u32::try_from(count).unwrap() + 1));
+    let CheckedEtching {
+      etching,
+      height,
+      supply,
+      timestamp,
+    } = EtchingParameters {
+      divisibility: self.divisibility,
+      mint_deadline: self.mint_deadline,
+      mint_limit: self.mint_limit,
+      mint_term: self.mint_term,
+      open_mint: self.mint_deadline.is_some()
+        || self.mint_limit.is_some()
+        || self.mint_term.is_some(),
+      rune: self.rune,
+      supply: self.supply,
+      symbol: self.symbol,
+    }
+    .check(&wallet)?;
 
-    ensure!(
-      rune >= minimum_at_height,
-      "rune is less than minimum for next block: {} < {minimum_at_height}",
-      rune,
-    );
+    let SpacedRune { rune, spacers } = self.rune;
 
-    ensure!(!rune.is_reserved(), "rune `{}` is reserved", rune);
+    let mint = etching.mint;
 
-    ensure!(
-      self.divisibility <= crate::runes::MAX_DIVISIBILITY,
-      "<DIVISIBILITY> must be equal to or less than 38"
-    );
+    let bitcoin_client = wallet.bitcoin_client();
 
     let destination = wallet.get_change_address()?;
 
     let runestone = Runestone {
-      etching: Some(Etching {
-        divisibility: self.divisibility,
-        mint: None,
-        rune: Some(rune),
-        spacers,
-        symbol: Some(self.symbol),
-      }),
-      edicts: vec![Edict {
-        amount: self.supply.to_amount(self.divisibility)?,
-        id: 0,
-        output: 1,
-      }],
+      etching: Some(etching),
+      // an edict amount of zero allocates everything, so with an open mint
+      // and no supply for the etcher, leave the etched runes unallocated
+      edicts: if mint.is_some() && supply == 0 {
+        Vec::new()
+      } else {
+        vec![Edict {
+          amount: supply,
+          id: 0,
+          output: 1,
+        }]
+      },
       default_output: None,
       burn: false,
       claim: None,
@@ -78,8 +88,8 @@ impl Etch {
     let script_pubkey = runestone.encipher();
 
     ensure!(
-      script_pubkey.len() <= 82,
-      "runestone greater than maximum OP_RETURN size: {} > 82",
+      script_pubkey.len() <= MAX_RUNESTONE_SIZE,
+      "runestone greater than maximum OP_RETURN size: {} > {MAX_RUNESTONE_SIZE}",
       script_pubkey.len()
     );
 
@@ -118,7 +128,32 @@ impl Etch {
 
     let transaction = bitcoin_client.send_raw_transaction(&signed_transaction)?;
 
+    // the entry the index will create if the etching is confirmed in the
+    // next block
+    let entry = RuneEntry {
+      burned: 0,
+      divisibility: self.divisibility,
+      etching: transaction,
+      mint: mint.map(|mint| MintEntry {
+        deadline: mint.deadline,
+        end: mint.term.map(|term| term + height),
+        limit: mint.limit,
+      }),
+      mints: 0,
+      number: wallet.rune_count(),
+      rune,
+      spacers,
+      supply: if mint.is_none() && supply == 0 {
+        u128::MAX
+      } else {
+        supply
+      },
+      symbol: Some(self.symbol),
+      timestamp,
+    };
+
     Ok(Some(Box::new(Output {
+      entry,
       rune: self.rune,
       transaction,
     })))
@@ -84,6 +84,7 @@ impl Inscribe {
 
     let postages;
     let destinations;
+    let etching_info;
     let inscriptions;
     let mode;
     let parent_info;
@@ -114,6 +115,8 @@ impl Inscribe {
           None,
         )?];
 
+        etching_info = None;
+
         mode = Mode::SeparateOutputs;
 
         reinscribe = self.reinscribe;
@@ -149,6 +152,26 @@ impl Inscribe {
             .map(|(satpoint, txout)| (satpoint.outpoint, txout.clone())),
         );
 
+        etching_info = batchfile
+          .etching
+          .map(|etching| -> Result<EtchingInfo> {
+            ensure!(
+              wallet.has_rune_index(),
+              "etching in a batch requires index created with `--index-runes` flag",
+            );
+
+            let CheckedEtching {
+              etching, supply, ..
+            } = etching.etching(&wallet)?;
+
+            Ok(EtchingInfo {
+              destination: wallet.get_change_address()?,
+              etching,
+              supply,
+            })
+          })
+          .transpose()?;
+
         mode = batchfile.mode;
 
         reinscribe = batchfile.reinscribe;
@@ -166,6 +189,7 @@ impl Inscribe {
       commit_fee_rate: self.commit_fee_rate.unwrap_or(self.fee_rate),
       destinations,
       dry_run: self.dry_run,
+      etching_info,
       inscriptions,
       mode,
       no_backup: self.no_backup,
@@ -215,6 +239,8 @@ mod tests {
     tempfile::TempDir,
   };
 
+  const RUNE: u128 = 99246114928149462;
+
   #[test]
   fn reveal_transaction_pays_fee() {
     let utxos = vec![(outpoint(1), tx_out(20000, address()))];
@@ -894,6 +920,114 @@ inscriptions:
     );
   }
 
+  #[test]
+  fn batch_inscribe_with_etching() {
+    let utxos = vec![(outpoint(1), tx_out(50_000, address()))];
+
+    let etching = Etching {
+      divisibility: 1,
+      mint: None,
+      rune: Some(Rune(RUNE)),
+      spacers: 1,
+      symbol: Some('¢'),
+    };
+
+    let (commit_tx, reveal_tx, _private_key, _) = Batch {
+      destinations: vec![recipient()],
+      etching_info: Some(EtchingInfo {
+        destination: change(2),
+        etching,
+        supply: 1000,
+      }),
+      inscriptions: vec![inscription("text/plain", "logo")],
+      mode: Mode::SharedOutput,
+      postages: vec![TARGET_POSTAGE],
+      ..Default::default()
+    }
+    .create_batch_inscription_transactions(
+      BTreeMap::new(),
+      Chain::Mainnet,
+      BTreeSet::new(),
+      BTreeSet::new(),
+      utxos.into_iter().collect(),
+      [change(0), change(1)],
+    )
+    .unwrap();
+
+    assert_eq!(reveal_tx.output.len(), 3);
+    assert_eq!(
+      reveal_tx.output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(reveal_tx.output[0].value, TARGET_POSTAGE.to_sat());
+    assert_eq!(reveal_tx.output[1].script_pubkey, change(2).script_pubkey());
+    assert_eq!(reveal_tx.output[1].value, TARGET_POSTAGE.to_sat());
+    assert_eq!(reveal_tx.output[2].value, 0);
+
+    assert_eq!(
+      Runestone::from_transaction(&reveal_tx).unwrap(),
+      Runestone {
+        etching: Some(etching),
+        edicts: vec![Edict {
+          amount: 1000,
+          id: 0,
+          output: 1,
+        }],
+        ..Default::default()
+      },
+    );
+
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    let fee = Amount::from_sat((1.0 * (reveal_tx.vsize() as f64)).ceil() as u64);
+
+    assert_eq!(
+      commit_tx.output[0].value,
+      fee.to_sat() + 2 * TARGET_POSTAGE.to_sat(),
+    );
+  }
+
+  #[test]
+  fn batch_inscribe_with_open_mint_etching_and_no_supply_has_no_premine_output() {
+    let utxos = vec![(outpoint(1), tx_out(50_000, address()))];
+
+    let (_commit_tx, reveal_tx, _private_key, _) = Batch {
+      destinations: vec![recipient()],
+      etching_info: Some(EtchingInfo {
+        destination: change(2),
+        etching: Etching {
+          mint: Some(Mint {
+            limit: Some(100),
+            ..Default::default()
+          }),
+          rune: Some(Rune(RUNE)),
+          ..Default::default()
+        },
+        supply: 0,
+      }),
+      inscriptions: vec![inscription("text/plain", "logo")],
+      mode: Mode::SharedOutput,
+      postages: vec![TARGET_POSTAGE],
+      ..Default::default()
+    }
+    .create_batch_inscription_transactions(
+      BTreeMap::new(),
+      Chain::Mainnet,
+      BTreeSet::new(),
+      BTreeSet::new(),
+      utxos.into_iter().collect(),
+      [change(0), change(1)],
+    )
+    .unwrap();
+
+    assert_eq!(reveal_tx.output.len(), 2);
+    assert!(reveal_tx.output[1].script_pubkey.is_op_return());
+    assert_eq!(
+      Runestone::from_transaction(&reveal_tx).unwrap().edicts,
+      Vec::new()
+    );
+  }
+
   #[test]
   fn batch_inscribe_satpoints_with_parent() {
     let utxos = vec![
@@ -0,0 +1,108 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Mint {
+  #[clap(long, help = "Mint with fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[clap(long, help = "Mint rune <RUNE>. May contain `.` or `•`as spacers.")]
+  rune: SpacedRune,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Output {
+  pub rune: SpacedRune,
+  pub pile: Pile,
+  pub transaction: Txid,
+}
+
+impl Mint {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_rune_index(),
+      "`ord wallet mint` requires index created with `--index-runes` flag",
+    );
+
+    let rune = self.rune.rune;
+
+    let bitcoin_client = wallet.bitcoin_client();
+
+    let count = bitcoin_client.get_block_count()?;
+
+    let Some((id, entry, _)) = wallet.get_rune(rune)? else {
+      bail!("rune `{rune}` has not been etched");
+    };
+
+    let Some(mint) = entry.mint else {
+      bail!("rune `{rune}` is not mintable");
+    };
+
+    let height = u32::try_from(count).unwrap() + 1;
+
+    if let Some(end) = mint.end {
+      ensure!(height < end, "rune `{rune}` mint ended at block {end}");
+    }
+
+    if let Some(deadline) = mint.deadline {
+      // the next block's timestamp isn't known yet, so use the chain tip's
+      let tip = bitcoin_client.get_block_header(&bitcoin_client.get_block_hash(count)?)?;
+
+      ensure!(
+        tip.time < deadline,
+        "rune `{rune}` mint deadline {deadline} has passed",
+      );
+    }
+
+    let amount = mint.limit.unwrap_or(crate::runes::MAX_LIMIT);
+
+    let destination = wallet.get_change_address()?;
+
+    // with no edicts, claimed runes are allocated to the first non-OP_RETURN
+    // output, which is the destination
+    let runestone = Runestone {
+      claim: Some(id.into()),
+      ..Default::default()
+    };
+
+    let unfunded_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![
+        TxOut {
+          script_pubkey: runestone.encipher(),
+          value: 0,
+        },
+        TxOut {
+          script_pubkey: destination.script_pubkey(),
+          value: TARGET_POSTAGE.to_sat(),
+        },
+      ],
+    };
+
+    send::Send::lock_non_cardinal_outputs(
+      bitcoin_client,
+      wallet.inscriptions(),
+      &wallet.get_runic_outputs()?,
+      wallet.utxos(),
+    )?;
+
+    let unsigned_transaction =
+      fund_raw_transaction(bitcoin_client, self.fee_rate, &unfunded_transaction)?;
+
+    let signed_transaction = bitcoin_client
+      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+      .hex;
+
+    let transaction = bitcoin_client.send_raw_transaction(&signed_transaction)?;
+
+    Ok(Some(Box::new(Output {
+      rune: entry.spaced_rune(),
+      pile: Pile {
+        amount,
+        divisibility: entry.divisibility,
+        symbol: entry.symbol,
+      },
+      transaction,
+    })))
+  }
+}
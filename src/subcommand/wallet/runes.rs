@@ -0,0 +1,140 @@
+use {super::*, base64::Engine, bitcoin::psbt::Psbt, std::collections::HashSet};
+
+pub mod consolidate;
+pub mod split;
+
+#[derive(Debug, Parser)]
+pub(crate) enum RunesSubcommand {
+  #[command(about = "Merge all outputs holding a rune into one")]
+  Consolidate(consolidate::Consolidate),
+  #[command(about = "Split exact amounts of a rune into separate outputs")]
+  Split(split::Split),
+}
+
+impl RunesSubcommand {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    match self {
+      Self::Consolidate(consolidate) => consolidate.run(wallet),
+      Self::Split(split) => split.run(wallet),
+    }
+  }
+}
+
+fn outputs_holding(wallet: &Wallet, rune: Rune) -> Result<Vec<(OutPoint, u128)>> {
+  let inscribed_outputs = wallet
+    .inscriptions()
+    .keys()
+    .map(|satpoint| satpoint.outpoint)
+    .collect::<HashSet<OutPoint>>();
+
+  let mut outputs = Vec::new();
+
+  for output in wallet.get_runic_outputs()? {
+    if inscribed_outputs.contains(&output) {
+      continue;
+    }
+
+    let balance = wallet.get_rune_balance_in_output(&output, rune)?;
+
+    if balance > 0 {
+      outputs.push((output, balance));
+    }
+  }
+
+  Ok(outputs)
+}
+
+fn fund_and_send(
+  wallet: &Wallet,
+  fee_rate: FeeRate,
+  transaction: &Transaction,
+  dry_run: bool,
+) -> Result<(Transaction, String, u64)> {
+  let bitcoin_client = wallet.bitcoin_client();
+  let unspent_outputs = wallet.utxos();
+
+  send::Send::lock_non_cardinal_outputs(
+    bitcoin_client,
+    wallet.inscriptions(),
+    &wallet.get_runic_outputs()?,
+    unspent_outputs,
+  )?;
+
+  let transaction: Transaction = consensus::encode::deserialize(&fund_raw_transaction(
+    bitcoin_client,
+    fee_rate,
+    transaction,
+  )?)?;
+
+  let psbt = bitcoin_client
+    .wallet_process_psbt(
+      &base64::engine::general_purpose::STANDARD
+        .encode(Psbt::from_unsigned_tx(transaction.clone())?.serialize()),
+      Some(!dry_run),
+      None,
+      None,
+    )?
+    .psbt;
+
+  if !dry_run {
+    let signed_transaction = bitcoin_client
+      .finalize_psbt(&psbt, None)?
+      .hex
+      .ok_or_else(|| anyhow!("unable to sign transaction"))?;
+
+    bitcoin_client.send_raw_transaction(&signed_transaction)?;
+  }
+
+  let fee = transaction
+    .input
+    .iter()
+    .map(|txin| unspent_outputs.get(&txin.previous_output).unwrap().value)
+    .sum::<u64>()
+    .checked_sub(
+      transaction
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<u64>(),
+    )
+    .unwrap();
+
+  Ok((transaction, psbt, fee))
+}
+
+fn unfunded_transaction(
+  inputs: &[OutPoint],
+  runestone: &Runestone,
+  destinations: Vec<ScriptBuf>,
+) -> Result<Transaction> {
+  let script_pubkey = runestone.encipher();
+
+  ensure!(
+    script_pubkey.len() <= MAX_RUNESTONE_SIZE,
+    "runestone is {} bytes, larger than the standard limit of {MAX_RUNESTONE_SIZE} bytes",
+    script_pubkey.len(),
+  );
+
+  Ok(Transaction {
+    version: 2,
+    lock_time: LockTime::ZERO,
+    input: inputs
+      .iter()
+      .map(|previous_output| TxIn {
+        previous_output: *previous_output,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      })
+      .collect(),
+    output: std::iter::once(TxOut {
+      script_pubkey,
+      value: 0,
+    })
+    .chain(destinations.into_iter().map(|script_pubkey| TxOut {
+      script_pubkey,
+      value: TARGET_POSTAGE.to_sat(),
+    }))
+    .collect(),
+  })
+}
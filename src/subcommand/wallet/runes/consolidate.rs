@@ -0,0 +1,112 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Consolidate {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Consolidate outputs holding rune <RUNE>. May contain `.` or `•`as spacers."
+  )]
+  rune: SpacedRune,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub fee: u64,
+  pub inputs: Vec<OutPoint>,
+  pub output: OutPoint,
+  pub psbt: String,
+  pub rune: SpacedRune,
+  pub total: Pile,
+  pub txid: Txid,
+}
+
+impl Consolidate {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_rune_index(),
+      "`ord wallet runes consolidate` requires index created with `--index-runes` flag",
+    );
+
+    let (id, entry, _parent) = wallet
+      .get_rune(self.rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", self.rune.rune))?;
+
+    let (inputs, balances): (Vec<OutPoint>, Vec<u128>) =
+      outputs_holding(&wallet, entry.rune)?.into_iter().unzip();
+
+    ensure!(
+      inputs.len() > 1,
+      "`{}` is held in {} output{}, nothing to consolidate",
+      self.rune,
+      inputs.len(),
+      if inputs.len() == 1 { "" } else { "s" },
+    );
+
+    // the consolidated rune goes to output 1, and any other runes held in the
+    // same inputs go to output 2, so they aren't mixed into the consolidated
+    // output
+    let mut other_runes = BTreeSet::new();
+
+    for input in &inputs {
+      for (spaced_rune, _pile) in wallet.get_runes_balances_for_output(input)? {
+        if spaced_rune.rune != entry.rune {
+          other_runes.insert(spaced_rune.rune);
+        }
+      }
+    }
+
+    let mut edicts = vec![Edict {
+      amount: 0,
+      id: id.into(),
+      output: 1,
+    }];
+
+    for rune in &other_runes {
+      let (id, _entry, _parent) = wallet
+        .get_rune(*rune)?
+        .with_context(|| format!("rune `{rune}` has not been etched"))?;
+
+      edicts.push(Edict {
+        amount: 0,
+        id: id.into(),
+        output: 2,
+      });
+    }
+
+    let mut destinations = vec![wallet.get_change_address()?.script_pubkey()];
+
+    if !other_runes.is_empty() {
+      destinations.push(wallet.get_change_address()?.script_pubkey());
+    }
+
+    let (transaction, psbt, fee) = fund_and_send(
+      &wallet,
+      self.fee_rate,
+      &unfunded_transaction(
+        &inputs,
+        &Runestone {
+          edicts,
+          ..Default::default()
+        },
+        destinations,
+      )?,
+      self.dry_run,
+    )?;
+
+    let txid = transaction.txid();
+
+    Ok(Some(Box::new(Output {
+      fee,
+      inputs,
+      output: OutPoint { txid, vout: 1 },
+      psbt,
+      rune: self.rune,
+      total: entry.pile(balances.into_iter().sum()),
+      txid,
+    })))
+  }
+}
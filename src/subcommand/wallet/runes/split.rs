@@ -0,0 +1,119 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Split {
+  #[arg(
+    long,
+    required = true,
+    value_delimiter = ',',
+    help = "Split off one output holding each of <AMOUNTS>, separated by commas."
+  )]
+  amounts: Vec<Decimal>,
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Split rune <RUNE>. May contain `.` or `•`as spacers.")]
+  rune: SpacedRune,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub fee: u64,
+  pub outputs: Vec<(OutPoint, Pile)>,
+  pub psbt: String,
+  pub rune: SpacedRune,
+  pub txid: Txid,
+}
+
+impl Split {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      wallet.has_rune_index(),
+      "`ord wallet runes split` requires index created with `--index-runes` flag",
+    );
+
+    let (id, entry, _parent) = wallet
+      .get_rune(self.rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", self.rune.rune))?;
+
+    let amounts = self
+      .amounts
+      .iter()
+      .map(|decimal| {
+        let amount = decimal.to_amount(entry.divisibility)?;
+        ensure!(amount > 0, "amounts must be greater than zero");
+        Ok(amount)
+      })
+      .collect::<Result<Vec<u128>>>()?;
+
+    let total = amounts.iter().sum::<u128>();
+
+    let mut input_runes = 0;
+    let mut inputs = Vec::new();
+
+    for (output, balance) in outputs_holding(&wallet, entry.rune)? {
+      if input_runes >= total {
+        break;
+      }
+
+      input_runes += balance;
+      inputs.push(output);
+    }
+
+    ensure! {
+      input_runes >= total,
+      "insufficient `{}` balance, only {} in wallet",
+      self.rune,
+      entry.pile(input_runes),
+    }
+
+    // output 1 receives the remaining balance of the split rune along with
+    // any other runes held in the inputs, and the split outputs follow
+    let runestone = Runestone {
+      edicts: amounts
+        .iter()
+        .enumerate()
+        .map(|(i, amount)| Edict {
+          amount: *amount,
+          id: id.into(),
+          output: (i + 2).try_into().unwrap(),
+        })
+        .collect(),
+      ..Default::default()
+    };
+
+    let destinations = (0..=amounts.len())
+      .map(|_| Ok(wallet.get_change_address()?.script_pubkey()))
+      .collect::<Result<Vec<ScriptBuf>>>()?;
+
+    let (transaction, psbt, fee) = fund_and_send(
+      &wallet,
+      self.fee_rate,
+      &unfunded_transaction(&inputs, &runestone, destinations)?,
+      self.dry_run,
+    )?;
+
+    let txid = transaction.txid();
+
+    Ok(Some(Box::new(Output {
+      fee,
+      outputs: amounts
+        .into_iter()
+        .enumerate()
+        .map(|(i, amount)| {
+          (
+            OutPoint {
+              txid,
+              vout: (i + 2).try_into().unwrap(),
+            },
+            entry.pile(amount),
+          )
+        })
+        .collect(),
+      psbt,
+      rune: self.rune,
+      txid,
+    })))
+  }
+}
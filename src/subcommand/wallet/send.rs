@@ -16,6 +16,11 @@ pub(crate) struct Send {
     help = "Target amount of postage to include with sent inscriptions [default: 10000 sat]"
   )]
   pub(crate) postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Allow spending runic UTXOs to pay fees and postage when sending an inscription or satpoint, sending their runes to a change output"
+  )]
+  pub(crate) spend_runic_utxos: bool,
   address: Address<NetworkUnchecked>,
   outgoing: Outgoing,
 }
@@ -35,6 +40,15 @@ impl Send {
       .clone()
       .require_network(wallet.chain().network())?;
 
+    ensure!(
+      !self.spend_runic_utxos
+        || matches!(
+          self.outgoing,
+          Outgoing::InscriptionId(_) | Outgoing::SatPoint(_)
+        ),
+      "`--spend-runic-utxos` can only be used when sending an inscription or satpoint",
+    );
+
     let unsigned_transaction = match self.outgoing {
       Outgoing::Amount(amount) => {
         Self::create_unsigned_send_amount_transaction(&wallet, address, amount, self.fee_rate)?
@@ -57,6 +71,7 @@ impl Send {
         self.postage,
         self.fee_rate,
         true,
+        self.spend_runic_utxos,
       )?,
       Outgoing::SatPoint(satpoint) => Self::create_unsigned_send_satpoint_transaction(
         &wallet,
@@ -65,6 +80,7 @@ impl Send {
         self.postage,
         self.fee_rate,
         false,
+        self.spend_runic_utxos,
       )?,
     };
 
@@ -127,7 +143,7 @@ impl Send {
     })))
   }
 
-  fn lock_non_cardinal_outputs(
+  pub(crate) fn lock_non_cardinal_outputs(
     bitcoin_client: &Client,
     inscriptions: &BTreeMap<SatPoint, Vec<InscriptionId>>,
     runic_outputs: &BTreeSet<OutPoint>,
@@ -191,6 +207,7 @@ impl Send {
     postage: Option<Amount>,
     fee_rate: FeeRate,
     sending_inscription: bool,
+    spend_runic_utxos: bool,
   ) -> Result<Transaction> {
     if !sending_inscription {
       for inscription_satpoint in wallet.inscriptions().keys() {
@@ -227,6 +244,7 @@ impl Send {
         fee_rate,
         postage,
       )
+      .spend_runic_utxos(spend_runic_utxos)
       .build_transaction()?,
     )
   }
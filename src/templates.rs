@@ -1,6 +1,7 @@
 use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
+  address::AddressHtml,
   block::BlockHtml,
   children::ChildrenHtml,
   clock::ClockSvg,
@@ -13,6 +14,7 @@ pub(crate) use {
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
   output::OutputHtml,
+  pending_inscription::PendingInscriptionHtml,
   preview::{
     PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
     PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
@@ -25,10 +27,17 @@ pub(crate) use {
 };
 
 pub use {
-  blocks::BlocksHtml, rune::RuneHtml, runes::RunesHtml, status::StatusHtml,
+  blocks::BlocksHtml,
+  inscription_history::{InscriptionHistoryHtml, InscriptionTransfer},
+  mempool::{MempoolHtml, PendingRuneTransfer, PendingTransaction, PendingTransfer},
+  rune::RuneHtml,
+  rune_holders::{Concentration, RuneHolder, RuneHoldersHtml},
+  runes::RunesHtml,
+  status::StatusHtml,
   transaction::TransactionHtml,
 };
 
+mod address;
 pub mod block;
 pub mod blocks;
 mod children;
@@ -38,15 +47,19 @@ mod home;
 mod iframe;
 mod input;
 pub mod inscription;
+pub mod inscription_history;
 pub mod inscriptions;
 mod inscriptions_block;
+pub mod mempool;
 mod metadata;
 pub mod output;
+mod pending_inscription;
 mod preview;
 mod range;
 mod rare;
 pub mod rune;
 pub mod rune_balances;
+pub mod rune_holders;
 pub mod runes;
 pub mod sat;
 pub mod status;
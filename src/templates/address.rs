@@ -0,0 +1,111 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHtml {
+  pub(crate) address: Address,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) outputs: Vec<OutPoint>,
+  pub(crate) runes_balances: Vec<(SpacedRune, Pile)>,
+  pub(crate) sat_balance: u64,
+  pub(crate) sat_ranges: Option<Vec<(u64, u64)>>,
+}
+
+impl PageContent for AddressHtml {
+  fn title(&self) -> String {
+    format!("Address {}", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn address() -> Address {
+    "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked()
+  }
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        inscriptions: Vec::new(),
+        outputs: Vec::new(),
+        runes_balances: Vec::new(),
+        sat_balance: 0,
+        sat_ranges: None,
+      },
+      "
+        <h1>Address <span class=monospace>bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq</span></h1>
+        <dl>
+          <dt>sat balance</dt><dd>0</dd>
+        </dl>
+        <h2>0 Outputs</h2>
+        <ul class=monospace>
+        </ul>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_outputs_inscriptions_runes_and_sat_ranges() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        inscriptions: vec![inscription_id(1)],
+        outputs: vec![outpoint(1), outpoint(2)],
+        runes_balances: vec![(
+          SpacedRune {
+            rune: Rune(26),
+            spacers: 1,
+          },
+          Pile {
+            amount: 1000,
+            divisibility: 1,
+            symbol: Some('$'),
+          }
+        )],
+        sat_balance: 3,
+        sat_ranges: Some(vec![(0, 1), (1, 3)]),
+      },
+      "
+        <h1>Address <span class=monospace>bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq</span></h1>
+        <dl>
+          <dt>sat balance</dt><dd>3</dd>
+          <dt>inscriptions</dt>
+          <dd class=thumbnails>
+            <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          </dd>
+          <dt>runes</dt>
+          <dd>
+            <table>
+              <tr>
+                <th>rune</th>
+                <th>balance</th>
+              </tr>
+              <tr>
+                <td><a href=/rune/A•A>A•A</a></td>
+                <td>100\u{00A0}\\$</td>
+              </tr>
+            </table>
+          </dd>
+        </dl>
+        <h2>2 Outputs</h2>
+        <ul class=monospace>
+          <li><a href=/output/1{64}:1>1{64}:1</a></li>
+          <li><a href=/output/2{64}:2>2{64}:2</a></li>
+        </ul>
+        <h2>2 Sat Ranges</h2>
+        <ul class=monospace>
+          <li><a href=/sat/0 class=mythic>0</a></li>
+          <li><a href=/range/1/3 class=common>1–3</a></li>
+        </ul>
+      "
+      .unindent()
+    );
+  }
+}
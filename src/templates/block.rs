@@ -9,6 +9,7 @@ pub(crate) struct BlockHtml {
   height: Height,
   inscription_count: usize,
   featured_inscriptions: Vec<InscriptionId>,
+  runes: Vec<(RuneEntry, RuneActivity)>,
 }
 
 impl BlockHtml {
@@ -18,6 +19,7 @@ impl BlockHtml {
     best_height: Height,
     inscription_count: usize,
     featured_inscriptions: Vec<InscriptionId>,
+    runes: Vec<(RuneEntry, RuneActivity)>,
   ) -> Self {
     Self {
       hash: block.header.block_hash(),
@@ -27,6 +29,7 @@ impl BlockHtml {
       best_height,
       inscription_count,
       featured_inscriptions,
+      runes,
     }
   }
 }
@@ -49,6 +52,7 @@ mod tests {
         Height(0),
         Height(0),
         0,
+        Vec::new(),
         Vec::new()
       ),
       "
@@ -84,6 +88,7 @@ mod tests {
         Height(0),
         Height(1),
         0,
+        Vec::new(),
         Vec::new()
       ),
       r"<h1>Block 0</h1>.*prev\s*<a class=next href=/block/1>next</a>.*"
@@ -98,12 +103,69 @@ mod tests {
         Height(1),
         Height(1),
         0,
+        Vec::new(),
         Vec::new()
       ),
       r"<h1>Block 1</h1>.*<a class=prev href=/block/0>prev</a>\s*next.*",
     );
   }
 
+  #[test]
+  fn runes() {
+    assert_regex_match!(
+      BlockHtml::new(
+        Chain::Mainnet.genesis_block(),
+        Height(0),
+        Height(0),
+        0,
+        Vec::new(),
+        vec![(
+          RuneEntry {
+            divisibility: 1,
+            rune: Rune(0),
+            symbol: Some('%'),
+            ..Default::default()
+          },
+          RuneActivity {
+            burned: 1,
+            etched: true,
+            minted: 20,
+            mints: 2,
+            transferred: 30,
+            transfers: 3,
+          }
+        )],
+      ),
+      "
+        .*
+        <h2>1 Rune</h2>
+        <table>
+          <tr>
+            <th>rune</th>
+            <th>etched</th>
+            <th>mints</th>
+            <th>minted</th>
+            <th>transfers</th>
+            <th>transferred</th>
+            <th>burned</th>
+          </tr>
+          <tr>
+            <td><a href=/rune/A>A</a></td>
+            <td>true</td>
+            <td>2</td>
+            <td>2\u{A0}%</td>
+            <td>3</td>
+            <td>3\u{A0}%</td>
+            <td>0.1\u{A0}%</td>
+          </tr>
+        </table>
+        <h2>1 Transaction</h2>
+        .*
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn block_hash_serializes_as_hex_string() {
     assert_eq!(
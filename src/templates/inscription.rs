@@ -7,12 +7,14 @@ pub(crate) struct InscriptionHtml {
   pub(crate) children: Vec<InscriptionId>,
   pub(crate) fee: u64,
   pub(crate) height: u32,
+  pub(crate) history: bool,
   pub(crate) inscription: Inscription,
   pub(crate) id: InscriptionId,
   pub(crate) number: i32,
   pub(crate) next: Option<InscriptionId>,
   pub(crate) output: Option<TxOut>,
   pub(crate) parent: Option<InscriptionId>,
+  pub(crate) pending_transfer: Option<PendingTransfer>,
   pub(crate) previous: Option<InscriptionId>,
   pub(crate) rune: Option<SpacedRune>,
   pub(crate) sat: Option<Sat>,
@@ -414,6 +416,34 @@ mod tests {
     );
   }
 
+  #[test]
+  fn with_history() {
+    assert_regex_match!(
+      InscriptionHtml {
+        fee: 1,
+        history: true,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        id: inscription_id(1),
+        number: 1,
+        satpoint: satpoint(1, 0),
+        ..Default::default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        .*
+        <dl>
+          .*
+          <dt>offset</dt>
+          <dd>0</dd>
+          <dt>history</dt>
+          <dd><a href=/inscription/1{64}i1/history>transfers</a></dd>
+          .*
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
   #[test]
   fn with_content_encoding() {
     assert_regex_match!(
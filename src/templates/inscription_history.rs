@@ -0,0 +1,118 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionHistoryHtml {
+  pub id: InscriptionId,
+  pub number: i32,
+  pub transfers: Vec<InscriptionTransfer>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionTransfer {
+  pub address: Option<String>,
+  pub height: u32,
+  pub new_satpoint: SatPoint,
+  pub old_satpoint: SatPoint,
+  pub txid: Txid,
+  pub value: Option<u64>,
+}
+
+impl PageContent for InscriptionHistoryHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} History", self.number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        id: inscription_id(1),
+        number: 1,
+        transfers: Vec::new(),
+      },
+      "
+        <h1>Inscription 1 History</h1>
+        <dl>
+          <dt>id</dt>
+          <dd><a class=monospace href=/inscription/1{64}i1>1{64}i1</a></dd>
+          <dt>transfers</dt>
+          <dd>0</dd>
+        </dl>
+        <p>No transfers recorded.</p>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_transfers() {
+    assert_regex_match!(
+      InscriptionHistoryHtml {
+        id: inscription_id(1),
+        number: 1,
+        transfers: vec![
+          InscriptionTransfer {
+            address: Some("bc1qxyz".into()),
+            height: 2,
+            new_satpoint: satpoint(2, 0),
+            old_satpoint: satpoint(1, 0),
+            txid: txid(2),
+            value: Some(10_000),
+          },
+          InscriptionTransfer {
+            address: None,
+            height: 3,
+            new_satpoint: SatPoint {
+              outpoint: OutPoint::null(),
+              offset: 0,
+            },
+            old_satpoint: satpoint(2, 0),
+            txid: txid(3),
+            value: None,
+          },
+        ],
+      },
+      "
+        <h1>Inscription 1 History</h1>
+        <dl>
+          <dt>id</dt>
+          <dd><a class=monospace href=/inscription/1{64}i1>1{64}i1</a></dd>
+          <dt>transfers</dt>
+          <dd>2</dd>
+        </dl>
+        <table>
+          <tr>
+            <th>height</th>
+            <th>transaction</th>
+            <th>from</th>
+            <th>to</th>
+            <th>value</th>
+            <th>address</th>
+          </tr>
+          <tr>
+            <td><a href=/block/2>2</a></td>
+            <td><a class=monospace href=/tx/2{64}>2{64}</a></td>
+            <td class=monospace>1{64}:1:0</td>
+            <td class=monospace>2{64}:2:0</td>
+            <td>10000</td>
+            <td class=monospace><a href=/address/bc1qxyz>bc1qxyz</a></td>
+          </tr>
+          <tr>
+            <td><a href=/block/3>3</a></td>
+            <td><a class=monospace href=/tx/3{64}>3{64}</a></td>
+            <td class=monospace>2{64}:2:0</td>
+            <td class=monospace>0{64}:4294967295:0</td>
+            <td>lost</td>
+            <td></td>
+          </tr>
+        </table>
+      "
+      .unindent()
+    );
+  }
+}
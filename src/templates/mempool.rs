@@ -0,0 +1,142 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MempoolHtml {
+  pub count: usize,
+  pub transactions: Vec<PendingTransaction>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingTransaction {
+  pub etching: Option<SpacedRune>,
+  pub inscriptions: Vec<InscriptionId>,
+  pub runes: Vec<PendingRuneTransfer>,
+  pub transfers: Vec<PendingTransfer>,
+  pub txid: Txid,
+}
+
+impl PendingTransaction {
+  pub(crate) fn is_active(&self) -> bool {
+    self.etching.is_some()
+      || !self.inscriptions.is_empty()
+      || !self.runes.is_empty()
+      || !self.transfers.is_empty()
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingTransfer {
+  pub id: InscriptionId,
+  pub location: Option<SatPoint>,
+  pub txid: Txid,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingRuneTransfer {
+  pub location: Option<OutPoint>,
+  pub pile: Pile,
+  pub rune: SpacedRune,
+}
+
+impl PageContent for MempoolHtml {
+  fn title(&self) -> String {
+    "Mempool".to_string()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty() {
+    assert_regex_match!(
+      MempoolHtml {
+        count: 3,
+        transactions: Vec::new(),
+      },
+      "<h1>Mempool</h1>
+<dl>
+  <dt>unconfirmed transactions</dt>
+  <dd>3</dd>
+</dl>
+<p>No unconfirmed transactions create or move inscriptions or runes.</p>
+"
+    );
+  }
+
+  #[test]
+  fn with_transactions() {
+    assert_regex_match!(
+      MempoolHtml {
+        count: 2,
+        transactions: vec![PendingTransaction {
+          etching: Some(SpacedRune {
+            rune: Rune(0),
+            spacers: 0,
+          }),
+          inscriptions: vec![inscription_id(1)],
+          runes: vec![
+            PendingRuneTransfer {
+              location: Some(OutPoint {
+                txid: txid(1),
+                vout: 0,
+              }),
+              pile: Pile {
+                amount: 100,
+                divisibility: 0,
+                symbol: None,
+              },
+              rune: SpacedRune {
+                rune: Rune(1),
+                spacers: 0,
+              },
+            },
+            PendingRuneTransfer {
+              location: None,
+              pile: Pile {
+                amount: 5,
+                divisibility: 0,
+                symbol: None,
+              },
+              rune: SpacedRune {
+                rune: Rune(1),
+                spacers: 0,
+              },
+            },
+          ],
+          transfers: vec![PendingTransfer {
+            id: inscription_id(2),
+            location: None,
+            txid: txid(1),
+          }],
+          txid: txid(1),
+        }],
+      },
+      "<h1>Mempool</h1>
+<dl>
+  <dt>unconfirmed transactions</dt>
+  <dd>2</dd>
+</dl>
+<table>
+  <tr>
+    <th>transaction</th>
+    <th>inscriptions</th>
+    <th>transfers</th>
+    <th>runes</th>
+  </tr>
+  <tr>
+    <td class=monospace><a href=/tx/1{64}>1{64}</a></td>
+    <td>1</td>
+    <td>1</td>
+    <td>
+      <div>etching A</div>
+      <div><a href=/rune/B>B</a> 100 to output 0</div>
+      <div><a href=/rune/B>B</a> 5 burned</div>
+    </td>
+  </tr>
+</table>
+"
+    );
+  }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct PendingInscriptionHtml {
+  pub(crate) id: InscriptionId,
+  pub(crate) inscription: Inscription,
+}
+
+impl PageContent for PendingInscriptionHtml {
+  fn title(&self) -> String {
+    format!("Unconfirmed Inscription {}", self.id)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn html() {
+    assert_regex_match!(
+      PendingInscriptionHtml {
+        id: inscription_id(1),
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+      },
+      "
+        <h1>Unconfirmed Inscription</h1>
+        <p>.*</p>
+        <dl>
+          <dt>id</dt>
+          <dd class=monospace>1{64}i1</dd>
+          <dt>content length</dt>
+          <dd>10 bytes</dd>
+          <dt>content type</dt>
+          <dd>text/plain;charset=utf-8</dd>
+          <dt>reveal transaction</dt>
+          <dd><a class=monospace href=/tx/1{64}>1{64}</a></dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
@@ -77,6 +77,8 @@ mod tests {
   <dd>123456789.123456789\u{00A0}%</dd>
   <dt>burned</dt>
   <dd>123456789.123456789\u{00A0}%</dd>
+  <dt>holders</dt>
+  <dd><a href=/rune/10:9/holders>holders</a></dd>
   <dt>divisibility</dt>
   <dd>9</dd>
   <dt>symbol</dt>
@@ -131,6 +133,8 @@ mod tests {
   <dd>123456789.123456789\u{00A0}%</dd>
   <dt>burned</dt>
   <dd>123456789.123456789\u{00A0}%</dd>
+  <dt>holders</dt>
+  <dd><a href=/rune/10:9/holders>holders</a></dd>
   <dt>divisibility</dt>
   <dd>9</dd>
   <dt>symbol</dt>
@@ -198,6 +202,8 @@ mod tests {
   <dd>123456789.123456789\u{00A0}%</dd>
   <dt>burned</dt>
   <dd>123456789.123456789\u{00A0}%</dd>
+  <dt>holders</dt>
+  <dd><a href=/rune/10:9/holders>holders</a></dd>
   <dt>divisibility</dt>
   <dd>9</dd>
   <dt>symbol</dt>
@@ -0,0 +1,180 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHoldersHtml {
+  pub concentration: Concentration,
+  pub entry: RuneEntry,
+  pub held: u128,
+  pub holders: Vec<RuneHolder>,
+  pub id: RuneId,
+  pub more: bool,
+  pub outputs: usize,
+  pub page_index: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolder {
+  pub address: Option<String>,
+  pub amount: u128,
+  pub outpoint: OutPoint,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Concentration {
+  pub top_1: u128,
+  pub top_10: u128,
+  pub top_100: u128,
+}
+
+impl Concentration {
+  pub(crate) fn new(balances: &[u128]) -> Self {
+    let top = |n: usize| balances.iter().take(n).sum();
+
+    Self {
+      top_1: top(1),
+      top_10: top(10),
+      top_100: top(100),
+    }
+  }
+}
+
+impl RuneHoldersHtml {
+  pub(crate) const PAGE_SIZE: usize = 100;
+
+  fn rank(&self, i: usize) -> usize {
+    usize::try_from(self.page_index).unwrap() * Self::PAGE_SIZE + i + 1
+  }
+
+  fn pile(&self, amount: u128) -> Pile {
+    Pile {
+      amount,
+      divisibility: self.entry.divisibility,
+      symbol: self.entry.symbol,
+    }
+  }
+
+  fn share(&self, amount: u128) -> String {
+    if self.held == 0 {
+      return "0.00%".into();
+    }
+
+    format!("{:.2}%", amount as f64 / self.held as f64 * 100.0)
+  }
+}
+
+impl PageContent for RuneHoldersHtml {
+  fn title(&self) -> String {
+    format!("Rune {} Holders", self.entry.spaced_rune())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::runes::Rune};
+
+  #[test]
+  fn concentration() {
+    assert_eq!(Concentration::new(&[]), Concentration::default());
+
+    assert_eq!(
+      Concentration::new(&(1..=200).rev().collect::<Vec<u128>>()),
+      Concentration {
+        top_1: 200,
+        top_10: (191..=200).sum(),
+        top_100: (101..=200).sum(),
+      }
+    );
+  }
+
+  #[test]
+  fn display() {
+    assert_regex_match!(
+      RuneHoldersHtml {
+        concentration: Concentration {
+          top_1: 750,
+          top_10: 1000,
+          top_100: 1000,
+        },
+        entry: RuneEntry {
+          burned: 0,
+          divisibility: 1,
+          etching: Txid::all_zeros(),
+          mint: None,
+          mints: 0,
+          number: 0,
+          rune: Rune(99246114928149462),
+          spacers: 0,
+          supply: 1000,
+          symbol: Some('%'),
+          timestamp: 0,
+        },
+        held: 1000,
+        holders: vec![
+          RuneHolder {
+            address: Some("bc1qxyz".into()),
+            amount: 750,
+            outpoint: OutPoint {
+              txid: txid(1),
+              vout: 1,
+            },
+          },
+          RuneHolder {
+            address: None,
+            amount: 250,
+            outpoint: OutPoint {
+              txid: txid(2),
+              vout: 0,
+            },
+          },
+        ],
+        id: RuneId {
+          height: 10,
+          index: 9,
+        },
+        more: true,
+        outputs: 2,
+        page_index: 0,
+      },
+      "<h1>AAAAAAAAAAAAA Holders</h1>
+<dl>
+  <dt>rune</dt>
+  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
+  <dt>holding outputs</dt>
+  <dd>2</dd>
+  <dt>held</dt>
+  <dd>100\u{00A0}%</dd>
+  <dt>top output</dt>
+  <dd>75\u{00A0}% \\(75.00%\\)</dd>
+  <dt>top 10 outputs</dt>
+  <dd>100\u{00A0}% \\(100.00%\\)</dd>
+  <dt>top 100 outputs</dt>
+  <dd>100\u{00A0}% \\(100.00%\\)</dd>
+</dl>
+<table>
+  <tr>
+    <th>rank</th>
+    <th>output</th>
+    <th>address</th>
+    <th>balance</th>
+  </tr>
+  <tr>
+    <td>1</td>
+    <td class=monospace><a href=/output/1{64}:1>1{64}:1</a></td>
+    <td class=monospace><a href=/address/bc1qxyz>bc1qxyz</a></td>
+    <td>75\u{00A0}%</td>
+  </tr>
+  <tr>
+    <td>2</td>
+    <td class=monospace><a href=/output/2{64}:0>2{64}:0</a></td>
+    <td></td>
+    <td>25\u{00A0}%</td>
+  </tr>
+</table>
+<div class=center>
+prev
+<a class=next href=/rune/10:9/holders/1>next</a>
+</div>
+"
+    );
+  }
+}
@@ -3,6 +3,12 @@ use super::*;
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RunesHtml {
   pub entries: Vec<(RuneId, RuneEntry)>,
+  pub more: bool,
+  #[serde(skip)]
+  pub next: Option<String>,
+  pub page_index: u32,
+  #[serde(skip)]
+  pub prev: Option<String>,
 }
 
 impl PageContent for RunesHtml {
@@ -30,12 +36,42 @@ mod tests {
             ..Default::default()
           }
         )],
+        more: false,
+        next: None,
+        page_index: 0,
+        prev: None,
       }
       .to_string(),
       "<h1>Runes</h1>
 <ul>
   <li><a href=/rune/A•A>A•A</a></li>
 </ul>
+<div class=center>
+prev
+next
+</div>
+"
+    );
+  }
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_eq!(
+      RunesHtml {
+        entries: Vec::new(),
+        more: true,
+        next: Some("/runes?page=2&sort=supply".into()),
+        page_index: 1,
+        prev: Some("/runes?page=0&sort=supply".into()),
+      }
+      .to_string(),
+      "<h1>Runes</h1>
+<ul>
+</ul>
+<div class=center>
+<a class=prev href=/runes?page=0&amp;sort=supply>prev</a>
+<a class=next href=/runes?page=2&amp;sort=supply>next</a>
+</div>
 "
     );
   }
@@ -11,6 +11,8 @@ pub struct StatusHtml {
   pub inscriptions: u64,
   pub lost_sats: u64,
   pub minimum_rune_for_next_block: Rune,
+  pub reorgs: Vec<ReorgEntry>,
+  pub replica_lag: Option<u32>,
   pub rune_index: bool,
   pub runes: u64,
   pub sat_index: bool,
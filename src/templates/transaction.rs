@@ -5,6 +5,9 @@ pub struct TransactionHtml {
   pub chain: Chain,
   pub etching: Option<SpacedRune>,
   pub inscription_count: u32,
+  pub pending: Option<PendingTransaction>,
+  pub runestone: Option<Runestone>,
+  pub runestone_flaws: Vec<Flaw>,
   pub transaction: Transaction,
   pub txid: Txid,
 }
@@ -49,6 +52,9 @@ mod tests {
         chain: Chain::Mainnet,
         etching: None,
         inscription_count: 0,
+        pending: None,
+        runestone: None,
+        runestone_flaws: Vec::new(),
         txid: transaction.txid(),
         transaction,
       }.to_string(),
@@ -87,4 +93,152 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn runestone_flaws() {
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    assert_regex_match!(
+      TransactionHtml {
+        chain: Chain::Mainnet,
+        etching: None,
+        inscription_count: 0,
+        pending: None,
+        runestone: Some(Runestone {
+          burn: true,
+          ..Default::default()
+        }),
+        runestone_flaws: vec![Flaw::UnrecognizedEvenTag(20), Flaw::TruncatedVarint],
+        txid: transaction.txid(),
+        transaction,
+      },
+      "
+        <h1>Transaction <span class=monospace>[[:xdigit:]]{64}</span></h1>
+        <dl>
+          <dt>runestone</dt>
+          <dd>burns input runes</dd>
+          <dt>runestone flaws</dt>
+          <dd>
+            <ul>
+              <li>unrecognized even tag 20</li>
+              <li>payload ends with truncated varint</li>
+            </ul>
+          </dd>
+        </dl>
+        .*
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn pending() {
+    let transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: Vec::new(),
+    };
+
+    let txid = transaction.txid();
+
+    assert_regex_match!(
+      TransactionHtml {
+        chain: Chain::Mainnet,
+        etching: None,
+        inscription_count: 0,
+        pending: Some(PendingTransaction {
+          etching: Some(SpacedRune {
+            rune: Rune(0),
+            spacers: 0,
+          }),
+          inscriptions: vec![InscriptionId { txid, index: 0 }],
+          runes: vec![
+            PendingRuneTransfer {
+              location: Some(OutPoint {
+                txid,
+                vout: 0,
+              }),
+              pile: Pile {
+                amount: 100,
+                divisibility: 0,
+                symbol: None,
+              },
+              rune: SpacedRune {
+                rune: Rune(1),
+                spacers: 0,
+              },
+            },
+            PendingRuneTransfer {
+              location: None,
+              pile: Pile {
+                amount: 5,
+                divisibility: 0,
+                symbol: None,
+              },
+              rune: SpacedRune {
+                rune: Rune(1),
+                spacers: 0,
+              },
+            },
+          ],
+          transfers: vec![
+            PendingTransfer {
+              id: inscription_id(1),
+              location: Some(SatPoint {
+                outpoint: OutPoint { txid, vout: 0 },
+                offset: 0,
+              }),
+              txid,
+            },
+            PendingTransfer {
+              id: inscription_id(2),
+              location: None,
+              txid,
+            },
+          ],
+          txid,
+        }),
+        runestone: None,
+        runestone_flaws: Vec::new(),
+        txid,
+        transaction,
+      },
+      "
+        <h1>Transaction <span class=monospace>[[:xdigit:]]{64}</span></h1>
+        <p>This transaction is unconfirmed. It may yet be replaced or evicted from the mempool.</p>
+        <dl>
+          <dt>pending inscriptions</dt>
+          <dd>
+            <ul>
+              <li><a class=monospace href=/inscription/[[:xdigit:]]{64}i0>[[:xdigit:]]{64}i0</a></li>
+            </ul>
+          </dd>
+          <dt>pending transfers</dt>
+          <dd>
+            <ul>
+              <li><a class=monospace href=/inscription/1{64}i1>1{64}i1</a> to <span class=monospace>[[:xdigit:]]{64}:0:0</span></li>
+              <li><a class=monospace href=/inscription/2{64}i2>2{64}i2</a> to fees or an unknown location</li>
+            </ul>
+          </dd>
+          <dt>pending etching</dt>
+          <dd>A</dd>
+          <dt>pending rune transfers</dt>
+          <dd>
+            <ul>
+              <li><a href=/rune/B>B</a> 100 to <span class=monospace>[[:xdigit:]]{64}:0</span></li>
+              <li><a href=/rune/B>B</a> 5 burned</li>
+            </ul>
+          </dd>
+        </dl>
+        .*
+      "
+      .unindent()
+    );
+  }
 }
@@ -8,6 +8,7 @@ use {
     Network,
   },
   bitcoincore_rpc::bitcoincore_rpc_json::{Descriptor, ImportDescriptors, Timestamp},
+  etching::{CheckedEtching, EtchingParameters},
   fee_rate::FeeRate,
   futures::{
     future::{self, FutureExt},
@@ -19,6 +20,7 @@ use {
   transaction_builder::TransactionBuilder,
 };
 
+pub mod etching;
 pub mod inscribe;
 pub mod transaction_builder;
 
@@ -51,6 +53,7 @@ pub(crate) struct Wallet {
   output_info: BTreeMap<OutPoint, api::Output>,
   inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
   locked_utxos: BTreeMap<OutPoint, TxOut>,
+  rune_count: u64,
   settings: Settings,
 }
 
@@ -187,6 +190,7 @@ impl Wallet {
           ord_client,
           output_info,
           rpc_url,
+          rune_count: status.runes,
           settings,
           utxos,
         })
@@ -475,6 +479,10 @@ impl Wallet {
     self.has_rune_index
   }
 
+  pub(crate) fn rune_count(&self) -> u64 {
+    self.rune_count
+  }
+
   pub(crate) fn chain(&self) -> Chain {
     self.settings.chain()
   }
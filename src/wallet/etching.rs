@@ -0,0 +1,118 @@
+use super::*;
+
+pub(crate) struct EtchingParameters {
+  pub(crate) divisibility: u8,
+  pub(crate) mint_deadline: Option<u32>,
+  pub(crate) mint_limit: Option<Decimal>,
+  pub(crate) mint_term: Option<u32>,
+  pub(crate) open_mint: bool,
+  pub(crate) rune: SpacedRune,
+  pub(crate) supply: Decimal,
+  pub(crate) symbol: char,
+}
+
+pub(crate) struct CheckedEtching {
+  pub(crate) etching: Etching,
+  pub(crate) height: u32,
+  pub(crate) supply: u128,
+  pub(crate) timestamp: u32,
+}
+
+impl EtchingParameters {
+  pub(crate) fn check(&self, wallet: &Wallet) -> Result<CheckedEtching> {
+    let SpacedRune { rune, spacers } = self.rune;
+
+    let bitcoin_client = wallet.bitcoin_client();
+
+    let count = bitcoin_client.get_block_count()?;
+
+    let height = u32::try_from(count).unwrap() + 1;
+
+    ensure!(
+      wallet.get_rune(rune)?.is_none(),
+      "rune `{}` has already been etched",
+      rune,
+    );
+
+    let minimum_at_height = Rune::minimum_at_height(wallet.chain(), Height(height));
+
+    ensure!(
+      rune >= minimum_at_height,
+      "rune is less than minimum for next block: {} < {minimum_at_height}",
+      rune,
+    );
+
+    ensure!(!rune.is_reserved(), "rune `{}` is reserved", rune);
+
+    ensure!(
+      self.divisibility <= crate::runes::MAX_DIVISIBILITY,
+      "<DIVISIBILITY> must be equal to or less than 38"
+    );
+
+    let timestamp = bitcoin_client
+      .get_block_header(&bitcoin_client.get_block_hash(count)?)?
+      .time;
+
+    let mint = if self.open_mint {
+      let limit = self
+        .mint_limit
+        .map(|limit| limit.to_amount(self.divisibility))
+        .transpose()?;
+
+      if let Some(limit) = limit {
+        ensure!(
+          limit <= crate::runes::MAX_LIMIT,
+          "<MINT_LIMIT> must be equal to or less than {}",
+          crate::runes::MAX_LIMIT,
+        );
+      }
+
+      if let Some(term) = self.mint_term {
+        ensure!(term > 0, "<MINT_TERM> must be greater than 0");
+
+        ensure!(
+          height.checked_add(term).is_some(),
+          "<MINT_TERM> {term} would end after the maximum block height",
+        );
+      }
+
+      if let Some(deadline) = self.mint_deadline {
+        // the next block's timestamp isn't known yet, so use the chain tip's
+        ensure!(
+          deadline > timestamp,
+          "<MINT_DEADLINE> {deadline} has already passed",
+        );
+      }
+
+      Some(Mint {
+        deadline: self.mint_deadline,
+        limit,
+        term: self.mint_term,
+      })
+    } else {
+      None
+    };
+
+    let supply = self.supply.to_amount(self.divisibility)?;
+
+    if let Some(mint) = mint {
+      ensure!(
+        supply <= mint.limit.unwrap_or(crate::runes::MAX_LIMIT),
+        "<SUPPLY> must be equal to or less than the mint limit for runes with open mints",
+      );
+    }
+
+    Ok(CheckedEtching {
+      etching: Etching {
+        divisibility: self.divisibility,
+        mint,
+        rune: Some(rune),
+        spacers,
+        symbol: Some(self.symbol),
+      },
+      height,
+      supply,
+      timestamp,
+    })
+  }
+}
@@ -14,10 +14,17 @@ use {
   wallet::transaction_builder::Target,
 };
 
-pub use {batch::Batch, batch_entry::BatchEntry, batch_file::Batchfile, mode::Mode};
+pub use {
+  batch::Batch,
+  batch_entry::BatchEntry,
+  batch_etching::{BatchEtching, BatchMint},
+  batch_file::Batchfile,
+  mode::Mode,
+};
 
 pub mod batch;
 pub mod batch_entry;
+pub mod batch_etching;
 pub mod batch_file;
 pub mod mode;
 
@@ -27,6 +34,12 @@ pub struct InscriptionInfo {
   pub location: SatPoint,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+pub struct EtchedRune {
+  pub location: Option<OutPoint>,
+  pub rune: SpacedRune,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub commit: Txid,
@@ -35,9 +48,17 @@ pub struct Output {
   pub parent: Option<InscriptionId>,
   pub reveal: Txid,
   pub reveal_psbt: Option<String>,
+  pub rune: Option<EtchedRune>,
   pub total_fees: u64,
 }
 
+#[derive(Clone, Debug)]
+pub struct EtchingInfo {
+  pub destination: Address,
+  pub etching: Etching,
+  pub supply: u128,
+}
+
 #[derive(Clone, Debug)]
 pub struct ParentInfo {
   pub destination: Address,
@@ -4,6 +4,7 @@ pub struct Batch {
   pub(crate) commit_fee_rate: FeeRate,
   pub(crate) destinations: Vec<Address>,
   pub(crate) dry_run: bool,
+  pub(crate) etching_info: Option<EtchingInfo>,
   pub(crate) inscriptions: Vec<Inscription>,
   pub(crate) mode: Mode,
   pub(crate) no_backup: bool,
@@ -22,6 +23,7 @@ impl Default for Batch {
       commit_fee_rate: 1.0.try_into().unwrap(),
       destinations: Vec::new(),
       dry_run: false,
+      etching_info: None,
       inscriptions: Vec::new(),
       mode: Mode::SharedOutput,
       no_backup: false,
@@ -207,7 +209,26 @@ impl Batch {
       total_fees,
       parent: self.parent_info.clone().map(|info| info.id),
       inscriptions: inscriptions_output,
+      rune: self.etching_info.as_ref().map(|info| EtchedRune {
+        location: self
+          .premine_vout()
+          .map(|vout| OutPoint { txid: reveal, vout }),
+        rune: SpacedRune {
+          rune: info.etching.rune.unwrap(),
+          spacers: info.etching.spacers,
+        },
+      }),
+    }
+  }
+
+  fn premine_vout(&self) -> Option<u32> {
+    let info = self.etching_info.as_ref()?;
+
+    if info.etching.mint.is_some() && info.supply == 0 {
+      return None;
     }
+
+    Some(u32::try_from(usize::from(self.parent_info.is_some()) + self.destinations.len()).unwrap())
   }
 
   pub(crate) fn create_batch_inscription_transactions(
@@ -377,6 +398,49 @@ impl Batch {
       });
     }
 
+    let mut premine_postage = 0;
+
+    if let Some(info) = &self.etching_info {
+      let edicts = if let Some(vout) = self.premine_vout() {
+        assert_eq!(usize::try_from(vout).unwrap(), reveal_outputs.len());
+
+        premine_postage = TARGET_POSTAGE.to_sat();
+
+        reveal_outputs.push(TxOut {
+          script_pubkey: info.destination.script_pubkey(),
+          value: premine_postage,
+        });
+
+        vec![Edict {
+          amount: info.supply,
+          id: 0,
+          output: vout.into(),
+        }]
+      } else {
+        Vec::new()
+      };
+
+      let script_pubkey = Runestone {
+        etching: Some(info.etching),
+        edicts,
+        default_output: None,
+        burn: false,
+        claim: None,
+      }
+      .encipher();
+
+      ensure!(
+        script_pubkey.len() <= MAX_RUNESTONE_SIZE,
+        "runestone greater than maximum OP_RETURN size: {} > {MAX_RUNESTONE_SIZE}",
+        script_pubkey.len()
+      );
+
+      reveal_outputs.push(TxOut {
+        script_pubkey,
+        value: 0,
+      });
+    }
+
     let commit_input = usize::from(self.parent_info.is_some()) + self.reveal_satpoints.len();
 
     let (_, reveal_fee) = Self::build_reveal_transaction(
@@ -389,9 +453,9 @@ impl Batch {
     );
 
     let target = if self.mode == Mode::SatPoints {
-      Target::Value(reveal_fee)
+      Target::Value(reveal_fee + Amount::from_sat(premine_postage))
     } else {
-      Target::Value(reveal_fee + Amount::from_sat(total_postage))
+      Target::Value(reveal_fee + Amount::from_sat(total_postage + premine_postage))
     };
 
     let unsigned_commit_tx = TransactionBuilder::new(
@@ -0,0 +1,35 @@
+use super::*;
+
+#[derive(Deserialize, PartialEq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BatchEtching {
+  pub(crate) divisibility: u8,
+  pub(crate) mint: Option<BatchMint>,
+  pub(crate) rune: SpacedRune,
+  pub(crate) supply: Decimal,
+  pub(crate) symbol: char,
+}
+
+#[derive(Deserialize, PartialEq, Debug, Clone, Copy, Default)]
+#[serde(deny_unknown_fields)]
+pub struct BatchMint {
+  pub(crate) deadline: Option<u32>,
+  pub(crate) limit: Option<Decimal>,
+  pub(crate) term: Option<u32>,
+}
+
+impl BatchEtching {
+  pub(crate) fn etching(&self, wallet: &Wallet) -> Result<CheckedEtching> {
+    EtchingParameters {
+      divisibility: self.divisibility,
+      mint_deadline: self.mint.and_then(|mint| mint.deadline),
+      mint_limit: self.mint.and_then(|mint| mint.limit),
+      mint_term: self.mint.and_then(|mint| mint.term),
+      open_mint: self.mint.is_some(),
+      rune: self.rune,
+      supply: self.supply,
+      symbol: self.symbol,
+    }
+    .check(wallet)
+  }
+}
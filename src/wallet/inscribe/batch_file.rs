@@ -3,6 +3,7 @@ use super::*;
 #[derive(Deserialize, PartialEq, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Batchfile {
+  pub(crate) etching: Option<BatchEtching>,
   pub(crate) inscriptions: Vec<BatchEntry>,
   pub(crate) mode: Mode,
   pub(crate) parent: Option<InscriptionId>,
@@ -195,6 +196,44 @@ impl Batchfile {
 mod tests {
   use super::*;
 
+  #[test]
+  fn batchfile_with_etching() {
+    let tempdir = tempfile::TempDir::new().unwrap();
+    let batch_file = tempdir.path().join("batch.yaml");
+    fs::write(
+      batch_file.clone(),
+      r#"
+mode: separate-outputs
+etching:
+  divisibility: 2
+  rune: A•BC
+  supply: 1000.5
+  symbol: $
+  mint:
+    limit: 10
+    term: 100
+inscriptions:
+- file: logo.png
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      Batchfile::load(batch_file.as_path()).unwrap().etching,
+      Some(BatchEtching {
+        divisibility: 2,
+        mint: Some(BatchMint {
+          deadline: None,
+          limit: Some("10".parse().unwrap()),
+          term: Some(100),
+        }),
+        rune: "A•BC".parse().unwrap(),
+        supply: "1000.5".parse().unwrap(),
+        symbol: '$',
+      }),
+    );
+  }
+
   #[test]
   fn batchfile_not_sat_and_satpoint() {
     let tempdir = tempfile::TempDir::new().unwrap();
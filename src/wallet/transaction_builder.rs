@@ -20,6 +20,11 @@
 //! `Target::Value(Amount)` ensures that the outgoing value is exactly the
 //! requested amount,
 //!
+//! Runic UTXOs are never used to pad or fund transactions unless
+//! `TransactionBuilder::spend_runic_utxos` is set, in which case they are
+//! selected only once cardinal UTXOs are exhausted. Their runes are sent to a
+//! change output by a runestone whose default output is that change output.
+//!
 //! Internally, `TransactionBuilder` calls multiple methods that implement
 //! transformations responsible for individual concerns, such as ensuring that
 //! the transaction fee is paid, and that outgoing outputs aren't too large.
@@ -33,6 +38,7 @@
 
 use {
   super::*,
+  crate::runes::Report,
   std::cmp::{max, min},
 };
 
@@ -103,6 +109,7 @@ pub struct TransactionBuilder {
   outputs: Vec<(Address, Amount)>,
   recipient: Address,
   runic_utxos: BTreeSet<OutPoint>,
+  spend_runic_utxos: bool,
   target: Target,
   unused_change_addresses: Vec<Address>,
   utxos: BTreeSet<OutPoint>,
@@ -139,11 +146,17 @@ impl TransactionBuilder {
       outputs: Vec::new(),
       recipient,
       runic_utxos,
+      spend_runic_utxos: false,
       target,
       unused_change_addresses: change.to_vec(),
     }
   }
 
+  pub fn spend_runic_utxos(mut self, spend_runic_utxos: bool) -> Self {
+    self.spend_runic_utxos = spend_runic_utxos;
+    self
+  }
+
   pub fn build_transaction(self) -> Result<Transaction> {
     if self.change_addresses.len() < 2 {
       return Err(Error::DuplicateAddress(
@@ -174,7 +187,7 @@ impl TransactionBuilder {
       .align_outgoing()
       .pad_alignment_output()?
       .add_value()?
-      .strip_value()
+      .strip_value()?
       .deduct_fee()
       .build()
   }
@@ -308,7 +321,25 @@ impl TransactionBuilder {
           .checked_add(additional_fee)
           .ok_or(Error::ValueOverflow)?;
 
-        let (utxo, value) = self.select_cardinal_utxo(needed.to_sat(), false)?;
+        let (utxo, value) = match self.select_cardinal_utxo(needed.to_sat(), false) {
+          Err(Error::NotEnoughCardinalUtxos) if self.spend_runic_utxos => {
+            if !self.spends_runes() {
+              // reserve value for the runestone and the change output that
+              // receives the runes
+              deficit = deficit
+                .checked_add(self.rune_change_cost())
+                .ok_or(Error::ValueOverflow)?;
+            }
+
+            self.select_runic_utxo(
+              deficit
+                .checked_add(additional_fee)
+                .ok_or(Error::ValueOverflow)?
+                .to_sat(),
+            )?
+          }
+          result => result?,
+        };
 
         let benefit = value
           .checked_sub(additional_fee)
@@ -331,7 +362,7 @@ impl TransactionBuilder {
     Ok(self)
   }
 
-  fn strip_value(mut self) -> Self {
+  fn strip_value(mut self) -> Result<Self> {
     let sat_offset = self.calculate_sat_offset();
 
     let total_output_amount = self
@@ -348,6 +379,10 @@ impl TransactionBuilder {
 
     let value = total_output_amount - Amount::from_sat(sat_offset);
 
+    if self.spends_runes() {
+      return self.strip_rune_change(value);
+    }
+
     if let Some(excess) = value.checked_sub(self.fee_rate.fee(self.estimate_vbytes())) {
       let (max, target) = match self.target {
         Target::ExactPostage(postage) => (postage, postage),
@@ -379,7 +414,43 @@ impl TransactionBuilder {
       }
     }
 
-    self
+    Ok(self)
+  }
+
+  fn strip_rune_change(mut self, value: Amount) -> Result<Self> {
+    let change = self
+      .unused_change_addresses
+      .pop()
+      .expect("not enough change addresses");
+
+    let change_cost = change.script_pubkey().dust_value()
+      + self
+        .fee_rate
+        .fee(self.estimate_vbytes() + Self::ADDITIONAL_OUTPUT_VBYTES);
+
+    let available = value
+      .checked_sub(change_cost)
+      .ok_or(Error::NotEnoughCardinalUtxos)?;
+
+    let target = match self.target {
+      Target::ExactPostage(postage) => postage,
+      Target::Postage => min(TARGET_POSTAGE, available),
+      Target::Value(value) => value,
+    };
+
+    if target > available || target < self.recipient.script_pubkey().dust_value() {
+      return Err(Error::NotEnoughCardinalUtxos);
+    }
+
+    tprintln!(
+      "stripped {} sats into rune change",
+      (value - target).to_sat()
+    );
+
+    self.outputs.last_mut().expect("no outputs found").1 = target;
+    self.outputs.push((change, value - target));
+
+    Ok(self)
   }
 
   fn deduct_fee(mut self) -> Self {
@@ -428,7 +499,45 @@ impl TransactionBuilder {
         .map(|(address, _amount)| address)
         .cloned()
         .collect(),
-    )
+    ) + if self.spends_runes() {
+      Self::runestone_vbytes()
+    } else {
+      0
+    }
+  }
+
+  fn spends_runes(&self) -> bool {
+    self
+      .inputs
+      .iter()
+      .any(|input| self.runic_utxos.contains(input))
+  }
+
+  fn runestone(rune_change: usize) -> TxOut {
+    TxOut {
+      value: 0,
+      script_pubkey: Runestone {
+        default_output: Some(rune_change.try_into().unwrap()),
+        ..Default::default()
+      }
+      .encipher(),
+    }
+  }
+
+  fn runestone_vbytes() -> usize {
+    consensus::encode::serialize(&Self::runestone(0)).len()
+  }
+
+  fn rune_change_cost(&self) -> Amount {
+    self
+      .unused_change_addresses
+      .last()
+      .unwrap()
+      .script_pubkey()
+      .dust_value()
+      + self
+        .fee_rate
+        .fee(Self::ADDITIONAL_OUTPUT_VBYTES + Self::runestone_vbytes())
   }
 
   fn estimate_vbytes_with(inputs: usize, outputs: Vec<Address>) -> usize {
@@ -480,6 +589,11 @@ impl TransactionBuilder {
           value: amount.to_sat(),
           script_pubkey: address.script_pubkey(),
         })
+        .chain(
+          self
+            .spends_runes()
+            .then(|| Self::runestone(self.outputs.len() - 1)),
+        )
         .collect(),
     };
 
@@ -591,7 +705,7 @@ impl TransactionBuilder {
           offset, sat_offset,
           "invariant: sat is at first position in recipient output"
         );
-      } else {
+      } else if !output.script_pubkey.is_op_return() {
         assert!(
           self
             .change_addresses
@@ -624,12 +738,61 @@ impl TransactionBuilder {
     );
 
     for tx_out in &transaction.output {
+      if tx_out.script_pubkey.is_op_return() {
+        continue;
+      }
+
       assert!(
         Amount::from_sat(tx_out.value) >= tx_out.script_pubkey.dust_value(),
         "invariant: all outputs are above dust limit",
       );
     }
 
+    assert_eq!(
+      transaction
+        .output
+        .iter()
+        .filter(|tx_out| tx_out.script_pubkey.is_op_return())
+        .count(),
+      usize::from(self.spends_runes()),
+      "invariant: transaction contains a runestone if and only if it spends runes",
+    );
+
+    if self.spends_runes() {
+      assert!(
+        self.spend_runic_utxos || self.runic_utxos.contains(&self.outgoing.outpoint),
+        "invariant: runic utxos are only spent when allowed",
+      );
+
+      let Report { flaws, runestone } = Runestone::diagnose(&transaction);
+
+      assert!(flaws.is_empty(), "invariant: runestone has no flaws");
+
+      let runestone = runestone.expect("invariant: runestone is valid");
+
+      assert!(
+        !runestone.burn
+          && runestone.edicts.is_empty()
+          && runestone.claim.is_none()
+          && runestone.etching.is_none(),
+        "invariant: runestone only sets default output",
+      );
+
+      let rune_change = runestone
+        .default_output
+        .and_then(|vout| usize::try_from(vout).ok())
+        .and_then(|vout| transaction.output.get(vout))
+        .expect("invariant: runestone default output exists");
+
+      assert!(
+        self
+          .change_addresses
+          .iter()
+          .any(|change_address| change_address.script_pubkey() == rune_change.script_pubkey),
+        "invariant: runes are sent to change output",
+      );
+    }
+
     Ok(transaction)
   }
 
@@ -661,6 +824,21 @@ impl TransactionBuilder {
       if prefer_under { "smaller" } else { "bigger" }
     );
 
+    self.select_utxo(target_value, prefer_under, false)
+  }
+
+  fn select_runic_utxo(&mut self, target_value: u64) -> Result<(OutPoint, Amount)> {
+    tprintln!("looking for runic worth {target_value}");
+
+    self.select_utxo(target_value, false, true)
+  }
+
+  fn select_utxo(
+    &mut self,
+    target_value: u64,
+    prefer_under: bool,
+    runic: bool,
+  ) -> Result<(OutPoint, Amount)> {
     let inscribed_utxos = self
       .inscriptions
       .keys()
@@ -669,7 +847,7 @@ impl TransactionBuilder {
 
     let mut best_match = None;
     for utxo in &self.utxos {
-      if self.runic_utxos.contains(utxo)
+      if self.runic_utxos.contains(utxo) != runic
         || inscribed_utxos.contains(utxo)
         || self.locked_utxos.contains(utxo)
       {
@@ -715,7 +893,11 @@ impl TransactionBuilder {
     let (utxo, value) = best_match.ok_or(Error::NotEnoughCardinalUtxos)?;
 
     self.utxos.remove(&utxo);
-    tprintln!("found cardinal worth {}", value);
+    tprintln!(
+      "found {} worth {}",
+      if runic { "runic" } else { "cardinal" },
+      value
+    );
 
     Ok((utxo, Amount::from_sat(value)))
   }
@@ -777,6 +959,7 @@ mod tests {
       inscriptions: BTreeMap::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      spend_runic_utxos: false,
       recipient: recipient(),
       unused_change_addresses: vec![change(0), change(1)],
       change_addresses: vec![change(0), change(1)].into_iter().collect(),
@@ -870,6 +1053,7 @@ mod tests {
     .unwrap()
     .align_outgoing()
     .strip_value()
+    .unwrap()
     .deduct_fee();
   }
 
@@ -1219,6 +1403,7 @@ mod tests {
     .add_value()
     .unwrap()
     .strip_value()
+    .unwrap()
     .deduct_fee();
 
     builder.change_addresses = BTreeSet::new();
@@ -1248,6 +1433,7 @@ mod tests {
     .add_value()
     .unwrap()
     .strip_value()
+    .unwrap()
     .deduct_fee()
     .build()
     .unwrap();
@@ -1272,6 +1458,7 @@ mod tests {
     .select_outgoing()
     .unwrap()
     .strip_value()
+    .unwrap()
     .deduct_fee()
     .build()
     .unwrap();
@@ -1296,6 +1483,7 @@ mod tests {
     .select_outgoing()
     .unwrap()
     .strip_value()
+    .unwrap()
     .build()
     .unwrap();
   }
@@ -1314,6 +1502,7 @@ mod tests {
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      spend_runic_utxos: false,
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
@@ -1345,6 +1534,7 @@ mod tests {
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      spend_runic_utxos: false,
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
@@ -1410,6 +1600,156 @@ mod tests {
     )
   }
 
+  #[test]
+  fn select_runic_utxos_when_allowed() {
+    let utxos = vec![
+      (outpoint(1), tx_out(100, address())),
+      (outpoint(2), tx_out(49 * COIN_VALUE, address())),
+    ];
+
+    let transaction = TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      utxos.into_iter().collect(),
+      BTreeSet::new(),
+      vec![outpoint(2)].into_iter().collect(),
+      recipient(),
+      [change(0), change(1)],
+      FeeRate::try_from(1.0).unwrap(),
+      Target::Postage,
+    )
+    .spend_runic_utxos(true)
+    .build_transaction()
+    .unwrap();
+
+    assert_eq!(
+      transaction
+        .input
+        .iter()
+        .map(|input| input.previous_output)
+        .collect::<Vec<OutPoint>>(),
+      [outpoint(1), outpoint(2)],
+    );
+
+    assert_eq!(transaction.output.len(), 3);
+    assert_eq!(
+      transaction.output[0].script_pubkey,
+      recipient().script_pubkey()
+    );
+    assert_eq!(transaction.output[0].value, TARGET_POSTAGE.to_sat());
+    assert_eq!(
+      transaction.output[1].script_pubkey,
+      change(1).script_pubkey()
+    );
+    assert_eq!(transaction.output[2].value, 0);
+
+    assert_eq!(
+      Runestone::diagnose(&transaction).runestone,
+      Some(Runestone {
+        default_output: Some(1),
+        ..Default::default()
+      }),
+    );
+  }
+
+  #[test]
+  fn prefer_cardinal_utxos_over_runic_utxos() {
+    let utxos = vec![
+      (outpoint(1), tx_out(100, address())),
+      (outpoint(2), tx_out(49 * COIN_VALUE, address())),
+      (outpoint(3), tx_out(20_000, address())),
+    ];
+
+    let transaction = TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      utxos.into_iter().collect(),
+      BTreeSet::new(),
+      vec![outpoint(2)].into_iter().collect(),
+      recipient(),
+      [change(0), change(1)],
+      FeeRate::try_from(1.0).unwrap(),
+      Target::Postage,
+    )
+    .spend_runic_utxos(true)
+    .build_transaction()
+    .unwrap();
+
+    assert_eq!(
+      transaction
+        .input
+        .iter()
+        .map(|input| input.previous_output)
+        .collect::<Vec<OutPoint>>(),
+      [outpoint(1), outpoint(3)],
+    );
+
+    assert!(transaction
+      .output
+      .iter()
+      .all(|output| !output.script_pubkey.is_op_return()));
+  }
+
+  #[test]
+  fn runic_utxos_can_fund_exact_value() {
+    let utxos = vec![
+      (outpoint(1), tx_out(100, address())),
+      (outpoint(2), tx_out(49 * COIN_VALUE, address())),
+    ];
+
+    let transaction = TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      utxos.into_iter().collect(),
+      BTreeSet::new(),
+      vec![outpoint(2)].into_iter().collect(),
+      recipient(),
+      [change(0), change(1)],
+      FeeRate::try_from(1.0).unwrap(),
+      Target::Value(Amount::from_sat(1_000_000)),
+    )
+    .spend_runic_utxos(true)
+    .build_transaction()
+    .unwrap();
+
+    assert_eq!(transaction.output[0].value, 1_000_000);
+    assert_eq!(
+      Runestone::diagnose(&transaction)
+        .runestone
+        .unwrap()
+        .default_output,
+      Some(1),
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "invariant: runes are sent to change output")]
+  fn invariant_runes_are_sent_to_change_output() {
+    let utxos = vec![
+      (outpoint(1), tx_out(10_000, address())),
+      (outpoint(2), tx_out(20_000, address())),
+    ];
+
+    TransactionBuilder {
+      amounts: utxos.into_iter().collect(),
+      fee_rate: FeeRate::try_from(1.0).unwrap(),
+      utxos: BTreeSet::new(),
+      locked_utxos: BTreeSet::new(),
+      runic_utxos: vec![outpoint(2)].into_iter().collect(),
+      spend_runic_utxos: true,
+      outgoing: satpoint(1, 0),
+      inscriptions: BTreeMap::new(),
+      recipient: recipient(),
+      unused_change_addresses: vec![change(0), change(1)],
+      change_addresses: vec![change(0), change(1)].into_iter().collect(),
+      inputs: vec![outpoint(1), outpoint(2)],
+      outputs: vec![(recipient(), Amount::from_sat(29_820))],
+      target: Target::Value(Amount::from_sat(29_820)),
+    }
+    .build()
+    .unwrap();
+  }
+
   #[test]
   fn do_not_send_two_inscriptions_at_once() {
     let utxos = vec![(outpoint(1), tx_out(1_000, address()))];
@@ -0,0 +1,752 @@
+use {
+  super::*,
+  crate::index::event::{Event, WebhookEvent},
+  bitcoin::hashes::{
+    hmac::{Hmac, HmacEngine},
+    sha256, HashEngine,
+  },
+  reqwest::{blocking::Client as HttpClient, header},
+};
+
+const BATCH_SIZE: usize = 100;
+const MAX_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Webhook {
+  pub(crate) addresses: Vec<Address<NetworkUnchecked>>,
+  pub(crate) inscription_ids: Vec<InscriptionId>,
+  pub(crate) kinds: Vec<String>,
+  pub(crate) parents: Vec<InscriptionId>,
+  pub(crate) runes: Vec<SpacedRune>,
+  pub(crate) secret: Option<String>,
+  pub(crate) url: String,
+}
+
+struct Subscription {
+  inscription_ids: HashSet<InscriptionId>,
+  kinds: HashSet<String>,
+  parents: HashSet<InscriptionId>,
+  rune_ids: HashSet<RuneId>,
+  runes: Vec<SpacedRune>,
+  script_pubkeys: HashSet<ScriptBuf>,
+  secret: Option<String>,
+  url: String,
+}
+
+impl Subscription {
+  fn is_filtered(&self) -> bool {
+    !(self.inscription_ids.is_empty()
+      && self.parents.is_empty()
+      && self.runes.is_empty()
+      && self.script_pubkeys.is_empty())
+  }
+
+  fn pays_to(&self, event: &WebhookEvent) -> bool {
+    event
+      .script_pubkeys
+      .iter()
+      .any(|script_pubkey| self.script_pubkeys.contains(script_pubkey))
+  }
+
+  fn matches(&self, webhook_event: &WebhookEvent) -> bool {
+    let event = &webhook_event.event;
+
+    if !self.kinds.is_empty() && !self.kinds.contains(event.kind()) {
+      return false;
+    }
+
+    if !self.is_filtered() {
+      return true;
+    }
+
+    match event {
+      Event::BlockCommitted { .. } | Event::ReorgRolledBack { .. } => true,
+      Event::InscriptionCreated {
+        inscription_id,
+        parent_inscription_id,
+        ..
+      } => {
+        self.inscription_ids.contains(inscription_id)
+          || parent_inscription_id.map_or(false, |parent| self.parents.contains(&parent))
+          || self.pays_to(webhook_event)
+      }
+      Event::InscriptionTransferred { inscription_id, .. } => {
+        self.inscription_ids.contains(inscription_id) || self.pays_to(webhook_event)
+      }
+      Event::RuneBurned { rune_id, .. }
+      | Event::RuneEtched { rune_id, .. }
+      | Event::RuneMinted { rune_id, .. } => self.rune_ids.contains(rune_id),
+      Event::RuneTransferred { rune_id, .. } => {
+        self.rune_ids.contains(rune_id) || self.pays_to(webhook_event)
+      }
+    }
+  }
+}
+
+pub(crate) struct Dispatcher {
+  client: HttpClient,
+  retry_delay: Duration,
+  subscriptions: Vec<Subscription>,
+}
+
+impl Dispatcher {
+  pub(crate) fn new(webhooks: &[Webhook], chain: Chain, index_addresses: bool) -> Result<Self> {
+    let mut urls = HashSet::new();
+    let mut subscriptions = Vec::new();
+
+    for webhook in webhooks {
+      webhook
+        .url
+        .parse::<Url>()
+        .with_context(|| format!("invalid webhook url `{}`", webhook.url))?;
+
+      ensure!(
+        urls.insert(webhook.url.clone()),
+        "duplicate webhook url `{}`",
+        webhook.url
+      );
+
+      ensure!(
+        webhook.addresses.is_empty() || index_addresses,
+        "webhook `{}` address filter requires index created with `--index-addresses` flag",
+        webhook.url
+      );
+
+      for kind in &webhook.kinds {
+        ensure!(
+          Event::KINDS.contains(&kind.as_str()),
+          "unknown event kind `{kind}` for webhook `{}`",
+          webhook.url
+        );
+      }
+
+      subscriptions.push(Subscription {
+        inscription_ids: webhook.inscription_ids.iter().copied().collect(),
+        kinds: webhook.kinds.iter().cloned().collect(),
+        parents: webhook.parents.iter().copied().collect(),
+        rune_ids: HashSet::new(),
+        runes: webhook.runes.clone(),
+        script_pubkeys: webhook
+          .addresses
+          .iter()
+          .map(|address| {
+            Ok(
+              address
+                .clone()
+                .require_network(chain.network())?
+                .script_pubkey(),
+            )
+          })
+          .collect::<Result<HashSet<ScriptBuf>>>()?,
+        secret: webhook.secret.clone(),
+        url: webhook.url.clone(),
+      });
+    }
+
+    Ok(Self {
+      client: HttpClient::builder()
+        .timeout(Duration::from_secs(30))
+        .build()?,
+      retry_delay: Duration::from_millis(250),
+      subscriptions,
+    })
+  }
+
+  pub(crate) fn dispatch(&mut self, index: &Index) -> Result {
+    for i in 0..self.subscriptions.len() {
+      self.resolve_runes(index, i)?;
+
+      let subscription = &self.subscriptions[i];
+      let url = &subscription.url;
+
+      let mut cursor = index.webhook_cursor(url)?;
+
+      loop {
+        let events = index.webhook_events(cursor, BATCH_SIZE)?;
+
+        if events.is_empty() {
+          break;
+        }
+
+        let start = cursor;
+        let mut failed = false;
+
+        for (sequence_number, event) in events {
+          if subscription.matches(&event)
+            && !self.deliver(subscription, sequence_number, &event.event)
+          {
+            failed = true;
+            break;
+          }
+
+          cursor = sequence_number + 1;
+        }
+
+        // the cursor is persisted once per batch, so a crash may cause the
+        // rest of the batch to be delivered again
+        if cursor != start {
+          index.set_webhook_cursor(url, cursor)?;
+        }
+
+        if failed {
+          break;
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  fn resolve_runes(&mut self, index: &Index, i: usize) -> Result {
+    let subscription = &mut self.subscriptions[i];
+
+    if subscription.rune_ids.len() == subscription.runes.len() {
+      return Ok(());
+    }
+
+    for spaced_rune in &subscription.runes {
+      if let Some((rune_id, _entry, _parent)) = index.rune(spaced_rune.rune)? {
+        subscription.rune_ids.insert(rune_id);
+      }
+    }
+
+    Ok(())
+  }
+
+  fn deliver(&self, subscription: &Subscription, sequence_number: u64, event: &Event) -> bool {
+    let body = serde_json::to_vec(event).unwrap();
+
+    let mut delay = self.retry_delay;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+      let mut request = self
+        .client
+        .post(&subscription.url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("X-Ord-Event-Sequence", sequence_number.to_string());
+
+      if let Some(secret) = &subscription.secret {
+        request = request.header(
+          "X-Ord-Signature",
+          format!("sha256={}", signature(secret, &body)),
+        );
+      }
+
+      match request.body(body.clone()).send() {
+        Ok(response) if response.status().is_success() => return true,
+        Ok(response) => log::warn!(
+          "webhook `{}` responded with {} to event {sequence_number} (attempt {attempt}/{MAX_ATTEMPTS})",
+          subscription.url,
+          response.status(),
+        ),
+        Err(err) => log::warn!(
+          "failed to deliver event {sequence_number} to webhook `{}` (attempt {attempt}/{MAX_ATTEMPTS}): {err}",
+          subscription.url,
+        ),
+      }
+
+      if attempt < MAX_ATTEMPTS {
+        thread::sleep(delay);
+        delay *= 2;
+      }
+    }
+
+    false
+  }
+}
+
+fn signature(secret: &str, body: &[u8]) -> String {
+  let mut engine = HmacEngine::<sha256::Hash>::new(secret.as_bytes());
+  engine.input(body);
+  Hmac::<sha256::Hash>::from_engine(engine).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    crate::index::{testing::Context, Statistic},
+    std::{
+      io::{BufRead, BufReader, Write},
+      net::TcpListener,
+    },
+  };
+
+  #[derive(Debug)]
+  struct Request {
+    body: Vec<u8>,
+    headers: BTreeMap<String, String>,
+  }
+
+  impl Request {
+    fn event(&self) -> Event {
+      serde_json::from_slice(&self.body).unwrap()
+    }
+  }
+
+  struct Receiver {
+    requests: Arc<Mutex<Vec<Request>>>,
+    statuses: Arc<Mutex<VecDeque<u16>>>,
+    url: String,
+  }
+
+  impl Receiver {
+    fn new() -> Self {
+      let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+      let url = format!("http://{}/events", listener.local_addr().unwrap());
+
+      let requests = Arc::new(Mutex::new(Vec::new()));
+      let statuses = Arc::new(Mutex::new(VecDeque::new()));
+
+      {
+        let requests = requests.clone();
+        let statuses = statuses.clone();
+        thread::spawn(move || {
+          for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+
+            let mut headers = BTreeMap::new();
+            loop {
+              let mut line = String::new();
+              reader.read_line(&mut line).unwrap();
+              let line = line.trim_end();
+              if line.is_empty() {
+                break;
+              }
+              let (name, value) = line.split_once(':').unwrap();
+              headers.insert(name.to_lowercase(), value.trim().to_string());
+            }
+
+            let mut body = vec![
+              0;
+              headers
+                .get("content-length")
+                .map(|length| length.parse().unwrap())
+                .unwrap_or_default()
+            ];
+            reader.read_exact(&mut body).unwrap();
+
+            // ignore stray connections from other tests that reuse the port
+            let status = if line.starts_with("POST /events ") {
+              statuses.lock().unwrap().pop_front().unwrap_or(200)
+            } else {
+              404
+            };
+
+            if status == 200 {
+              requests.lock().unwrap().push(Request { body, headers });
+            }
+
+            write!(
+              stream,
+              "HTTP/1.1 {status} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            )
+            .unwrap();
+          }
+        });
+      }
+
+      Self {
+        requests,
+        statuses,
+        url,
+      }
+    }
+
+    fn events(&self) -> Vec<Event> {
+      self
+        .requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(Request::event)
+        .collect()
+    }
+
+    fn fail(&self, n: usize) {
+      self.statuses.lock().unwrap().extend(vec![500; n]);
+    }
+  }
+
+  fn context(webhooks: &[Webhook]) -> Context {
+    Context::builder()
+      .arg("--index-addresses")
+      .config(
+        &serde_yaml::to_string(&serde_json::json!({
+          "webhooks": webhooks,
+        }))
+        .unwrap(),
+      )
+      .build()
+  }
+
+  fn dispatcher(webhooks: &[Webhook]) -> Dispatcher {
+    let mut dispatcher = Dispatcher::new(webhooks, Chain::Regtest, true).unwrap();
+    dispatcher.retry_delay = Duration::from_millis(1);
+    dispatcher
+  }
+
+  #[test]
+  fn signature_is_hmac_sha256() {
+    assert_eq!(
+      signature("key", b"The quick brown fox jumps over the lazy dog"),
+      "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+    );
+  }
+
+  #[test]
+  fn invalid_webhooks_are_rejected() {
+    assert_eq!(
+      Dispatcher::new(
+        &[Webhook {
+          url: "foo".into(),
+          ..Default::default()
+        }],
+        Chain::Regtest,
+        true,
+      )
+      .err()
+      .unwrap()
+      .to_string(),
+      "invalid webhook url `foo`",
+    );
+
+    assert_eq!(
+      Dispatcher::new(
+        &[
+          Webhook {
+            url: "http://example.com".into(),
+            ..Default::default()
+          },
+          Webhook {
+            url: "http://example.com".into(),
+            ..Default::default()
+          }
+        ],
+        Chain::Regtest,
+        true,
+      )
+      .err()
+      .unwrap()
+      .to_string(),
+      "duplicate webhook url `http://example.com`",
+    );
+
+    assert_eq!(
+      Dispatcher::new(
+        &[Webhook {
+          url: "http://example.com".into(),
+          kinds: vec!["foo".into()],
+          ..Default::default()
+        }],
+        Chain::Regtest,
+        true,
+      )
+      .err()
+      .unwrap()
+      .to_string(),
+      "unknown event kind `foo` for webhook `http://example.com`",
+    );
+
+    assert!(Dispatcher::new(
+      &[Webhook {
+        url: "http://example.com".into(),
+        addresses: vec!["bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"
+          .parse()
+          .unwrap()],
+        ..Default::default()
+      }],
+      Chain::Regtest,
+      true,
+    )
+    .is_err());
+
+    assert_eq!(
+      Dispatcher::new(
+        &[Webhook {
+          url: "http://example.com".into(),
+          addresses: vec!["bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+            .parse()
+            .unwrap()],
+          ..Default::default()
+        }],
+        Chain::Regtest,
+        false,
+      )
+      .err()
+      .unwrap()
+      .to_string(),
+      "webhook `http://example.com` address filter requires index created with `--index-addresses` flag",
+    );
+  }
+
+  #[test]
+  fn events_are_delivered_once_with_signature() {
+    let receiver = Receiver::new();
+
+    let webhooks = [Webhook {
+      url: receiver.url.clone(),
+      secret: Some("secret".into()),
+      ..Default::default()
+    }];
+
+    let context = context(&webhooks);
+
+    context.mine_blocks(1);
+
+    let commits = context.index.statistic(Statistic::Commits);
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    assert_eq!(
+      receiver.events(),
+      [
+        Event::BlockCommitted { block_height: 0 },
+        Event::BlockCommitted { block_height: 1 },
+      ]
+    );
+
+    assert_eq!(context.index.statistic(Statistic::Commits), commits);
+
+    for (i, request) in receiver.requests.lock().unwrap().iter().enumerate() {
+      assert_eq!(request.headers["content-type"], "application/json");
+      assert_eq!(request.headers["x-ord-event-sequence"], i.to_string());
+      assert_eq!(
+        request.headers["x-ord-signature"],
+        format!("sha256={}", signature("secret", &request.body)),
+      );
+    }
+
+    assert_eq!(context.index.webhook_cursor(&receiver.url).unwrap(), 2);
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    assert_eq!(receiver.events().len(), 2);
+
+    context.mine_blocks(1);
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    assert_eq!(
+      receiver.events(),
+      [
+        Event::BlockCommitted { block_height: 0 },
+        Event::BlockCommitted { block_height: 1 },
+        Event::BlockCommitted { block_height: 2 },
+      ]
+    );
+  }
+
+  #[test]
+  fn delivered_events_are_pruned() {
+    let receiver = Receiver::new();
+
+    let webhooks = [Webhook {
+      url: receiver.url.clone(),
+      ..Default::default()
+    }];
+
+    let context = context(&webhooks);
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.webhook_events(0, 100).unwrap().len(), 2);
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    assert_eq!(context.index.webhook_events(0, 100).unwrap(), []);
+  }
+
+  #[test]
+  fn failed_deliveries_are_retried() {
+    let receiver = Receiver::new();
+
+    let webhooks = [Webhook {
+      url: receiver.url.clone(),
+      ..Default::default()
+    }];
+
+    let context = context(&webhooks);
+
+    receiver.fail(usize::try_from(MAX_ATTEMPTS).unwrap() - 1);
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    assert_eq!(
+      receiver.events(),
+      [Event::BlockCommitted { block_height: 0 }]
+    );
+
+    context.mine_blocks(1);
+
+    receiver.fail(usize::try_from(MAX_ATTEMPTS).unwrap());
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    assert_eq!(receiver.events().len(), 1);
+    assert_eq!(context.index.webhook_cursor(&receiver.url).unwrap(), 1);
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    assert_eq!(
+      receiver.events(),
+      [
+        Event::BlockCommitted { block_height: 0 },
+        Event::BlockCommitted { block_height: 1 },
+      ]
+    );
+  }
+
+  #[test]
+  fn events_are_filtered_by_kind_and_inscription() {
+    let receiver = Receiver::new();
+
+    let mut webhooks = [Webhook {
+      url: receiver.url.clone(),
+      kinds: vec!["inscription_created".into()],
+      ..Default::default()
+    }];
+
+    let context = context(&webhooks);
+
+    context.mine_blocks(2);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    webhooks[0].inscription_ids = vec![inscription_id];
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    let events = receiver.events();
+
+    assert_eq!(events.len(), 1);
+
+    let Event::InscriptionCreated {
+      inscription_id: id, ..
+    } = events[0]
+    else {
+      panic!("unexpected event: {:?}", events[0]);
+    };
+
+    assert_eq!(id, inscription_id);
+  }
+
+  #[test]
+  fn events_are_filtered_by_address() {
+    let receiver = Receiver::new();
+
+    let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap();
+
+    let webhooks = [Webhook {
+      url: receiver.url.clone(),
+      kinds: vec!["inscription_created".into()],
+      addresses: vec![address.clone()],
+      ..Default::default()
+    }];
+
+    let context = context(&webhooks);
+
+    context.mine_blocks(2);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      recipient: Some(address.assume_checked()),
+      ..Default::default()
+    });
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    let events = receiver.events();
+
+    assert_eq!(events.len(), 1);
+
+    let Event::InscriptionCreated { inscription_id, .. } = events[0] else {
+      panic!("unexpected event: {:?}", events[0]);
+    };
+
+    assert_eq!(inscription_id, InscriptionId { txid, index: 0 });
+  }
+
+  #[test]
+  fn transfers_from_spent_outputs_are_filtered_by_address() {
+    let receiver = Receiver::new();
+
+    let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap();
+
+    let webhooks = [Webhook {
+      url: receiver.url.clone(),
+      kinds: vec!["inscription_transferred".into()],
+      addresses: vec![address.clone()],
+      ..Default::default()
+    }];
+
+    let context = context(&webhooks);
+
+    context.mine_blocks(1);
+
+    let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      recipient: Some(address.clone().assume_checked()),
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      ..Default::default()
+    });
+
+    context.mine_blocks(1);
+
+    // the transfer spent the only output paying to the address before any
+    // event was delivered
+    assert_eq!(
+      context
+        .index
+        .get_output_address(OutPoint { txid, vout: 0 })
+        .unwrap(),
+      None
+    );
+
+    dispatcher(&webhooks).dispatch(&context.index).unwrap();
+
+    let events = receiver.events();
+
+    assert_eq!(events.len(), 1);
+
+    let Event::InscriptionTransferred { inscription_id, .. } = events[0] else {
+      panic!("unexpected event: {:?}", events[0]);
+    };
+
+    assert_eq!(inscription_id, InscriptionId { txid, index: 0 });
+  }
+}
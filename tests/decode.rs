@@ -2,11 +2,11 @@ use {
   super::*,
   bitcoin::{
     absolute::LockTime, consensus::Encodable, opcodes, script, ScriptBuf, Sequence, Transaction,
-    TxIn, Witness,
+    TxIn, TxOut, Witness,
   },
   ord::{
     subcommand::decode::{CompactInscription, CompactOutput, RawOutput},
-    Envelope, Inscription,
+    Edict, Envelope, Flaw, Inscription, Runestone,
   },
 };
 
@@ -53,6 +53,7 @@ fn from_file() {
       .write("transaction.bin", transaction())
       .run_and_deserialize_output::<RawOutput>(),
     RawOutput {
+      flows: None,
       inscriptions: vec![Envelope {
         payload: Inscription {
           body: Some(vec![0, 1, 2, 3]),
@@ -64,6 +65,8 @@ fn from_file() {
         pushnum: false,
         stutter: false,
       }],
+      runestone: None,
+      runestone_flaws: Vec::new(),
     },
   );
 }
@@ -75,6 +78,7 @@ fn from_stdin() {
       .stdin(transaction())
       .run_and_deserialize_output::<RawOutput>(),
     RawOutput {
+      flows: None,
       inscriptions: vec![Envelope {
         payload: Inscription {
           body: Some(vec![0, 1, 2, 3]),
@@ -86,6 +90,8 @@ fn from_stdin() {
         pushnum: false,
         stutter: false,
       }],
+      runestone: None,
+      runestone_flaws: Vec::new(),
     },
   );
 }
@@ -106,6 +112,7 @@ fn from_core() {
       .bitcoin_rpc_server(&bitcoin_rpc_server)
       .run_and_deserialize_output::<RawOutput>(),
     RawOutput {
+      flows: None,
       inscriptions: vec![Envelope {
         payload: Inscription {
           body: Some(b"FOO".into()),
@@ -117,6 +124,8 @@ fn from_core() {
         pushnum: false,
         stutter: false,
       }],
+      runestone: None,
+      runestone_flaws: Vec::new(),
     },
   );
 }
@@ -128,6 +137,7 @@ fn compact() {
       .write("transaction.bin", transaction())
       .run_and_deserialize_output::<CompactOutput>(),
     CompactOutput {
+      flows: None,
       inscriptions: vec![CompactInscription {
         body: Some("00010203".into()),
         content_encoding: None,
@@ -140,6 +150,56 @@ fn compact() {
         pointer: None,
         unrecognized_even_field: false,
       }],
+      runestone: None,
+      runestone_flaws: Vec::new(),
+    },
+  );
+}
+
+#[test]
+fn runestone() {
+  let transaction = Transaction {
+    version: 2,
+    lock_time: LockTime::ZERO,
+    input: Vec::new(),
+    output: vec![TxOut {
+      script_pubkey: script::Builder::new()
+        .push_opcode(opcodes::all::OP_RETURN)
+        .push_slice(b"RUNE_TEST")
+        .push_slice([126, 0, 0, 1, 2, 3])
+        .into_script(),
+      value: 0,
+    }],
+  };
+
+  let mut buffer = Vec::new();
+
+  transaction.consensus_encode(&mut buffer).unwrap();
+
+  assert_eq!(
+    CommandBuilder::new("decode --file transaction.bin")
+      .write("transaction.bin", buffer)
+      .run_and_deserialize_output::<RawOutput>(),
+    RawOutput {
+      flows: None,
+      inscriptions: Vec::new(),
+      runestone: Some(Runestone {
+        burn: true,
+        edicts: vec![Edict {
+          id: 1,
+          amount: 2,
+          output: 3,
+        }],
+        ..Default::default()
+      }),
+      runestone_flaws: vec![
+        Flaw::EdictOutput {
+          edict: 0,
+          output: 3,
+          outputs: 1,
+        },
+        Flaw::BurnTag,
+      ],
     },
   );
 }
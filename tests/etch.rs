@@ -1,8 +1,8 @@
 use {
   super::*,
   ord::{
-    subcommand::wallet::{balance, etch::Output},
-    Rune,
+    subcommand::wallet::{balance, etch::Output, send},
+    MintEntry, Rune,
   },
 };
 
@@ -210,6 +210,158 @@ fn runes_can_be_etched() {
   assert_eq!(output.runes.unwrap()[&Rune(RUNE)], 10000);
 }
 
+#[test]
+fn runes_can_be_etched_with_open_mints() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet etch --rune {} --divisibility 1 --fee-rate 1 --supply 10 \
+     --symbol ¢ --mint-limit 100 --mint-term 5 --mint-deadline 10",
+    Rune(RUNE),
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  pretty_assert_eq!(
+    output.entry,
+    RuneEntry {
+      burned: 0,
+      divisibility: 1,
+      etching: output.transaction,
+      mint: Some(MintEntry {
+        deadline: Some(10),
+        end: Some(7),
+        limit: Some(1000),
+      }),
+      mints: 0,
+      number: 0,
+      rune: Rune(RUNE),
+      spacers: 0,
+      supply: 100,
+      symbol: Some('¢'),
+      timestamp: 1,
+    }
+  );
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let rune = serde_json::from_str::<api::Rune>(
+    &ord_rpc_server
+      .json_request(format!("/rune/{}", Rune(RUNE)))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  pretty_assert_eq!(
+    rune.entry,
+    RuneEntry {
+      timestamp: 2,
+      ..output.entry
+    }
+  );
+
+  let output = CommandBuilder::new("--regtest --index-runes wallet balance")
+    .bitcoin_rpc_server(&bitcoin_rpc_server)
+    .ord_rpc_server(&ord_rpc_server)
+    .run_and_deserialize_output::<balance::Output>();
+
+  assert_eq!(output.runes.unwrap()[&Rune(RUNE)], 100);
+}
+
+#[test]
+fn open_mint_etcher_allocation_is_optional() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet etch --rune {} --divisibility 0 --fee-rate 1 --supply 0 \
+     --symbol ¢ --mint-limit 100",
+    Rune(RUNE),
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.entry.supply, 0);
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  assert_eq!(runes(&bitcoin_rpc_server)[&Rune(RUNE)].supply, 0);
+
+  let output = CommandBuilder::new("--regtest --index-runes wallet balance")
+    .bitcoin_rpc_server(&bitcoin_rpc_server)
+    .ord_rpc_server(&ord_rpc_server)
+    .run_and_deserialize_output::<balance::Output>();
+
+  assert_eq!(output.runes, Some(BTreeMap::new()));
+}
+
+#[test]
+fn invalid_mint_terms_are_errors() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  for (args, error) in [
+    (
+      "--mint-limit 18446744073709551617",
+      "<MINT_LIMIT> must be equal to or less than 18446744073709551616",
+    ),
+    ("--mint-term 0", "<MINT_TERM> must be greater than 0"),
+    (
+      "--mint-term 4294967295",
+      "<MINT_TERM> 4294967295 would end after the maximum block height",
+    ),
+    ("--mint-deadline 1", "<MINT_DEADLINE> 1 has already passed"),
+    (
+      "--mint-limit 10 --supply 11",
+      "<SUPPLY> must be equal to or less than the mint limit for runes with open mints",
+    ),
+  ] {
+    CommandBuilder::new(format!(
+      "--index-runes --regtest wallet etch --rune {} --divisibility 0 --fee-rate 1 --symbol ¢ {}{}",
+      Rune(RUNE),
+      args,
+      if args.contains("--supply") {
+        ""
+      } else {
+        " --supply 0"
+      },
+    ))
+    .bitcoin_rpc_server(&bitcoin_rpc_server)
+    .ord_rpc_server(&ord_rpc_server)
+    .expected_stderr(format!("error: {error}\n"))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+  }
+}
+
 #[test]
 fn etch_sets_integer_fee_rate_correctly() {
   let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
@@ -478,3 +630,70 @@ fn send_inscription_does_not_select_runic_utxos() {
     .expected_exit_code(1)
     .run_and_extract_stdout();
 }
+
+#[test]
+fn send_inscription_can_select_runic_utxos_when_allowed() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks_with_subsidy(1, 10000);
+
+  CommandBuilder::new(
+    format!(
+    "--index-runes --regtest wallet etch --rune {} --divisibility 1 --fee-rate 0 --supply 1000 --symbol ¢",
+    Rune(RUNE),
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+    .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  bitcoin_rpc_server.mine_blocks_with_subsidy(1, 10000);
+
+  let inscribe =
+    CommandBuilder::new("--regtest --index-runes wallet inscribe --fee-rate 0 --file foo.txt")
+      .write("foo.txt", "FOO")
+      .bitcoin_rpc_server(&bitcoin_rpc_server)
+      .ord_rpc_server(&ord_rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
+
+  bitcoin_rpc_server.mine_blocks_with_subsidy(1, 0);
+
+  let send = CommandBuilder::new(format!("--regtest --index-runes wallet send --postage 10001sat --fee-rate 0 --spend-runic-utxos bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw {}", inscribe.inscriptions[0].id))
+    .bitcoin_rpc_server(&bitcoin_rpc_server)
+    .ord_rpc_server(&ord_rpc_server)
+    .run_and_deserialize_output::<send::Output>();
+
+  bitcoin_rpc_server.mine_blocks_with_subsidy(1, 0);
+
+  let transaction = bitcoin_rpc_server.tx(4, 1);
+
+  assert_eq!(transaction.txid(), send.txid);
+  assert_eq!(transaction.output.len(), 3);
+  assert_eq!(transaction.output[0].value, 10001);
+
+  assert_eq!(
+    Runestone::diagnose(&transaction).runestone,
+    Some(Runestone {
+      default_output: Some(1),
+      ..Default::default()
+    }),
+  );
+
+  let change = serde_json::from_str::<api::Output>(
+    &ord_rpc_server
+      .json_request(format!("/output/{}:1", send.txid))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(change.value, 9999);
+  assert_eq!(change.runes.len(), 1);
+  assert_eq!(change.runes[0].1.amount, 10000);
+}
@@ -90,3 +90,119 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn verify() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(3);
+
+  let output = CommandBuilder::new("--index-sats index verify --sample 10")
+    .bitcoin_rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::verify::Output>();
+
+  assert!(output.ok);
+
+  assert_eq!(
+    output
+      .checks
+      .keys()
+      .map(String::as_str)
+      .collect::<Vec<&str>>(),
+    [
+      "bitcoind",
+      "inscription_ids",
+      "inscription_numbers",
+      "sat_ranges",
+      "satpoints",
+    ]
+  );
+
+  assert_eq!(output.checks["sat_ranges"].checked, 4);
+}
+
+#[test]
+fn migrate_current_index_is_a_no_op() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  CommandBuilder::new(format!("--index {} index migrate", index_path.display()))
+    .bitcoin_rpc_server(&rpc_server)
+    .expected_stderr(format!("error: no index at `{}`\n", index_path.display()))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(format!("--index {} index update", index_path.display()))
+    .bitcoin_rpc_server(&rpc_server)
+    .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!(
+    "--index {} index migrate --dry-run",
+    index_path.display()
+  ))
+  .bitcoin_rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::migrate::Output>();
+
+  assert!(output.dry_run);
+  assert_eq!(output.from, output.to);
+  assert!(output.steps.is_empty());
+}
+
+#[test]
+fn snapshot_and_restore() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(2);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let snapshot = tempdir.path().join("snapshot");
+
+  let manifest = CommandBuilder::new(format!(
+    "--index-sats index snapshot {}",
+    snapshot.display()
+  ))
+  .bitcoin_rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Manifest>();
+
+  assert_eq!(manifest.height, Some(2));
+  assert!(manifest.index_sats);
+  assert!(!manifest.index_runes);
+
+  let index_path = tempdir.path().join("restored.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} index restore {}",
+    index_path.display(),
+    snapshot.display()
+  ))
+  .bitcoin_rpc_server(&rpc_server)
+  .expected_stderr("error: snapshot was built with `--index-sats` but ord is running without it\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index-sats --index {} index restore {}",
+      index_path.display(),
+      snapshot.display()
+    ))
+    .bitcoin_rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::snapshot::Manifest>(),
+    manifest,
+  );
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "--index-sats --index {} index verify",
+    index_path.display()
+  ))
+  .bitcoin_rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::verify::Output>();
+
+  assert!(output.ok);
+  assert_eq!(output.checks["sat_ranges"].checked, 4);
+}
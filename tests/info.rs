@@ -16,6 +16,7 @@ fn json_with_satoshi_index() {
   "metadata_bytes": \d+,
   "outputs_traversed": 1,
   "page_size": \d+,
+  "reorgs": \[\],
   "sat_ranges": 1,
   "stored_bytes": \d+,
   "tables": .*,
@@ -50,6 +51,7 @@ fn json_without_satoshi_index() {
   "metadata_bytes": \d+,
   "outputs_traversed": 0,
   "page_size": \d+,
+  "reorgs": \[\],
   "sat_ranges": 0,
   "stored_bytes": \d+,
   "tables": .*,
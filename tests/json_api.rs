@@ -164,6 +164,7 @@ fn get_inscription() {
       next: None,
       value: Some(10000),
       parent: None,
+      pending_transfer: None,
       previous: None,
       rune: None,
       sat: Some(Sat(50 * COIN_VALUE)),
@@ -390,6 +391,7 @@ fn get_block() {
       best_height: 1,
       height: 0,
       inscriptions: vec![],
+      runes: vec![],
     }
   );
 }
@@ -449,6 +451,9 @@ fn get_transaction() {
       chain: Chain::Mainnet,
       etching: None,
       inscription_count: 0,
+      pending: None,
+      runestone: None,
+      runestone_flaws: Vec::new(),
       transaction,
       txid,
     }
@@ -500,6 +505,8 @@ fn get_status() {
       inscriptions: 1,
       lost_sats: 0,
       minimum_rune_for_next_block: Rune(99218849511960410),
+      reorgs: Vec::new(),
+      replica_lag: None,
       rune_index: true,
       runes: 0,
       sat_index: true,
@@ -626,7 +633,11 @@ fn get_runes() {
             timestamp: 9,
           }
         )
-      ]
+      ],
+      more: false,
+      next: None,
+      page_index: 0,
+      prev: None,
     }
   );
 }
@@ -702,3 +713,43 @@ fn get_runes_balances() {
 
   pretty_assert_eq!(runes_balance_json, rune_balances);
 }
+
+#[test]
+fn get_rune_holders() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks(3);
+
+  let etch = etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let response = ord_rpc_server.json_request(format!("/rune/{}/holders", etch.rune));
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let holders: api::RuneHolders = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  pretty_assert_eq!(
+    holders.holders,
+    [api::RuneHolder {
+      address: None,
+      amount: 1000,
+      outpoint: OutPoint {
+        txid: etch.transaction,
+        vout: 1,
+      },
+    }]
+  );
+  assert_eq!(holders.held, 1000);
+  assert_eq!(holders.outputs, 1);
+  assert_eq!(holders.concentration.top_1, 1000);
+  assert!(!holders.more);
+  assert_eq!(holders.page_index, 0);
+}
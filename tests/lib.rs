@@ -62,6 +62,7 @@ mod info;
 mod json_api;
 mod list;
 mod parse;
+mod rune_name;
 mod runes;
 mod server;
 mod settings;
@@ -0,0 +1,73 @@
+use {super::*, ord::subcommand::rune_name::Output};
+
+#[test]
+fn flag_is_required() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  CommandBuilder::new("--regtest rune-name AAAAAAAAAAAAA")
+    .bitcoin_rpc_server(&rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: `ord rune-name` requires index created with `--index-runes` flag\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn names_are_checked_in_batch() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  let output = CommandBuilder::new(
+    "--regtest --index-runes rune-name AAAAAAAAAAAAA AAAAAAAAAAAAB AAAAAAAAAAAA AAAAAAAAAAAAAAAAAAAAAAAAAAA",
+  )
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.runes.len(), 4);
+
+  let etched = &output.runes[0];
+  assert_eq!(etched.rune.to_string(), "AAAAAAAAAAAAA");
+  assert_eq!(
+    etched.etched,
+    Some(RuneId {
+      height: 2,
+      index: 1
+    })
+  );
+  assert!(!etched.available);
+  assert!(!etched.reserved);
+  assert!(etched.unlocked);
+  assert_eq!(etched.unlock_height, Some(0));
+  assert_eq!(etched.unlock_time, Some(ord::timestamp(1296688602)));
+
+  let available = &output.runes[1];
+  assert_eq!(available.etched, None);
+  assert!(available.available);
+  assert!(available.unlocked);
+
+  let locked = &output.runes[2];
+  assert_eq!(locked.etched, None);
+  assert!(!locked.available);
+  assert!(!locked.unlocked);
+  assert_eq!(locked.unlock_height, Some(17499));
+  assert_eq!(
+    locked.unlock_time,
+    Some(ord::timestamp(2 + 600 * (17499 - 2)))
+  );
+
+  let reserved = &output.runes[3];
+  assert_eq!(reserved.etched, None);
+  assert!(!reserved.available);
+  assert!(reserved.reserved);
+  assert_eq!(reserved.unlock_height, None);
+  assert_eq!(reserved.unlock_time, None);
+}
@@ -1,4 +1,7 @@
-use {super::*, ord::subcommand::runes::Output};
+use {
+  super::*,
+  ord::subcommand::runes::{ListOutput, Output},
+};
 
 #[test]
 fn flag_is_required() {
@@ -148,3 +151,53 @@ fn two_runes() {
     }
   );
 }
+
+#[test]
+fn runes_can_be_sorted_filtered_and_paginated() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE + 1));
+
+  let runes = |args: &str| {
+    let output = CommandBuilder::new(format!("--index-runes --regtest runes {args}"))
+      .bitcoin_rpc_server(&bitcoin_rpc_server)
+      .run_and_deserialize_output::<ListOutput>();
+
+    (
+      output
+        .runes
+        .into_iter()
+        .map(|info| info.rune)
+        .collect::<Vec<Rune>>(),
+      output.more,
+    )
+  };
+
+  assert_eq!(
+    runes("--sort number"),
+    (vec![Rune(RUNE), Rune(RUNE + 1)], false)
+  );
+  assert_eq!(
+    runes("--order desc --page 0"),
+    (vec![Rune(RUNE + 1), Rune(RUNE)], false)
+  );
+  assert_eq!(
+    runes("--sort timestamp --order desc"),
+    (vec![Rune(RUNE + 1), Rune(RUNE)], false)
+  );
+  assert_eq!(
+    runes("--prefix AAAAAAAAAAAAB"),
+    (vec![Rune(RUNE + 1)], false)
+  );
+  assert_eq!(runes("--mintable"), (Vec::new(), false));
+  assert_eq!(runes("--page 0"), (vec![Rune(RUNE), Rune(RUNE + 1)], false));
+  assert_eq!(runes("--page 1"), (Vec::new(), false));
+}
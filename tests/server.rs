@@ -619,3 +619,85 @@ fn authentication() {
 
   child.kill().unwrap();
 }
+
+#[test]
+fn read_only_server_requires_existing_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("replica.redb");
+
+  CommandBuilder::new(format!(
+    "--index {} server --read-only",
+    index_path.display()
+  ))
+  .bitcoin_rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr(format!(
+    "error: no index at `{}`, read-only servers need an existing index, e.g. from `ord index restore`\n",
+    index_path.display()
+  ))
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn read_only_server_follows_snapshots() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+
+  let writer = tempdir.path().join("writer.redb");
+  let snapshots = tempdir.path().join("snapshots");
+
+  CommandBuilder::new(format!(
+    "--index {} index snapshot {}",
+    writer.display(),
+    snapshots.join("1").display()
+  ))
+  .bitcoin_rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Manifest>();
+
+  let server = TestServer::spawn_with_server_args(
+    &rpc_server,
+    &[],
+    &[
+      "--read-only",
+      "--snapshot-dir",
+      snapshots.to_str().unwrap(),
+      "--polling-interval",
+      "100ms",
+    ],
+  );
+
+  let status = |server: &TestServer| {
+    reqwest::blocking::Client::new()
+      .get(server.url().join("/status").unwrap())
+      .header(reqwest::header::ACCEPT, "application/json")
+      .send()
+      .unwrap()
+      .json::<serde_json::Value>()
+      .unwrap()
+  };
+
+  assert_eq!(status(&server)["height"], 1);
+  assert_eq!(status(&server)["replica_lag"], 0);
+
+  rpc_server.mine_blocks(1);
+
+  assert_eq!(status(&server)["replica_lag"], 1);
+
+  CommandBuilder::new(format!(
+    "--index {} index snapshot {}",
+    writer.display(),
+    snapshots.join("2").display()
+  ))
+  .bitcoin_rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::snapshot::Manifest>();
+
+  server.sync_server();
+
+  assert_eq!(status(&server)["height"], 2);
+  assert_eq!(status(&server)["replica_lag"], 0);
+}
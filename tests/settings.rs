@@ -10,6 +10,7 @@ fn default() {
   "bitcoin_rpc_password": null,
   "bitcoin_rpc_url": "127.0.0.1:8332",
   "bitcoin_rpc_username": null,
+  "bitcoin_zmq_url": null,
   "chain": "mainnet",
   "commit_interval": 5000,
   "config": null,
@@ -20,16 +21,23 @@ fn default() {
   "height_limit": null,
   "hidden": \[\],
   "index": ".*index\.redb",
+  "index_addresses": false,
   "index_cache_size": \d+,
+  "index_from_blk_files": false,
   "index_runes": false,
   "index_sats": false,
   "index_spent_sats": false,
   "index_transactions": false,
+  "index_transfers": false,
   "integration_test": false,
+  "max_savepoints": 2,
   "no_index_inscriptions": false,
+  "savepoint_chain_tip_distance": 21,
+  "savepoint_interval": 10,
   "server_password": null,
   "server_url": null,
-  "server_username": null
+  "server_username": null,
+  "webhooks": null
 \}
 "#,
     )
@@ -2,7 +2,7 @@ use {
   super::*,
   axum_server::Handle,
   bitcoincore_rpc::{Auth, Client, RpcApi},
-  ord::{parse_ord_server_args, Index},
+  ord::parse_ord_server_args,
   reqwest::blocking::Response,
 };
 
@@ -53,13 +53,18 @@ impl TestServer {
       ord_server_args.join(" "),
     ));
 
-    let index = Arc::new(Index::open(&settings).unwrap());
+    let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+    let index = Arc::new(server.open_index(&settings, Some(event_sender)).unwrap());
     let ord_server_handle = Handle::new();
 
     {
       let index = index.clone();
       let ord_server_handle = ord_server_handle.clone();
-      thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+      thread::spawn(|| {
+        server
+          .run(settings, index, event_receiver, ord_server_handle)
+          .unwrap()
+      });
     }
 
     for i in 0.. {
@@ -1,5 +1,6 @@
 use super::*;
 
+mod airdrop;
 mod authentication;
 mod balance;
 mod cardinals;
@@ -7,9 +8,11 @@ mod create;
 mod dump;
 mod inscribe;
 mod inscriptions;
+mod mint;
 mod outputs;
 mod receive;
 mod restore;
+mod runes;
 mod sats;
 mod send;
 mod transactions;
@@ -0,0 +1,217 @@
+use {super::*, ord::subcommand::wallet::airdrop::Output};
+
+const ADDRESS: &str = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+
+fn recipients(n: usize) -> String {
+  let mut csv = "address,amount\n".to_string();
+
+  for i in 0..n {
+    csv.push_str(&format!("{ADDRESS},{}\n", i + 1));
+  }
+
+  csv
+}
+
+#[test]
+fn airdrop_requires_rune_index() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server = TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  CommandBuilder::new(format!(
+    "--regtest wallet airdrop --fee-rate 1 --rune {} --recipients recipients.csv",
+    Rune(RUNE)
+  ))
+  .write("recipients.csv", recipients(1))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: `ord wallet airdrop` requires index created with `--index-runes` flag\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn airdrop_splits_recipients_across_transactions() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet airdrop --fee-rate 1 --rune {} --recipients recipients.csv",
+    Rune(RUNE)
+  ))
+  .write("recipients.csv", recipients(40))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert!(output.batches.len() > 1);
+  assert_eq!(output.remaining, 0);
+  assert_eq!(output.total.amount, 820);
+  assert_eq!(
+    output
+      .batches
+      .iter()
+      .map(|batch| batch.recipients)
+      .sum::<usize>(),
+    40
+  );
+
+  let mempool = bitcoin_rpc_server.mempool();
+
+  assert_eq!(
+    mempool.iter().map(|tx| tx.txid()).collect::<Vec<Txid>>(),
+    output
+      .batches
+      .iter()
+      .map(|batch| batch.txid)
+      .collect::<Vec<Txid>>(),
+  );
+
+  for (previous, next) in mempool.iter().zip(mempool.iter().skip(1)) {
+    assert_eq!(
+      next.input[0].previous_output,
+      OutPoint {
+        txid: previous.txid(),
+        vout: 1
+      }
+    );
+  }
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let mut amount = 0;
+
+  for batch in &output.batches {
+    for vout in 0..batch.recipients {
+      amount += 1;
+
+      let output = serde_json::from_str::<api::Output>(
+        &ord_rpc_server
+          .json_request(format!("/output/{}:{}", batch.txid, vout + 2))
+          .text()
+          .unwrap(),
+      )
+      .unwrap();
+
+      assert_eq!(output.address.as_deref(), Some(ADDRESS));
+      assert_eq!(output.runes.len(), 1);
+      assert_eq!(output.runes[0].1.amount, amount);
+    }
+  }
+
+  let change = serde_json::from_str::<api::Output>(
+    &ord_rpc_server
+      .json_request(format!("/output/{}:1", output.batches.last().unwrap().txid))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(change.runes[0].1.amount, 1000 - 820);
+}
+
+#[test]
+fn airdrop_stops_at_ancestor_limit() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  let mut csv = "address,amount\n".to_string();
+
+  for _ in 0..1000 {
+    csv.push_str(&format!("{ADDRESS},1\n"));
+  }
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet airdrop --fee-rate 1 --rune {} --recipients recipients.csv",
+    Rune(RUNE)
+  ))
+  .write("recipients.csv", csv)
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.batches.len(), 25);
+  assert_eq!(bitcoin_rpc_server.mempool().len(), 25);
+
+  let sent = output
+    .batches
+    .iter()
+    .map(|batch| batch.recipients)
+    .sum::<usize>();
+
+  assert!(output.remaining > 0);
+  assert_eq!(sent + output.remaining, 1000);
+  assert_eq!(output.total.amount, u128::try_from(sent).unwrap());
+}
+
+#[test]
+fn airdrop_dry_run_does_not_broadcast() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet airdrop --dry-run --fee-rate 1 --rune {} --recipients recipients.csv",
+    Rune(RUNE)
+  ))
+  .write("recipients.csv", recipients(40))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert!(output.batches.len() > 1);
+  assert!(output.batches.iter().all(|batch| !batch.psbt.is_empty()));
+  assert!(bitcoin_rpc_server.mempool().is_empty());
+}
+
+#[test]
+fn airdrop_with_insufficient_balance_is_an_error() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet airdrop --fee-rate 1 --rune {} --recipients recipients.csv",
+    Rune(RUNE)
+  ))
+  .write("recipients.csv", recipients(50))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: insufficient `AAAAAAAAAAAAA` balance, only 1000\u{00A0}¢ in wallet\n")
+  .run_and_extract_stdout();
+}
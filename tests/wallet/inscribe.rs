@@ -2597,3 +2597,104 @@ inscriptions:
          ),
   );
 }
+
+#[test]
+fn batch_etching_requires_rune_index() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server = TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  CommandBuilder::new("--regtest wallet inscribe --fee-rate 1 --batch batch.yaml")
+    .write("inscription.txt", "Hello World")
+    .write(
+      "batch.yaml",
+      format!(
+        "mode: shared-output\netching:\n  divisibility: 0\n  rune: {}\n  supply: 1000\n  symbol: ¢\ninscriptions:\n- file: inscription.txt\n",
+        Rune(RUNE),
+      ),
+    )
+    .bitcoin_rpc_server(&bitcoin_rpc_server)
+    .ord_rpc_server(&ord_rpc_server)
+    .expected_exit_code(1)
+    .expected_stderr("error: etching in a batch requires index created with `--index-runes` flag\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn batch_inscribe_with_etching() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(
+    "--regtest --index-runes wallet inscribe --fee-rate 1 --batch batch.yaml",
+  )
+  .write("logo.txt", "Hello World")
+  .write(
+    "batch.yaml",
+    format!(
+      "mode: shared-output\netching:\n  divisibility: 1\n  rune: {}\n  supply: 100.5\n  symbol: ¢\ninscriptions:\n- file: logo.txt\n",
+      "A•AAAAAAAAAAAA",
+    ),
+  )
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let premine = OutPoint {
+    txid: output.reveal,
+    vout: 1,
+  };
+
+  let rune = output.rune.unwrap();
+
+  assert_eq!(rune.location, Some(premine));
+  assert_eq!(rune.rune.to_string(), "A•AAAAAAAAAAAA");
+
+  let logo = output.inscriptions[0].id;
+
+  assert_eq!(logo.txid, output.reveal);
+
+  let inscription = serde_json::from_str::<api::Inscription>(
+    &ord_rpc_server
+      .json_request(format!("/inscription/{logo}"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(inscription.rune.unwrap().to_string(), "A•AAAAAAAAAAAA");
+
+  let premine_output = serde_json::from_str::<api::Output>(
+    &ord_rpc_server
+      .json_request(format!("/output/{premine}"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap();
+
+  assert_eq!(premine_output.runes.len(), 1);
+  assert_eq!(premine_output.runes[0].0.to_string(), "A•AAAAAAAAAAAA");
+  assert_eq!(premine_output.runes[0].1.amount, 1005);
+  assert_eq!(premine_output.inscriptions, Vec::new());
+
+  ord_rpc_server.assert_response_regex(
+    format!("/rune/{}", Rune(RUNE)),
+    format!(
+      ".*<dt>parent</dt>\\s*<dd><a class=monospace href=/inscription/{logo}>{logo}</a></dd>.*"
+    ),
+  );
+}
@@ -0,0 +1,228 @@
+use {
+  super::*,
+  bitcoin::Witness,
+  ord::{
+    subcommand::wallet::{balance, mint::Output},
+    Etching, Mint,
+  },
+};
+
+fn etch_open(
+  bitcoin_rpc_server: &test_bitcoincore_rpc::Handle,
+  ord_rpc_server: &TestServer,
+  mint: Mint,
+) {
+  create_wallet(bitcoin_rpc_server, ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Witness::new())],
+    op_return: Some(
+      Runestone {
+        etching: Some(Etching {
+          divisibility: 1,
+          mint: Some(mint),
+          rune: Some(Rune(RUNE)),
+          spacers: 0,
+          symbol: Some('¢'),
+        }),
+        ..Default::default()
+      }
+      .encipher(),
+    ),
+    ..Default::default()
+  });
+
+  bitcoin_rpc_server.mine_blocks(1);
+}
+
+#[test]
+fn flag_is_required() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server = TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  CommandBuilder::new(format!(
+    "--regtest wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: `ord wallet mint` requires index created with `--index-runes` flag\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn mint_open_rune() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  etch_open(
+    &bitcoin_rpc_server,
+    &ord_rpc_server,
+    Mint {
+      deadline: None,
+      limit: Some(1000),
+      term: None,
+    },
+  );
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.rune.to_string(), Rune(RUNE).to_string());
+  assert_eq!(output.pile.amount, 1000);
+  assert_eq!(output.pile.to_string(), "100\u{00A0}¢");
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  let balance = CommandBuilder::new("--index-runes --regtest wallet balance")
+    .bitcoin_rpc_server(&bitcoin_rpc_server)
+    .ord_rpc_server(&ord_rpc_server)
+    .run_and_deserialize_output::<balance::Output>();
+
+  assert_eq!(
+    balance.runes,
+    Some(vec![(Rune(RUNE), 1000)].into_iter().collect())
+  );
+
+  let transaction = bitcoin_rpc_server.tx(3, 1);
+
+  assert_eq!(transaction.txid(), output.transaction);
+
+  assert_eq!(
+    Runestone::diagnose(&transaction).runestone.unwrap().claim,
+    Some(
+      RuneId {
+        height: 2,
+        index: 1
+      }
+      .into()
+    )
+  );
+}
+
+#[test]
+fn mint_unknown_rune() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: rune `AAAAAAAAAAAAA` has not been etched\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn mint_closed_rune() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: rune `AAAAAAAAAAAAA` is not mintable\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn mint_after_term_end() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  etch_open(
+    &bitcoin_rpc_server,
+    &ord_rpc_server,
+    Mint {
+      deadline: None,
+      limit: Some(1000),
+      term: Some(2),
+    },
+  );
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: rune `AAAAAAAAAAAAA` mint ended at block 4\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn mint_after_deadline() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  etch_open(
+    &bitcoin_rpc_server,
+    &ord_rpc_server,
+    Mint {
+      deadline: Some(3),
+      limit: Some(1000),
+      term: None,
+    },
+  );
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(format!(
+    "--index-runes --regtest wallet mint --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: rune `AAAAAAAAAAAAA` mint deadline 3 has passed\n")
+  .run_and_extract_stdout();
+}
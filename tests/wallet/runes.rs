@@ -0,0 +1,224 @@
+use {
+  super::*,
+  ord::subcommand::wallet::runes::{consolidate, split},
+};
+
+fn output(ord_rpc_server: &TestServer, outpoint: OutPoint) -> api::Output {
+  serde_json::from_str(
+    &ord_rpc_server
+      .json_request(format!("/output/{outpoint}"))
+      .text()
+      .unwrap(),
+  )
+  .unwrap()
+}
+
+fn balances(ord_rpc_server: &TestServer, outpoint: OutPoint) -> Vec<(String, u128)> {
+  output(ord_rpc_server, outpoint)
+    .runes
+    .into_iter()
+    .map(|(spaced_rune, pile)| (spaced_rune.to_string(), pile.amount))
+    .collect()
+}
+
+#[test]
+fn consolidate_requires_rune_index() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server = TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  CommandBuilder::new(format!(
+    "--regtest wallet runes consolidate --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: `ord wallet runes consolidate` requires index created with `--index-runes` flag\n",
+  )
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn split_then_consolidate() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet runes consolidate --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: `AAAAAAAAAAAAA` is held in 1 output, nothing to consolidate\n")
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet runes split --fee-rate 1 --rune {} --amounts 600,500",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: insufficient `AAAAAAAAAAAAA` balance, only 1000\u{A0}¢ in wallet\n")
+  .run_and_extract_stdout();
+
+  let split = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet runes split --fee-rate 1 --rune {} --amounts 100,200,300",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<split::Output>();
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    split
+      .outputs
+      .iter()
+      .map(|(outpoint, pile)| (outpoint.vout, pile.amount))
+      .collect::<Vec<(u32, u128)>>(),
+    [(2, 100), (3, 200), (4, 300)],
+  );
+
+  for (vout, amount) in [(1, 400), (2, 100), (3, 200), (4, 300)] {
+    assert_eq!(
+      balances(
+        &ord_rpc_server,
+        OutPoint {
+          txid: split.txid,
+          vout
+        }
+      ),
+      [("AAAAAAAAAAAAA".to_string(), amount)],
+    );
+  }
+
+  let consolidate = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet runes consolidate --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<consolidate::Output>();
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  assert_eq!(consolidate.inputs.len(), 4);
+  assert_eq!(consolidate.total.amount, 1000);
+  assert_eq!(
+    consolidate.output,
+    OutPoint {
+      txid: consolidate.txid,
+      vout: 1
+    }
+  );
+
+  assert_eq!(
+    balances(&ord_rpc_server, consolidate.output),
+    [("AAAAAAAAAAAAA".to_string(), 1000)],
+  );
+
+  for input in consolidate.inputs {
+    assert!(output(&ord_rpc_server, input).spent);
+  }
+}
+
+#[test]
+fn consolidate_keeps_other_runes_separate() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  let ord_rpc_server =
+    TestServer::spawn_with_server_args(&bitcoin_rpc_server, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE));
+  etch(&bitcoin_rpc_server, &ord_rpc_server, Rune(RUNE + 1));
+
+  // merge both etchings into a single output holding both runes
+  let txid = bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+    inputs: &[(2, 1, 1, Default::default()), (4, 1, 1, Default::default())],
+    fee: 15_000,
+    ..Default::default()
+  });
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    balances(&ord_rpc_server, OutPoint { txid, vout: 0 }),
+    [
+      ("AAAAAAAAAAAAA".to_string(), 1000),
+      ("AAAAAAAAAAAAB".to_string(), 1000)
+    ],
+  );
+
+  let split = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet runes split --fee-rate 1 --rune {} --amounts 100,200",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<split::Output>();
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    balances(
+      &ord_rpc_server,
+      OutPoint {
+        txid: split.txid,
+        vout: 1
+      }
+    ),
+    [
+      ("AAAAAAAAAAAAA".to_string(), 700),
+      ("AAAAAAAAAAAAB".to_string(), 1000)
+    ],
+  );
+
+  let consolidate = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet runes consolidate --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .run_and_deserialize_output::<consolidate::Output>();
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  assert_eq!(consolidate.inputs.len(), 3);
+
+  assert_eq!(
+    balances(&ord_rpc_server, consolidate.output),
+    [("AAAAAAAAAAAAA".to_string(), 1000)],
+  );
+
+  assert_eq!(
+    balances(
+      &ord_rpc_server,
+      OutPoint {
+        txid: consolidate.txid,
+        vout: 2
+      }
+    ),
+    [("AAAAAAAAAAAAB".to_string(), 1000)],
+  );
+}
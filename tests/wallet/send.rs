@@ -450,6 +450,28 @@ fn send_btc_locks_inscriptions() {
   }))
 }
 
+#[test]
+fn send_btc_with_spend_runic_utxos_is_an_error() {
+  let bitcoin_rpc_server = test_bitcoincore_rpc::spawn();
+
+  let ord_rpc_server = TestServer::spawn_with_server_args(&bitcoin_rpc_server, &[], &[]);
+
+  create_wallet(&bitcoin_rpc_server, &ord_rpc_server);
+
+  bitcoin_rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "wallet send --fee-rate 1 --spend-runic-utxos bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .bitcoin_rpc_server(&bitcoin_rpc_server)
+  .ord_rpc_server(&ord_rpc_server)
+  .expected_stderr(
+    "error: `--spend-runic-utxos` can only be used when sending an inscription or satpoint\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
 #[test]
 fn send_btc_fails_if_lock_unspent_fails() {
   let bitcoin_rpc_server = test_bitcoincore_rpc::builder()